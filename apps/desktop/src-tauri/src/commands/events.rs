@@ -0,0 +1,91 @@
+//! Event Commands
+//!
+//! Live planning events streamed from the API (`GET /events/planning`)
+//! and forwarded to the webview as `planning-event`.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Event forwarded to the webview
+#[derive(Clone, Serialize)]
+pub struct PlanningEvent {
+    /// SSE event name: cells_changed, balances_changed, presence_changed
+    /// or resync
+    pub event: String,
+    pub data: serde_json::Value,
+}
+
+/// Subscribe to the planning events of a date range in the token's organization
+///
+/// The stream runs in the background until the API closes it; the
+/// webview then receives `planning-event-closed` and may subscribe again.
+#[tauri::command]
+pub async fn subscribe_planning_events(
+    app: AppHandle,
+    api_url: String,
+    token: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<(), String> {
+    let params: Vec<String> = [("from", from), ("to", to)]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+        .collect();
+    let url = format!(
+        "{}/events/planning?{}",
+        api_url.trim_end_matches('/'),
+        params.join("&")
+    );
+
+    let mut request = reqwest::Client::new()
+        .get(&url)
+        .header("Accept", "text/event-stream");
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    let mut response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Subscription failed: {}", response.status()));
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut buffer = String::new();
+
+        while let Ok(Some(chunk)) = response.chunk().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            // Events are separated by a blank line
+            while let Some(end) = buffer.find("\n\n") {
+                let block: String = buffer.drain(..end + 2).collect();
+                if let Some(event) = parse_event(&block) {
+                    let _ = app.emit("planning-event", event);
+                }
+            }
+        }
+
+        let _ = app.emit("planning-event-closed", ());
+    });
+
+    Ok(())
+}
+
+/// Parse one SSE block (comments and keep-alives are ignored)
+fn parse_event(block: &str) -> Option<PlanningEvent> {
+    let mut event = None;
+    let mut data = Vec::new();
+
+    for line in block.lines() {
+        if let Some(name) = line.strip_prefix("event:") {
+            event = Some(name.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.trim_start());
+        }
+    }
+
+    let data = data.join("\n");
+    Some(PlanningEvent {
+        event: event?,
+        data: serde_json::from_str(&data).unwrap_or(serde_json::Value::String(data)),
+    })
+}
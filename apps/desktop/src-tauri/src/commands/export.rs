@@ -0,0 +1,38 @@
+//! Export Commands
+//!
+//! Planning files downloaded from the API and saved locally.
+
+/// Download the planning workbook of an agent and save it to `path`
+///
+/// The workbook is generated by the API (`GET /exports/planning.xlsx`).
+/// Returns the path of the saved file.
+#[tauri::command]
+pub async fn export_planning_xlsx(
+    api_url: String,
+    token: Option<String>,
+    user_id: String,
+    year: i32,
+    path: String,
+) -> Result<String, String> {
+    let url = format!(
+        "{}/exports/planning.xlsx?userId={}&year={}",
+        api_url.trim_end_matches('/'),
+        user_id,
+        year
+    );
+
+    let mut request = reqwest::Client::new().get(&url);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Export failed: {}", response.status()));
+    }
+
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
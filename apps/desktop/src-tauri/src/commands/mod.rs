@@ -2,6 +2,9 @@
 //!
 //! IPC commands for the desktop application.
 
+pub mod events;
+pub mod export;
+
 /// Greet command (example)
 #[tauri::command]
 pub fn greet(name: &str) -> String {
@@ -23,6 +23,8 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::greet,
             commands::get_app_version,
+            commands::events::subscribe_planning_events,
+            commands::export::export_planning_xlsx,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,78 @@
+//! API Errors
+//!
+//! Error body returned by the handlers and the permission guards, with the
+//! mappings of the application errors.
+
+use axum::{http::StatusCode, Json};
+use serde::Serialize;
+
+use crate::api::middleware::Forbidden;
+use crate::application::ports::RepositoryError;
+use crate::domain::entities::edit_lock::EditLock;
+
+/// Error body
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+}
+
+/// Error of a handler
+pub type HandlerError = (StatusCode, Json<ErrorResponse>);
+
+/// Build an error
+pub fn error(status: StatusCode, code: &str, message: &str) -> HandlerError {
+    (
+        status,
+        Json(ErrorResponse {
+            code: code.to_string(),
+            message: message.to_string(),
+        }),
+    )
+}
+
+/// Log a database error and hide it from the client
+pub fn database_error(e: sqlx::Error) -> HandlerError {
+    tracing::error!("Database error: {:?}", e);
+    error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "DATABASE_ERROR",
+        "Failed to access the database",
+    )
+}
+
+/// Map an error of the repository ports
+pub fn repository_error(e: RepositoryError) -> HandlerError {
+    match e {
+        RepositoryError::NotFound(what) => error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            &format!("Not found: {}", what),
+        ),
+        RepositoryError::Duplicate(what) => error(
+            StatusCode::CONFLICT,
+            "DUPLICATE",
+            &format!("Already exists: {}", what),
+        ),
+        RepositoryError::Validation(message) => error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &message,
+        ),
+        RepositoryError::Database(e) => database_error(e),
+    }
+}
+
+/// Missing permission
+pub fn forbidden(e: Forbidden) -> HandlerError {
+    error(StatusCode::FORBIDDEN, "FORBIDDEN", &e.to_string())
+}
+
+/// Cells held by the edit locks of other planners
+pub fn locked_error(locks: Vec<EditLock>) -> HandlerError {
+    error(
+        StatusCode::LOCKED,
+        "EDIT_LOCKED",
+        &format!("Cells are locked by {}", locks[0].holder_name),
+    )
+}
@@ -9,8 +9,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::api::error::ErrorResponse;
+use crate::api::middleware::AuthUser;
 use crate::infrastructure::{
     auth::{jwt::JwtService, password::verify_password},
+    persistence::refresh_tokens::{self, Rotation},
     AppState,
 };
 
@@ -70,12 +73,6 @@ struct UserRow {
     role_name: Option<String>,
 }
 
-#[derive(Serialize)]
-pub struct ErrorResponse {
-    pub code: String,
-    pub message: String,
-}
-
 /// Login handler
 pub async fn login(
     State(state): State<AppState>,
@@ -148,9 +145,11 @@ pub async fn login(
         state.settings.jwt.refresh_expiry_secs,
     );
 
+    // Each login starts a session (refresh token family)
+    let session_id = Uuid::new_v4();
     let role_name = user.role_name.clone().unwrap_or_else(|| "agent".to_string());
     let tokens = jwt_service
-        .create_tokens(user.id, user.organization_id, &role_name)
+        .create_tokens(user.id, user.organization_id, &role_name, session_id)
         .map_err(|e| {
             tracing::error!("Token creation error: {:?}", e);
             (
@@ -162,6 +161,25 @@ pub async fn login(
             )
         })?;
 
+    refresh_tokens::create(
+        &state.db,
+        user.id,
+        session_id,
+        &tokens.refresh_token,
+        tokens.refresh_expires_at,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Database error storing refresh token: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                code: "DATABASE_ERROR".to_string(),
+                message: "An error occurred during login".to_string(),
+            }),
+        )
+    })?;
+
     // Update last login
     let _ = sqlx::query("UPDATE users SET last_login_at = NOW() WHERE id = $1")
         .bind(user.id)
@@ -205,7 +223,21 @@ pub struct RefreshResponse {
     pub expires_in: i64,
 }
 
+fn invalid_refresh_token() -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            code: "INVALID_TOKEN".to_string(),
+            message: "Invalid or expired refresh token".to_string(),
+        }),
+    )
+}
+
 /// Refresh token handler
+///
+/// The refresh token is rotated: the presented one stops working and a new
+/// one is returned. Presenting a token that was already rotated revokes
+/// its whole session.
 pub async fn refresh(
     State(state): State<AppState>,
     Json(body): Json<RefreshRequest>,
@@ -217,15 +249,11 @@ pub async fn refresh(
     );
 
     // Validate refresh token
-    let claims = jwt_service.validate_refresh_token(&body.refresh_token).map_err(|_| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(ErrorResponse {
-                code: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired refresh token".to_string(),
-            }),
-        )
-    })?;
+    let claims = jwt_service
+        .validate_refresh_token(&body.refresh_token)
+        .map_err(|_| invalid_refresh_token())?;
+    // Tokens issued before sessions were tracked cannot be rotated
+    let session_id = claims.sid.ok_or_else(invalid_refresh_token)?;
 
     // Get user role from database
     let role: Option<(String,)> = sqlx::query_as(
@@ -248,7 +276,7 @@ pub async fn refresh(
 
     // Create new tokens
     let tokens = jwt_service
-        .create_tokens(claims.sub, claims.org, &role_name)
+        .create_tokens(claims.sub, claims.org, &role_name, session_id)
         .map_err(|_| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -259,6 +287,45 @@ pub async fn refresh(
             )
         })?;
 
+    // Replace the presented token with the new one
+    let rotation = refresh_tokens::rotate(
+        &state.db,
+        &body.refresh_token,
+        &tokens.refresh_token,
+        tokens.refresh_expires_at,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Database error rotating refresh token: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                code: "DATABASE_ERROR".to_string(),
+                message: "Failed to refresh tokens".to_string(),
+            }),
+        )
+    })?;
+
+    match rotation {
+        Rotation::Rotated { .. } => {}
+        Rotation::Reused { user_id, family_id } => {
+            tracing::warn!(
+                "Refresh token reused for user {}; session {} revoked",
+                user_id,
+                family_id
+            );
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    code: "TOKEN_REUSED".to_string(),
+                    message: "Refresh token already used; the session has been revoked"
+                        .to_string(),
+                }),
+            ));
+        }
+        Rotation::Invalid => return Err(invalid_refresh_token()),
+    }
+
     Ok(Json(RefreshResponse {
         access_token: tokens.access_token,
         refresh_token: tokens.refresh_token,
@@ -266,58 +333,58 @@ pub async fn refresh(
     }))
 }
 
-/// Logout handler
-pub async fn logout(State(_state): State<AppState>) -> StatusCode {
-    // For stateless JWT, logout is handled client-side by deleting tokens
-    // Optionally, we could blacklist the token here
-    StatusCode::OK
+fn revoke_error(e: sqlx::Error) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::error!("Database error revoking refresh tokens: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            code: "DATABASE_ERROR".to_string(),
+            message: "Failed to log out".to_string(),
+        }),
+    )
 }
 
-/// Get current user handler
-pub async fn me(
+/// Logout handler
+///
+/// Revokes the refresh tokens of the current session. Access tokens are
+/// not stored and stay valid until they expire.
+pub async fn logout(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
-) -> Result<Json<UserResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Extract token from Authorization header
-    let auth_header = headers
-        .get(axum::http::header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    code: "MISSING_TOKEN".to_string(),
-                    message: "Authorization header required".to_string(),
-                }),
-            )
-        })?;
+    AuthUser(claims): AuthUser,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(session_id) = claims.sid {
+        refresh_tokens::revoke_family(&state.db, claims.sub, session_id)
+            .await
+            .map_err(revoke_error)?;
+    }
 
-    let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(ErrorResponse {
-                code: "INVALID_TOKEN".to_string(),
-                message: "Invalid authorization header format".to_string(),
-            }),
-        )
-    })?;
+    Ok(StatusCode::OK)
+}
 
-    let jwt_service = JwtService::new(
-        &state.settings.jwt.secret,
-        state.settings.jwt.access_expiry_secs,
-        state.settings.jwt.refresh_expiry_secs,
-    );
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutAllResponse {
+    /// Sessions whose refresh tokens were revoked
+    pub revoked_sessions: u64,
+}
 
-    let claims = jwt_service.validate_access_token(token).map_err(|_| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(ErrorResponse {
-                code: "INVALID_TOKEN".to_string(),
-                message: "Invalid or expired token".to_string(),
-            }),
-        )
-    })?;
+/// Log out everywhere: revoke every session of the current user
+pub async fn logout_all(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<LogoutAllResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let revoked_sessions = refresh_tokens::revoke_user(&state.db, claims.sub)
+        .await
+        .map_err(revoke_error)?;
+
+    Ok(Json(LogoutAllResponse { revoked_sessions }))
+}
 
+/// Get current user handler
+pub async fn me(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<UserResponse>, (StatusCode, Json<ErrorResponse>)> {
     // Fetch user from database
     let user: Option<UserRow> = sqlx::query_as(
         r#"
@@ -329,10 +396,11 @@ pub async fn me(
             r.name as role_name
         FROM users u
         LEFT JOIN roles r ON u.role_id = r.id
-        WHERE u.id = $1
+        WHERE u.id = $1 AND u.organization_id = $2
         "#,
     )
     .bind(claims.sub)
+    .bind(claims.org)
     .fetch_optional(&state.db)
     .await
     .map_err(|_| {
@@ -0,0 +1,227 @@
+//! Calendar Handlers
+//!
+//! ICS feeds of schedules, reached through a revocable secret URL token
+//! so that calendar apps can subscribe without a JWT.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::error::{database_error, error, forbidden, HandlerError};
+use crate::api::middleware::{Authorized, ManageCalendar};
+use crate::domain::entities::CalendarToken;
+use crate::infrastructure::export::{IcsCalendar, IcsEvent};
+use crate::infrastructure::persistence::{calendar_tokens, planning_data};
+use crate::infrastructure::AppState;
+
+/// Days of history included in a feed
+const FEED_PAST_DAYS: i64 = 90;
+/// Days ahead included in a feed
+const FEED_FUTURE_DAYS: i64 = 365;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarTokenResponse {
+    pub id: Uuid,
+    /// Agent of the feed (null for the team feed)
+    pub user_id: Option<Uuid>,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<CalendarToken> for CalendarTokenResponse {
+    fn from(token: CalendarToken) -> Self {
+        Self {
+            id: token.id,
+            user_id: token.user_id,
+            label: token.label,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCalendarTokenRequest {
+    /// Agent of the feed (team feed when omitted)
+    pub user_id: Option<Uuid>,
+    pub label: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCalendarTokenResponse {
+    pub token: CalendarTokenResponse,
+    /// Secret, shown only once
+    pub secret: String,
+    /// Subscription path, relative to the server
+    pub feed_path: String,
+}
+
+/// Create a feed token for an agent, or for the team
+pub async fn create_token(
+    State(state): State<AppState>,
+    auth: Authorized<ManageCalendar>,
+    Json(mut body): Json<CreateCalendarTokenRequest>,
+) -> Result<(StatusCode, Json<CreateCalendarTokenResponse>), HandlerError> {
+    let org_id = auth.claims.org;
+    body.user_id = auth.user_filter(body.user_id).map_err(forbidden)?;
+
+    if let Some(user_id) = body.user_id {
+        planning_data::find_agent(&state.db, org_id, user_id)
+            .await
+            .map_err(database_error)?
+            .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "User not found"))?;
+    }
+
+    let (token, secret) =
+        calendar_tokens::create(&state.db, org_id, body.user_id, body.label.as_deref())
+            .await
+            .map_err(database_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateCalendarTokenResponse {
+            token: token.into(),
+            feed_path: format!("/api/v1/calendar/feed/{}.ics", secret),
+            secret,
+        }),
+    ))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCalendarTokensQuery {
+    pub user_id: Option<Uuid>,
+}
+
+/// List feed tokens (secrets are never returned)
+pub async fn list_tokens(
+    State(state): State<AppState>,
+    auth: Authorized<ManageCalendar>,
+    Query(query): Query<ListCalendarTokensQuery>,
+) -> Result<Json<Vec<CalendarTokenResponse>>, HandlerError> {
+    let user_id = auth.user_filter(query.user_id).map_err(forbidden)?;
+    let tokens = calendar_tokens::list(&state.db, auth.claims.org, user_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+/// Revoke a feed token
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    auth: Authorized<ManageCalendar>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, HandlerError> {
+    let user_id = auth.user_filter(None).map_err(forbidden)?;
+    let revoked = calendar_tokens::revoke(&state.db, auth.claims.org, id, user_id)
+        .await
+        .map_err(database_error)?;
+
+    if !revoked {
+        return Err(error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "Calendar token not found or already revoked",
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ICS feed of a token (agent or team)
+///
+/// Covers the last 90 days and the next 365 days.
+pub async fn feed(
+    State(state): State<AppState>,
+    Path(secret): Path<String>,
+) -> Result<Response, HandlerError> {
+    let secret = secret.trim_end_matches(".ics");
+    let token = calendar_tokens::find_by_secret(&state.db, secret)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Calendar feed not found",
+            )
+        })?;
+    let org_id = token.organization_id;
+
+    let today = Utc::now().date_naive();
+    let (start, end) = (
+        today - Duration::days(FEED_PAST_DAYS),
+        today + Duration::days(FEED_FUTURE_DAYS),
+    );
+
+    let agents = match token.user_id {
+        Some(user_id) => planning_data::find_agent(&state.db, org_id, user_id)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .collect(),
+        None => planning_data::find_active_agents(&state.db, org_id)
+            .await
+            .map_err(database_error)?,
+    };
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, token.user_id)
+        .await
+        .map_err(database_error)?;
+    let holidays = planning_data::find_holidays(&state.db, org_id, start, end)
+        .await
+        .map_err(database_error)?;
+
+    let mut calendar = IcsCalendar::new(match (token.user_id, agents.first()) {
+        (Some(_), Some(agent)) => format!("Planning {} {}", agent.last_name, agent.first_name),
+        _ => "Planning équipe".to_string(),
+    });
+
+    for schedule in &schedules {
+        let Some(agent) = agents.iter().find(|a| a.id == schedule.user_id) else {
+            continue;
+        };
+        let Some(shift_type) = schedule
+            .shift_type_id
+            .and_then(|id| shift_types.iter().find(|st| st.id == id))
+        else {
+            continue;
+        };
+
+        let name = format!("{} {}", agent.last_name, agent.first_name);
+        let holiday = holidays
+            .iter()
+            .find(|h| h.date == schedule.date)
+            .map(|h| h.name.as_str());
+        calendar.events.push(IcsEvent::from_schedule(
+            schedule,
+            shift_type,
+            token.is_team_feed().then_some(name.as_str()),
+            holiday,
+        ));
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        calendar.render(),
+    )
+        .into_response())
+}
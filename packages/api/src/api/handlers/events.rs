@@ -0,0 +1,74 @@
+//! Event Handlers
+//!
+//! Server-sent events stream of planning changes, used by the web matrix
+//! and the desktop app to stay in sync without reloading.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::api::error::{error, HandlerError};
+use crate::api::middleware::{Authorized, ReadTeamSchedules};
+use crate::infrastructure::AppState;
+
+/// Interval of keep-alive comments on idle streams
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningEventsQuery {
+    /// First date of interest (open when omitted)
+    pub from: Option<NaiveDate>,
+    /// Last date of interest (open when omitted)
+    pub to: Option<NaiveDate>,
+}
+
+/// Live planning events of an organization and date range
+///
+/// Events: `cells_changed`, `balances_changed`, `presence_changed`, and
+/// `resync` when the client fell behind and must reload.
+pub async fn planning(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadTeamSchedules>,
+    Query(query): Query<PlanningEventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, HandlerError> {
+    let org_id = claims.org;
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err(error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "VALIDATION_ERROR",
+                "from must not be after to",
+            ));
+        }
+    }
+
+    let (from, to) = (query.from, query.to);
+    let stream =
+        BroadcastStream::new(state.events.subscribe()).filter_map(move |received| match received {
+            Ok(event) => event.within(org_id, from, to).map(|event| {
+                Ok(Event::default()
+                    .event(event.name())
+                    .json_data(&event)
+                    .unwrap_or_default())
+            }),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                tracing::warn!("Planning event subscriber lagged by {} events", skipped);
+                Some(Ok(Event::default()
+                    .event("resync")
+                    .data(skipped.to_string())))
+            }
+        });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(KEEP_ALIVE)))
+}
@@ -0,0 +1,215 @@
+//! Export Handlers
+//!
+//! Planning files generated from the database.
+
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::middleware::{Authorized, ReadSchedules, ReadTeamSchedules};
+use crate::domain::services::{DashboardBuilder, MonthlyStats};
+use crate::infrastructure::export::xlsx::MONTHS;
+use crate::infrastructure::export::{
+    PaperSize, PdfExporter, PlanningMatrix, PlanningWorkbook, XlsxExporter,
+};
+use crate::infrastructure::persistence::planning_data;
+use crate::infrastructure::AppState;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningExportQuery {
+    pub user_id: Uuid,
+    pub year: Option<i32>,
+}
+
+/// Planning workbook of an agent (planning_{year}.xlsx)
+pub async fn planning_xlsx(
+    State(state): State<AppState>,
+    auth: Authorized<ReadSchedules>,
+    Query(query): Query<PlanningExportQuery>,
+) -> Result<Response, HandlerError> {
+    let org_id = auth.claims.org;
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let agent = planning_data::find_agent(&state.db, org_id, query.user_id)
+        .await
+        .map_err(database_error)?
+        .filter(|agent| auth.covers(agent.id))
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "User not found"))?;
+
+    let periods = planning_data::find_periods(&state.db, org_id, year)
+        .await
+        .map_err(database_error)?;
+    let (start, end) = DashboardBuilder::date_range(year, &periods);
+
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, Some(agent.id))
+        .await
+        .map_err(database_error)?;
+    let holidays = planning_data::find_holidays(&state.db, org_id, start, end)
+        .await
+        .map_err(database_error)?;
+    let leave = planning_data::find_leave_entitlements(&state.db, org_id, Some(agent.id))
+        .await
+        .map_err(database_error)?;
+
+    let workbook = PlanningWorkbook {
+        year,
+        user_id: agent.id,
+        agent_name: format!("{} {}", agent.last_name, agent.first_name),
+        periods,
+        shift_types,
+        history,
+        schedules,
+        holidays,
+        leave,
+    };
+
+    let bytes = XlsxExporter::export(&workbook).map_err(|e| {
+        tracing::error!("XLSX export error: {:?}", e);
+        error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "EXPORT_ERROR",
+            "Failed to generate the workbook",
+        )
+    })?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", XlsxExporter::file_name(year)),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningPdfQuery {
+    pub year: Option<i32>,
+    /// Period number (1-13)
+    pub period: Option<i32>,
+    /// Calendar month (1-12)
+    pub month: Option<u32>,
+    #[serde(default)]
+    pub paper: PaperSize,
+}
+
+/// Printable planning matrix of a period or month (planning_{year}_{scope}.pdf)
+pub async fn planning_pdf(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadTeamSchedules>,
+    Query(query): Query<PlanningPdfQuery>,
+) -> Result<Response, HandlerError> {
+    let org_id = claims.org;
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let (title, scope, start, end) = match (query.period, query.month) {
+        (Some(number), None) => {
+            let periods = planning_data::find_periods(&state.db, org_id, year)
+                .await
+                .map_err(database_error)?;
+            let period = periods
+                .into_iter()
+                .find(|p| p.number == number)
+                .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+            (
+                format!("Planning {} {}", period.label(), year),
+                format!("P{:02}", number),
+                period.start_date,
+                period.end_date,
+            )
+        }
+        (None, Some(month)) if (1..=12).contains(&month) => {
+            let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+                error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "VALIDATION_ERROR",
+                    "Invalid year",
+                )
+            })?;
+            (
+                format!("Planning {} {}", MONTHS[month as usize - 1], year),
+                format!("{:02}", month),
+                start,
+                MonthlyStats::month_end(start),
+            )
+        }
+        _ => {
+            return Err(error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "VALIDATION_ERROR",
+                "Provide either a period (1-13) or a month (1-12)",
+            ))
+        }
+    };
+
+    let agents = planning_data::find_active_agents(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, None)
+        .await
+        .map_err(database_error)?;
+    let holidays = planning_data::find_holidays(&state.db, org_id, start, end)
+        .await
+        .map_err(database_error)?;
+
+    let matrix = PlanningMatrix {
+        title,
+        start_date: start,
+        end_date: end,
+        agents,
+        shift_types,
+        history,
+        schedules,
+        holidays,
+    };
+
+    let bytes = PdfExporter::export(&matrix, query.paper).map_err(|e| {
+        tracing::error!("PDF export error: {:?}", e);
+        error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "EXPORT_ERROR",
+            "Failed to generate the PDF",
+        )
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    PdfExporter::file_name(year, &scope)
+                ),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
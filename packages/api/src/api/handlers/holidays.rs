@@ -1,21 +1,252 @@
 //! Holiday Handlers
+//!
+//! Public holidays are generated from `HolidayCalculator` or entered by
+//! hand. Every change resyncs `schedules.is_holiday` on the affected dates
+//! and recalculates the balances of the agents planned on them.
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::infrastructure::AppState;
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::middleware::{Authorized, ManageHolidays, ReadHolidays};
+use crate::domain::entities::PublicHoliday;
+use crate::domain::services::HolidayCalculator;
+use crate::infrastructure::persistence::holidays;
+use crate::infrastructure::{events, AppState};
 
-pub async fn list(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Reject changes on a date of a closed period
+async fn ensure_open(state: &AppState, org_id: Uuid, date: NaiveDate) -> Result<(), HandlerError> {
+    let period = state
+        .repositories
+        .periods
+        .find_by_date(org_id, date)
+        .await
+        .map_err(|e| {
+            tracing::error!("Repository error: {:?}", e);
+            error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to access periods",
+            )
+        })?;
+
+    match period {
+        Some(period) if period.is_closed() => Err(error(
+            StatusCode::CONFLICT,
+            "PERIOD_CLOSED",
+            &format!("Period {} of {} is closed", period.label(), period.year),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Resync schedules on the dates and recalculate the affected balances
+///
+/// Returns the number of schedules whose holiday flag changed.
+async fn refresh(
+    state: &AppState,
+    org_id: Uuid,
+    dates: &[NaiveDate],
+) -> Result<usize, HandlerError> {
+    let changes = holidays::refresh_schedules(&state.db, org_id, dates)
+        .await
+        .map_err(database_error)?;
+    events::recalculate_balances(state, org_id, &changes).await;
+
+    Ok(changes.len())
+}
+
+/// Years accepted for holiday generation
+const YEARS: std::ops::RangeInclusive<i32> = 2000..=2100;
+
+/// Maximum number of years generated at once
+const MAX_GENERATED_YEARS: i32 = 10;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayListQuery {
+    /// All years when omitted
+    pub year: Option<i32>,
+}
+
+/// List the holidays of an organization
+pub async fn list(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadHolidays>,
+    Query(query): Query<HolidayListQuery>,
+) -> Result<Json<Vec<PublicHoliday>>, HandlerError> {
+    let org_id = claims.org;
+
+    let holidays = holidays::find_all(&state.db, org_id, query.year)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(holidays))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateHolidayRequest {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayChangeResponse {
+    pub holiday: PublicHoliday,
+    /// Schedules whose holiday flag changed
+    pub schedules_updated: usize,
 }
 
-pub async fn create(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Create a custom holiday
+pub async fn create(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageHolidays>,
+    Json(request): Json<CreateHolidayRequest>,
+) -> Result<(StatusCode, Json<HolidayChangeResponse>), HandlerError> {
+    let org_id = claims.org;
+
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > PublicHoliday::MAX_NAME_LENGTH {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &format!(
+                "name must be between 1 and {} characters",
+                PublicHoliday::MAX_NAME_LENGTH
+            ),
+        ));
+    }
+    ensure_open(&state, org_id, request.date).await?;
+
+    let holiday = holidays::create(&state.db, org_id, request.date, name)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            error(
+                StatusCode::CONFLICT,
+                "DUPLICATE",
+                &format!("A holiday already exists on {}", request.date),
+            )
+        })?;
+    let schedules_updated = refresh(&state, org_id, &[holiday.date]).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(HolidayChangeResponse {
+            holiday,
+            schedules_updated,
+        }),
+    ))
 }
 
-pub async fn delete(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Delete a holiday (generated or custom)
+pub async fn delete(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageHolidays>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<HolidayChangeResponse>, HandlerError> {
+    let org_id = claims.org;
+
+    let holiday = holidays::find(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Holiday not found"))?;
+    ensure_open(&state, org_id, holiday.date).await?;
+
+    if !holidays::delete(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?
+    {
+        return Err(error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "Holiday not found",
+        ));
+    }
+    let schedules_updated = refresh(&state, org_id, &[holiday.date]).await?;
+
+    Ok(Json(HolidayChangeResponse {
+        holiday,
+        schedules_updated,
+    }))
 }
 
-pub async fn generate(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateHolidaysRequest {
+    pub year: i32,
+    /// Last year to generate, inclusive (defaults to `year`)
+    pub to_year: Option<i32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateHolidaysResponse {
+    pub years: Vec<i32>,
+    /// Holidays created (dates that already had one are skipped)
+    pub created: Vec<PublicHoliday>,
+    pub skipped: usize,
+    /// Schedules whose holiday flag changed
+    pub schedules_updated: usize,
+}
+
+/// Generate the calculated holidays of one year or several
+///
+/// Idempotent: existing holidays, custom ones included, are kept.
+pub async fn generate(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageHolidays>,
+    Json(request): Json<GenerateHolidaysRequest>,
+) -> Result<Json<GenerateHolidaysResponse>, HandlerError> {
+    let org_id = claims.org;
+
+    let to_year = request.to_year.unwrap_or(request.year);
+    if !YEARS.contains(&request.year) || !YEARS.contains(&to_year) {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &format!(
+                "years must be between {} and {}",
+                YEARS.start(),
+                YEARS.end()
+            ),
+        ));
+    }
+    if to_year < request.year || to_year - request.year >= MAX_GENERATED_YEARS {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &format!(
+                "toYear must be within {} years after year",
+                MAX_GENERATED_YEARS - 1
+            ),
+        ));
+    }
+
+    let years: Vec<i32> = (request.year..=to_year).collect();
+    let created = holidays::generate(&state.db, org_id, &years)
+        .await
+        .map_err(database_error)?;
+
+    let calculated: usize = years
+        .iter()
+        .map(|y| HolidayCalculator::calculate_holidays(*y).len())
+        .sum();
+    let dates: Vec<_> = created.iter().map(|h| h.date).collect();
+    let schedules_updated = refresh(&state, org_id, &dates).await?;
+
+    Ok(Json(GenerateHolidaysResponse {
+        years,
+        skipped: calculated - created.len(),
+        created,
+        schedules_updated,
+    }))
 }
@@ -0,0 +1,174 @@
+//! Import Handlers
+//!
+//! Planning files read into the database.
+
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::handlers::schedules::{self, RejectedEntry};
+use crate::api::middleware::{Authorized, WriteSchedules};
+use crate::application::commands::create_schedule::CreateScheduleCommand;
+use crate::domain::entities::edit_lock::EditLock;
+use crate::domain::services::import_planner::{ImportConflict, UnknownCode};
+use crate::domain::services::ImportPlanner;
+use crate::infrastructure::import::XlsxImporter;
+use crate::infrastructure::persistence::planning_data::{self, AgentSummary};
+use crate::infrastructure::presence::{self, Editor};
+use crate::infrastructure::AppState;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningImportQuery {
+    /// Matricule or name of the agent (defaults to the CONFIGURATION sheet)
+    pub agent: Option<String>,
+    /// Year of the workbook (defaults to the year written in it)
+    pub year: Option<i32>,
+    /// Report only, without writing (default true)
+    pub dry_run: Option<bool>,
+    /// Replace days already holding another code
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanningImportResponse {
+    pub dry_run: bool,
+    pub year: i32,
+    pub agent: Option<AgentSummary>,
+    /// Agent labels matching no user
+    pub unknown_agents: Vec<String>,
+    /// Non-empty code cells read
+    pub days_read: usize,
+    pub to_write: usize,
+    pub unchanged: usize,
+    pub unknown_codes: Vec<UnknownCode>,
+    pub conflicts: Vec<ImportConflict>,
+    /// Rows written (0 on dry run)
+    pub written: usize,
+    /// Entries not written, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<RejectedEntry>,
+    /// Edit locks of other planners on the imported cells
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<EditLock>,
+}
+
+/// Import an agent's planning workbook (body: the .xlsx file)
+///
+/// Runs as a dry run unless `dryRun=false`, returning the report of
+/// unknown codes, unknown agents, conflicts with existing rows and edit
+/// locks held by other planners.
+pub async fn planning_xlsx(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Query(query): Query<PlanningImportQuery>,
+    body: Bytes,
+) -> Result<Json<PlanningImportResponse>, HandlerError> {
+    let org_id = claims.org;
+    let dry_run = query.dry_run.unwrap_or(true);
+
+    let workbook = XlsxImporter::read(&body, query.year).map_err(|e| {
+        error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &e.to_string(),
+        )
+    })?;
+
+    let label = query.agent.or(workbook.agent).ok_or_else(|| {
+        error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "No agent in the workbook. Pass agent=<matricule or name>.",
+        )
+    })?;
+    let agent = planning_data::find_agent_by_label(&state.db, org_id, &label)
+        .await
+        .map_err(database_error)?;
+
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let grammar = planning_data::find_shift_code_grammar(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    // Periods of the previous year may cover early January
+    let mut periods = planning_data::find_periods(&state.db, org_id, workbook.year - 1)
+        .await
+        .map_err(database_error)?;
+    periods.extend(
+        planning_data::find_periods(&state.db, org_id, workbook.year)
+            .await
+            .map_err(database_error)?,
+    );
+
+    let existing = match (&agent, workbook.days.first(), workbook.days.last()) {
+        (Some(agent), Some(first), Some(last)) => {
+            planning_data::find_schedules(&state.db, org_id, first.date, last.date, Some(agent.id))
+                .await
+                .map_err(database_error)?
+        }
+        _ => Vec::new(),
+    };
+
+    let user_id = agent.as_ref().map(|a| a.id).unwrap_or_default();
+    let plan = ImportPlanner::plan(
+        user_id,
+        &workbook.days,
+        &shift_types,
+        &grammar,
+        &existing,
+        &periods,
+        query.overwrite,
+    );
+
+    let mut written = 0;
+    let mut rejected = Vec::new();
+    let locks = if dry_run || plan.entries.is_empty() {
+        presence::check_write(&state, &editor, &plan.entries)
+            .await
+            .unwrap_or_else(|locks| locks)
+    } else {
+        if agent.is_none() {
+            return Err(error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "VALIDATION_ERROR",
+                &format!("Unknown agent: {}", label),
+            ));
+        }
+
+        let command = CreateScheduleCommand::bulk(org_id, Some(claims.sub), plan.entries.clone());
+        let (result, locks) = schedules::write(&state, command, &editor).await?;
+        written = result.schedules.len();
+        rejected = result.errors.into_iter().map(RejectedEntry::from).collect();
+        locks
+    };
+
+    Ok(Json(PlanningImportResponse {
+        dry_run,
+        year: workbook.year,
+        unknown_agents: if agent.is_none() {
+            vec![label]
+        } else {
+            Vec::new()
+        },
+        agent,
+        days_read: workbook.days.len(),
+        to_write: plan.entries.len(),
+        unchanged: plan.unchanged,
+        unknown_codes: plan.unknown_codes,
+        conflicts: plan.conflicts,
+        written,
+        rejected,
+        locks,
+    }))
+}
@@ -3,10 +3,18 @@
 //! HTTP request handlers for each endpoint.
 
 pub mod auth;
+pub mod calendar;
+pub mod events;
+pub mod exports;
 pub mod health;
 pub mod holidays;
+pub mod imports;
+pub mod payroll;
 pub mod periods;
+pub mod presence;
+pub mod roles;
 pub mod schedules;
 pub mod shift_types;
 pub mod statistics;
+pub mod suggestions;
 pub mod users;
@@ -0,0 +1,304 @@
+//! Payroll Handlers
+//!
+//! Wage code mapping and payroll exports of closed periods.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::middleware::{Authorized, ManagePayroll};
+use crate::domain::entities::payroll::{PayrollFormat, PayrollMapping};
+use crate::domain::entities::{PayrollBatch, Period};
+use crate::domain::services::PayrollCalculator;
+use crate::infrastructure::export::PayrollExporter;
+use crate::infrastructure::persistence::{payroll, planning_data};
+use crate::infrastructure::AppState;
+
+/// Maximum wage code length (fixed-width column)
+const MAX_WAGE_CODE: usize = 10;
+
+/// Wage code mapping of the organization
+pub async fn get_mapping(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManagePayroll>,
+) -> Result<Json<PayrollMapping>, HandlerError> {
+    let org_id = claims.org;
+    let mapping = payroll::find_mapping(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(mapping))
+}
+
+/// Replace the wage code mapping of the organization
+pub async fn update_mapping(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManagePayroll>,
+    Json(mapping): Json<PayrollMapping>,
+) -> Result<Json<PayrollMapping>, HandlerError> {
+    let org_id = claims.org;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    let validation_error = |message: String| {
+        error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &message,
+        )
+    };
+
+    let wage_codes = [
+        &mapping.worked_hours,
+        &mapping.night_hours,
+        &mapping.holiday_work_days,
+        &mapping.sunday_work_days,
+        &mapping.strike_days,
+        &mapping.leave_days,
+    ];
+    for wage_code in wage_codes.into_iter().chain(mapping.codes.values()) {
+        if wage_code.chars().count() > MAX_WAGE_CODE {
+            return Err(validation_error(format!(
+                "Wage code {} is longer than {} characters",
+                wage_code, MAX_WAGE_CODE
+            )));
+        }
+    }
+    for (code, wage_code) in &mapping.codes {
+        if !shift_types.iter().any(|st| &st.code == code) {
+            return Err(validation_error(format!("Unknown shift code {}", code)));
+        }
+        if wage_code.is_empty() {
+            return Err(validation_error(format!("Missing wage code for {}", code)));
+        }
+    }
+
+    payroll::save_mapping(&state.db, org_id, &mapping)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(mapping))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollBatchResponse {
+    pub id: Uuid,
+    pub format: PayrollFormat,
+    pub line_count: i32,
+    pub period_ids: Vec<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Download path of the file, relative to the server
+    pub file_path: String,
+}
+
+impl From<PayrollBatch> for PayrollBatchResponse {
+    fn from(batch: PayrollBatch) -> Self {
+        Self {
+            file_path: format!("/api/v1/payroll/batches/{}/file", batch.id),
+            id: batch.id,
+            format: batch.format,
+            line_count: batch.line_count,
+            period_ids: batch.period_ids,
+            created_by: batch.created_by,
+            created_at: batch.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePayrollBatchRequest {
+    pub period_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub format: PayrollFormat,
+    /// Allow periods already sent in a previous batch
+    #[serde(default)]
+    pub reexport: bool,
+}
+
+/// Export closed periods to payroll as a new batch
+pub async fn create_batch(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManagePayroll>,
+    Json(body): Json<CreatePayrollBatchRequest>,
+) -> Result<(StatusCode, Json<PayrollBatchResponse>), HandlerError> {
+    let org_id = claims.org;
+
+    if body.period_ids.is_empty() {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "Select at least one period",
+        ));
+    }
+
+    let mut periods: Vec<Period> = Vec::with_capacity(body.period_ids.len());
+    for id in &body.period_ids {
+        let period = planning_data::find_period(&state.db, org_id, *id)
+            .await
+            .map_err(database_error)?
+            .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+        if !period.is_closed() {
+            return Err(error(
+                StatusCode::CONFLICT,
+                "PERIOD_OPEN",
+                &format!(
+                    "Period {} {} must be closed before the payroll export",
+                    period.label(),
+                    period.year
+                ),
+            ));
+        }
+        if !periods.iter().any(|p| p.id == period.id) {
+            periods.push(period);
+        }
+    }
+    periods.sort_by_key(|p| p.start_date);
+    let period_ids: Vec<Uuid> = periods.iter().map(|p| p.id).collect();
+
+    if !body.reexport {
+        let exported = payroll::find_exported_periods(&state.db, &period_ids)
+            .await
+            .map_err(database_error)?;
+        if let Some(period) = periods.iter().find(|p| exported.contains(&p.id)) {
+            return Err(error(
+                StatusCode::CONFLICT,
+                "PERIOD_EXPORTED",
+                &format!(
+                    "Period {} {} was already exported to payroll",
+                    period.label(),
+                    period.year
+                ),
+            ));
+        }
+    }
+
+    let mapping = payroll::find_mapping(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let grammar = planning_data::find_shift_code_grammar(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    let mut lines = Vec::new();
+    for period in &periods {
+        let schedules = planning_data::find_schedules(
+            &state.db,
+            org_id,
+            period.start_date,
+            period.end_date,
+            None,
+        )
+        .await
+        .map_err(database_error)?;
+        // Agents deactivated since the period are still paid for it
+        let agents = planning_data::find_scheduled_agents(
+            &state.db,
+            org_id,
+            period.start_date,
+            period.end_date,
+        )
+        .await
+        .map_err(database_error)?;
+
+        for agent in &agents {
+            let totals = PayrollCalculator::totals(
+                period,
+                agent.id,
+                &schedules,
+                &shift_types,
+                &history,
+                &grammar,
+            );
+            lines.extend(PayrollCalculator::lines(
+                period,
+                &totals,
+                agent.matricule.as_deref(),
+                &format!("{} {}", agent.last_name, agent.first_name),
+                &mapping,
+            ));
+        }
+    }
+
+    let content = PayrollExporter::export(&lines, body.format);
+    let batch = payroll::create_batch(
+        &state.db,
+        org_id,
+        body.format,
+        &period_ids,
+        lines.len() as i32,
+        &content,
+        Some(claims.sub),
+    )
+    .await
+    .map_err(database_error)?;
+
+    Ok((StatusCode::CREATED, Json(batch.into())))
+}
+
+/// List the payroll batches of the organization
+pub async fn list_batches(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManagePayroll>,
+) -> Result<Json<Vec<PayrollBatchResponse>>, HandlerError> {
+    let org_id = claims.org;
+    let batches = payroll::list_batches(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(batches.into_iter().map(Into::into).collect()))
+}
+
+/// Download the file of a batch, as it was sent
+pub async fn batch_file(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManagePayroll>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, HandlerError> {
+    let org_id = claims.org;
+    let (format, content) = payroll::find_batch_content(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Payroll batch not found",
+            )
+        })?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                PayrollExporter::content_type(format).to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    PayrollExporter::file_name(id, format)
+                ),
+            ),
+        ],
+        content,
+    )
+        .into_response())
+}
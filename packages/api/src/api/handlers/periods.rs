@@ -1,21 +1,249 @@
 //! Period Handlers
+//!
+//! Periods are generated from the organization's anchor date
+//! (`organizations.year_start_date`); balances are computed live.
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::api::error::{database_error, error, repository_error, HandlerError};
+use crate::api::middleware::{Authorized, LockPeriods, ReadBalances, ReadPeriods, WritePeriods};
+use crate::application::queries::get_period_balances::{
+    GetPeriodBalancesHandler, GetPeriodBalancesQuery,
+};
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::{HourBankEntry, Period};
+use crate::infrastructure::persistence::hour_bank::{self, ClosePeriod};
+use crate::infrastructure::persistence::planning_data;
 use crate::infrastructure::AppState;
 
-pub async fn list(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Years accepted for period generation
+const YEARS: std::ops::RangeInclusive<i32> = 2000..=2100;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodListQuery {
+    /// Defaults to the current year
+    pub year: Option<i32>,
+}
+
+/// List the periods of a year
+pub async fn list(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadPeriods>,
+    Query(query): Query<PeriodListQuery>,
+) -> Result<Json<Vec<Period>>, HandlerError> {
+    let org_id = claims.org;
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let periods = state
+        .repositories
+        .periods
+        .find_by_year(org_id, year)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(periods))
+}
+
+/// Get a period
+pub async fn get(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadPeriods>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Period>, HandlerError> {
+    let period = state
+        .repositories
+        .periods
+        .find_by_id(claims.org, id)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+    Ok(Json(period))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodBalanceResponse {
+    pub user_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub hour_quota: i32,
+    pub balance: PeriodBalance,
+    pub validation: ValidationResponse,
+    /// Hours above (+) or below (-) quota, once the period is closed
+    pub hour_bank_delta: Option<f64>,
+    /// Running hour bank balance after this period, once closed
+    pub hour_bank_balance: Option<f64>,
+}
+
+/// Quota validation of a balance
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationResponse {
+    pub is_valid: bool,
+    pub status: &'static str,
+    /// Blocking errors (hours exceeded, missing RR)
+    pub errors: Vec<String>,
+    /// CH/RH/CV counts off target
+    pub warnings: Vec<String>,
+}
+
+/// Get agent balances for a period, with their quota validation
+pub async fn balances(
+    State(state): State<AppState>,
+    auth: Authorized<ReadBalances>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PeriodBalanceResponse>>, HandlerError> {
+    let result = GetPeriodBalancesHandler::new(state.repositories.clone())
+        .execute(&GetPeriodBalancesQuery {
+            organization_id: auth.claims.org,
+            period_id: id,
+        })
+        .await
+        .map_err(repository_error)?;
+
+    let hour_bank = hour_bank::find_period_entries(&state.db, auth.claims.org, id)
+        .await
+        .map_err(database_error)?;
+
+    // Callers limited to their own balance only get theirs
+    let balances = result
+        .agents
+        .into_iter()
+        .filter(|agent| auth.covers(agent.user.id))
+        .map(|agent| {
+            let entry = hour_bank.iter().find(|e| e.user_id == agent.user.id);
+            PeriodBalanceResponse {
+                user_id: agent.user.id,
+                first_name: agent.user.first_name,
+                last_name: agent.user.last_name,
+                hour_quota: result.period.hour_quota,
+                validation: ValidationResponse {
+                    is_valid: agent.validation.is_valid,
+                    status: agent.validation.status(),
+                    errors: agent.validation.error_messages(),
+                    warnings: agent.validation.warning_messages(),
+                },
+                balance: agent.balance,
+                hour_bank_delta: entry.map(|e| e.delta),
+                hour_bank_balance: entry.map(|e| e.balance),
+            }
+        })
+        .collect();
+
+    Ok(Json(balances))
 }
 
-pub async fn get(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratePeriodsRequest {
+    pub year: i32,
+}
+
+/// Generate the 13 periods of a year from the organization's anchor date
+///
+/// Idempotent: existing periods are kept, only missing ones are created
+/// (201 when any was created, 200 otherwise).
+pub async fn generate(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WritePeriods>,
+    Json(request): Json<GeneratePeriodsRequest>,
+) -> Result<(StatusCode, Json<Vec<Period>>), HandlerError> {
+    let org_id = claims.org;
+
+    if !YEARS.contains(&request.year) {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &format!("year must be between {} and {}", YEARS.start(), YEARS.end()),
+        ));
+    }
+
+    let existing = state
+        .repositories
+        .periods
+        .find_by_year(org_id, request.year)
+        .await
+        .map_err(repository_error)?;
+    let periods = state
+        .repositories
+        .periods
+        .generate_for_year(org_id, request.year)
+        .await
+        .map_err(repository_error)?;
+
+    let status = if periods.len() > existing.len() {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+
+    Ok((status, Json(periods)))
 }
 
-pub async fn balances(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosePeriodResponse {
+    pub period_id: Uuid,
+    pub closed_at: DateTime<Utc>,
+    pub hour_bank: Vec<HourBankEntry>,
 }
 
-pub async fn generate(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Close a period and record the hour bank of every active agent
+pub async fn close(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<LockPeriods>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ClosePeriodResponse>, HandlerError> {
+    let already_closed = || {
+        error(
+            StatusCode::CONFLICT,
+            "PERIOD_CLOSED",
+            "Period is already closed",
+        )
+    };
+
+    let period = planning_data::find_period(&state.db, claims.org, id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+    if period.is_closed() {
+        return Err(already_closed());
+    }
+
+    let entries = match hour_bank::close_period(&state.db, &period, Some(claims.sub))
+        .await
+        .map_err(database_error)?
+    {
+        ClosePeriod::Closed(entries) => entries,
+        ClosePeriod::AlreadyClosed => return Err(already_closed()),
+        ClosePeriod::EarlierPeriodOpen(label) => {
+            return Err(error(
+                StatusCode::CONFLICT,
+                "EARLIER_PERIOD_OPEN",
+                &format!("Period {} must be closed first", label),
+            ))
+        }
+    };
+
+    let closed_at = planning_data::find_period(&state.db, claims.org, id)
+        .await
+        .map_err(database_error)?
+        .and_then(|p| p.closed_at)
+        .unwrap_or_else(Utc::now);
+
+    Ok(Json(ClosePeriodResponse {
+        period_id: period.id,
+        closed_at,
+        hour_bank: entries,
+    }))
 }
@@ -0,0 +1,178 @@
+//! Presence Handlers
+//!
+//! Editors currently working on a period and their soft edit locks.
+//! Clients send a heartbeat while the period is open and may lock the
+//! whole period or some agent rows for a limited time.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::middleware::{Authorized, ReadPeriods, WriteSchedules};
+use crate::domain::entities::edit_lock::{EditLock, Presence};
+use crate::domain::entities::Period;
+use crate::infrastructure::persistence::planning_data;
+use crate::infrastructure::presence::{self, Editor};
+use crate::infrastructure::AppState;
+
+/// Period of the caller's organization
+async fn period(state: &AppState, org_id: Uuid, period_id: Uuid) -> Result<Period, HandlerError> {
+    planning_data::find_period(&state.db, org_id, period_id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceResponse {
+    pub period_id: Uuid,
+    pub editors: Vec<Presence>,
+    pub locks: Vec<EditLock>,
+}
+
+fn presence_response(state: &AppState, period: &Period) -> PresenceResponse {
+    PresenceResponse {
+        period_id: period.id,
+        editors: state.presence.editors(period.organization_id, period.id),
+        locks: state
+            .presence
+            .locks(period.organization_id, Some(period.id)),
+    }
+}
+
+/// Editors and locks of a period
+pub async fn get(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadPeriods>,
+    Path(period_id): Path<Uuid>,
+) -> Result<Json<PresenceResponse>, HandlerError> {
+    let period = period(&state, claims.org, period_id).await?;
+    Ok(Json(presence_response(&state, &period)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeartbeatRequest {
+    /// Agent rows being edited (whole period when omitted)
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Join a period or keep an editor present (send at least every minute)
+pub async fn heartbeat(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(period_id): Path<Uuid>,
+    Json(body): Json<HeartbeatRequest>,
+) -> Result<Json<PresenceResponse>, HandlerError> {
+    let period = period(&state, claims.org, period_id).await?;
+
+    let (_, changed) = state.presence.heartbeat(period.id, &editor, body.user_ids);
+    if changed {
+        presence::publish(&state, &period);
+    }
+
+    Ok(Json(presence_response(&state, &period)))
+}
+
+/// Leave a period
+pub async fn leave(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(period_id): Path<Uuid>,
+) -> Result<StatusCode, HandlerError> {
+    let period = period(&state, claims.org, period_id).await?;
+
+    if state.presence.leave(period.id, &editor) {
+        presence::publish(&state, &period);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcquireLockRequest {
+    /// Agent rows to lock (whole period when omitted)
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+    /// Lock duration (organization default when omitted)
+    pub ttl_minutes: Option<i64>,
+}
+
+/// Take a soft edit lock on a period or some agent rows
+///
+/// Taking the lock again extends it. Fails with 409 when another planner
+/// holds overlapping cells.
+pub async fn acquire_lock(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(period_id): Path<Uuid>,
+    Json(body): Json<AcquireLockRequest>,
+) -> Result<(StatusCode, Json<EditLock>), HandlerError> {
+    let period = period(&state, claims.org, period_id).await?;
+
+    if period.is_closed() {
+        return Err(error(
+            StatusCode::CONFLICT,
+            "PERIOD_CLOSED",
+            "Period is closed",
+        ));
+    }
+
+    let policy = planning_data::find_edit_lock_policy(&state.db, period.organization_id)
+        .await
+        .map_err(database_error)?;
+
+    let lock = state
+        .presence
+        .acquire(
+            &period,
+            body.user_ids,
+            &editor,
+            policy.ttl(body.ttl_minutes),
+        )
+        .map_err(|held| {
+            error(
+                StatusCode::CONFLICT,
+                "EDIT_LOCKED",
+                &format!(
+                    "{} is editing this period until {}",
+                    held.holder_name,
+                    held.expires_at.format("%H:%M")
+                ),
+            )
+        })?;
+
+    presence::publish(&state, &period);
+
+    Ok((StatusCode::CREATED, Json(lock)))
+}
+
+/// Release a lock held by the caller
+pub async fn release_lock(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path((period_id, lock_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, HandlerError> {
+    let period = period(&state, claims.org, period_id).await?;
+
+    state
+        .presence
+        .release(lock_id, &editor)
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Lock not found"))?;
+
+    presence::publish(&state, &period);
+
+    Ok(StatusCode::NO_CONTENT)
+}
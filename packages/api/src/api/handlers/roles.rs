@@ -0,0 +1,115 @@
+//! Role Handlers
+//!
+//! System roles and the custom roles of an organization
+//! (`ManageRolesHandler`), and the permission catalogue they draw from.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::api::error::{repository_error, HandlerError};
+use crate::api::middleware::{Authorized, ManageRoles, ReadRoles};
+use crate::application::commands::manage_roles::ManageRolesHandler;
+use crate::domain::entities::role::{CreateRole, UpdateRole};
+use crate::domain::entities::Role;
+use crate::domain::value_objects::Permission;
+use crate::infrastructure::AppState;
+
+/// List the roles of an organization (system roles first)
+pub async fn list(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadRoles>,
+) -> Result<Json<Vec<Role>>, HandlerError> {
+    let roles = state
+        .repositories
+        .roles
+        .find_all(claims.org)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(roles))
+}
+
+/// Get a role by ID
+pub async fn get(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadRoles>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Role>, HandlerError> {
+    let role = ManageRolesHandler::new(state.repositories.clone())
+        .find(claims.org, id)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(role))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionResponse {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Can be granted on the holder's own rows (`name:own`)
+    pub has_own_scope: bool,
+}
+
+/// Permission catalogue
+pub async fn permissions(_: Authorized<ReadRoles>) -> Json<Vec<PermissionResponse>> {
+    Json(
+        Permission::ALL
+            .into_iter()
+            .map(|p| PermissionResponse {
+                name: p.as_str(),
+                description: p.description(),
+                has_own_scope: p.has_own_scope(),
+            })
+            .collect(),
+    )
+}
+
+/// Create a custom role
+pub async fn create(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageRoles>,
+    Json(request): Json<CreateRole>,
+) -> Result<(StatusCode, Json<Role>), HandlerError> {
+    let role = ManageRolesHandler::new(state.repositories.clone())
+        .create(claims.org, request)
+        .await
+        .map_err(repository_error)?;
+
+    Ok((StatusCode::CREATED, Json(role)))
+}
+
+/// Rename a custom role or replace its permissions
+pub async fn update(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageRoles>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateRole>,
+) -> Result<Json<Role>, HandlerError> {
+    let role = ManageRolesHandler::new(state.repositories.clone())
+        .update(claims.org, id, request)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(role))
+}
+
+/// Delete a custom role no user holds
+pub async fn delete(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageRoles>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, HandlerError> {
+    ManageRolesHandler::new(state.repositories.clone())
+        .delete(claims.org, id)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,33 +1,546 @@
 //! Schedule Handlers
+//!
+//! Planning cells read and written through the application services
+//! (`CreateScheduleCommand`, `GetPlanningQuery`, `NormalizeVariantsCommand`).
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::infrastructure::AppState;
+use crate::api::error::{error, forbidden, locked_error, repository_error, HandlerError};
+use crate::api::middleware::{Authorized, Forbidden, ReadSchedules, WriteSchedules};
+use crate::application::commands::create_schedule::{
+    CreateScheduleCommand, CreateScheduleHandler, CreateScheduleResult, ScheduleError,
+};
+use crate::application::commands::normalize_variants::{
+    NormalizeVariantsCommand, NormalizeVariantsHandler,
+};
+use crate::application::queries::get_planning::{
+    GetPlanningHandler, GetPlanningQuery, PlanningMatrixResponse,
+};
+use crate::domain::entities::edit_lock::EditLock;
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::Schedule;
+use crate::domain::services::day_type_validator::DayTypeMismatch;
+use crate::domain::value_objects::{Permission, PermissionScope};
+use crate::infrastructure::presence::{self, Editor};
+use crate::infrastructure::{events, AppState};
 
-pub async fn list(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+async fn find_schedule(state: &AppState, org_id: Uuid, id: Uuid) -> Result<Schedule, HandlerError> {
+    state
+        .repositories
+        .schedules
+        .find_by_id(org_id, id)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Schedule not found"))
 }
 
-pub async fn matrix(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Check locks, run the command and notify subscribers
+///
+/// The editor's own edit locks do not apply. Every handler writing cells
+/// goes through here.
+pub(crate) async fn write(
+    state: &AppState,
+    command: CreateScheduleCommand,
+    editor: &Editor,
+) -> Result<(CreateScheduleResult, Vec<EditLock>), HandlerError> {
+    let locks = presence::check_write(state, editor, &command.entries)
+        .await
+        .map_err(locked_error)?;
+
+    let result = CreateScheduleHandler::new(state.repositories.clone())
+        .execute(&command)
+        .await
+        .map_err(repository_error)?;
+
+    let written: Vec<_> = result
+        .schedules
+        .iter()
+        .map(|s| CreateSchedule {
+            user_id: s.user_id,
+            shift_type_id: s.shift_type_id,
+            date: s.date,
+            notes: s.notes.clone(),
+        })
+        .collect();
+    events::schedules_written(state, command.organization_id, &written).await;
+
+    Ok((result, locks))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleListQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub user_id: Option<Uuid>,
+}
+
+/// List schedules of an organization (or one agent) in a date range
+pub async fn list(
+    State(state): State<AppState>,
+    auth: Authorized<ReadSchedules>,
+    Query(query): Query<ScheduleListQuery>,
+) -> Result<Json<Vec<Schedule>>, HandlerError> {
+    let org_id = auth.claims.org;
+
+    if query.from > query.to {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "from must not be after to",
+        ));
+    }
+
+    let schedules = match auth.user_filter(query.user_id).map_err(forbidden)? {
+        Some(user_id) => {
+            state
+                .repositories
+                .schedules
+                .find_by_user_range(org_id, user_id, query.from, query.to)
+                .await
+        }
+        None => {
+            state
+                .repositories
+                .schedules
+                .find_by_org_range(org_id, query.from, query.to)
+                .await
+        }
+    }
+    .map_err(repository_error)?;
+
+    Ok(Json(schedules))
+}
+
+/// Maximum number of days of a planning matrix (the year view)
+const MAX_MATRIX_DAYS: i64 = 366;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixQuery {
+    /// First day (defaults to the period start)
+    pub start_date: Option<NaiveDate>,
+    /// Last day (defaults to the period end)
+    pub end_date: Option<NaiveDate>,
+    pub period_id: Option<Uuid>,
+    /// Comma-separated agent IDs (all active agents when omitted)
+    pub user_ids: Option<String>,
+}
+
+/// Planning matrix: one row per agent, one cell per day
+pub async fn matrix(
+    State(state): State<AppState>,
+    auth: Authorized<ReadSchedules>,
+    Query(query): Query<MatrixQuery>,
+) -> Result<Json<PlanningMatrixResponse>, HandlerError> {
+    let org_id = auth.claims.org;
+
+    let user_ids = query
+        .user_ids
+        .as_deref()
+        .map(|ids| {
+            ids.split(',')
+                .filter(|id| !id.trim().is_empty())
+                .map(|id| id.trim().parse::<Uuid>())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+        .map_err(|_| {
+            error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "VALIDATION_ERROR",
+                "userIds must be comma-separated UUIDs",
+            )
+        })?;
+
+    // Callers limited to their own schedule only get their row
+    let user_ids = match user_ids {
+        Some(ids) if ids.iter().any(|id| !auth.covers(*id)) => {
+            return Err(forbidden(Forbidden(Permission::SchedulesRead)))
+        }
+        None if auth.scope == PermissionScope::Own => Some(vec![auth.claims.sub]),
+        user_ids => user_ids,
+    };
+
+    let period = match query.period_id {
+        Some(period_id) => Some(
+            state
+                .repositories
+                .periods
+                .find_by_id(org_id, period_id)
+                .await
+                .map_err(repository_error)?
+                .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?,
+        ),
+        None => None,
+    };
+
+    let (Some(start_date), Some(end_date)) = (
+        query.start_date.or(period.as_ref().map(|p| p.start_date)),
+        query.end_date.or(period.as_ref().map(|p| p.end_date)),
+    ) else {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "Pass periodId or both startDate and endDate",
+        ));
+    };
+    if start_date > end_date {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "startDate must not be after endDate",
+        ));
+    }
+    if (end_date - start_date).num_days() >= MAX_MATRIX_DAYS {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &format!("The matrix spans at most {} days", MAX_MATRIX_DAYS),
+        ));
+    }
+
+    let planning = GetPlanningHandler::new(state.repositories.clone())
+        .execute(&GetPlanningQuery {
+            organization_id: org_id,
+            start_date,
+            end_date,
+            user_ids,
+            period_id: query.period_id,
+        })
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(planning))
 }
 
-pub async fn get(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Get a schedule by ID
+pub async fn get(
+    State(state): State<AppState>,
+    auth: Authorized<ReadSchedules>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Schedule>, HandlerError> {
+    let schedule = find_schedule(&state, auth.claims.org, id).await?;
+    if !auth.covers(schedule.user_id) {
+        return Err(error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "Schedule not found",
+        ));
+    }
+    Ok(Json(schedule))
 }
 
-pub async fn create(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateScheduleRequest {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    /// None for an empty cell
+    pub shift_type_id: Option<Uuid>,
+    pub notes: Option<String>,
 }
 
-pub async fn update(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Write one cell (created, or replaced when the day already has one)
+pub async fn create(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Json(body): Json<CreateScheduleRequest>,
+) -> Result<(StatusCode, Json<Schedule>), HandlerError> {
+    let org_id = claims.org;
+
+    let mut command = CreateScheduleCommand::single(
+        org_id,
+        Some(claims.sub),
+        body.user_id,
+        body.date,
+        body.shift_type_id,
+    );
+    command.entries[0].notes = body.notes;
+
+    let (mut result, _) = write(&state, command, &editor).await?;
+    if let Some(rejected) = result.errors.first() {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &rejected.message,
+        ));
+    }
+
+    let status = if result.created > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(result.schedules.remove(0))))
 }
 
-pub async fn bulk_update(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateScheduleRequest {
+    /// New shift type (null clears the cell)
+    pub shift_type_id: Option<Uuid>,
+    pub notes: Option<String>,
 }
 
-pub async fn delete(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Change the shift type of a cell
+pub async fn update(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateScheduleRequest>,
+) -> Result<Json<Schedule>, HandlerError> {
+    let schedule = find_schedule(&state, claims.org, id).await?;
+
+    let mut command = CreateScheduleCommand::single(
+        schedule.organization_id,
+        Some(claims.sub),
+        schedule.user_id,
+        schedule.date,
+        body.shift_type_id,
+    );
+    command.entries[0].notes = body.notes;
+
+    let (mut result, _) = write(&state, command, &editor).await?;
+    if let Some(rejected) = result.errors.first() {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &rejected.message,
+        ));
+    }
+
+    Ok(Json(result.schedules.remove(0)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleEntry {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub shift_type_id: Option<Uuid>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkScheduleRequest {
+    pub schedules: Vec<ScheduleEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedEntry {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub message: String,
+}
+
+impl From<ScheduleError> for RejectedEntry {
+    fn from(e: ScheduleError) -> Self {
+        Self {
+            user_id: e.user_id,
+            date: e.date,
+            message: e.message,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkScheduleResponse {
+    pub created: usize,
+    pub updated: usize,
+    /// Entries not written, with the reason
+    pub errors: Vec<RejectedEntry>,
+    pub schedules: Vec<Schedule>,
+    /// Edit locks of other planners on the written cells
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<EditLock>,
+}
+
+/// Write many cells at once (drag & drop, fill)
+///
+/// Valid entries are written; the others are reported in `errors`.
+pub async fn bulk_update(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Json(body): Json<BulkScheduleRequest>,
+) -> Result<Json<BulkScheduleResponse>, HandlerError> {
+    let org_id = claims.org;
+
+    let entries = body
+        .schedules
+        .into_iter()
+        .map(|e| CreateSchedule {
+            user_id: e.user_id,
+            shift_type_id: e.shift_type_id,
+            date: e.date,
+            notes: e.notes,
+        })
+        .collect();
+    let command = CreateScheduleCommand::bulk(org_id, Some(claims.sub), entries);
+
+    let (result, locks) = write(&state, command, &editor).await?;
+
+    Ok(Json(BulkScheduleResponse {
+        created: result.created,
+        updated: result.updated,
+        errors: result.errors.into_iter().map(RejectedEntry::from).collect(),
+        schedules: result.schedules,
+        locks,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizeVariantsRequest {
+    /// First day (defaults to the period start)
+    pub start_date: Option<NaiveDate>,
+    /// Last day (defaults to the period end)
+    pub end_date: Option<NaiveDate>,
+    pub period_id: Option<Uuid>,
+    /// Agents to normalize (all when omitted)
+    pub user_ids: Option<Vec<Uuid>>,
+    /// Report the changes without writing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizeVariantsResponse {
+    pub dry_run: bool,
+    /// Cells rewritten (or to rewrite) with the variant fitting their day
+    pub normalized: Vec<DayTypeMismatch>,
+    /// Mismatched cells left unchanged: closed period, or the expected
+    /// code is not an active shift type
+    pub unresolved: Vec<DayTypeMismatch>,
+    /// Edit locks of other planners on the written cells
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<EditLock>,
+}
+
+/// Replace the code variants that do not fit their day (bulk fix)
+///
+/// 7101 on a weekday becomes 101, 101 on a holiday becomes 7101, following
+/// the organization's code grammar.
+pub async fn normalize_variants(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Json(body): Json<NormalizeVariantsRequest>,
+) -> Result<Json<NormalizeVariantsResponse>, HandlerError> {
+    let org_id = claims.org;
+
+    let period = match body.period_id {
+        Some(period_id) => Some(
+            state
+                .repositories
+                .periods
+                .find_by_id(org_id, period_id)
+                .await
+                .map_err(repository_error)?
+                .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?,
+        ),
+        None => None,
+    };
+    let (Some(start_date), Some(end_date)) = (
+        body.start_date.or(period.as_ref().map(|p| p.start_date)),
+        body.end_date.or(period.as_ref().map(|p| p.end_date)),
+    ) else {
+        return Err(error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            "Pass periodId or both startDate and endDate",
+        ));
+    };
+
+    let command = NormalizeVariantsCommand {
+        organization_id: org_id,
+        start_date,
+        end_date,
+        user_ids: body.user_ids,
+        updated_by: Some(claims.sub),
+    };
+    let handler = NormalizeVariantsHandler::new(state.repositories.clone());
+    let preview = handler.preview(&command).await.map_err(repository_error)?;
+    if body.dry_run || preview.normalized.is_empty() {
+        return Ok(Json(NormalizeVariantsResponse {
+            dry_run: body.dry_run,
+            normalized: preview.normalized,
+            unresolved: preview.unresolved,
+            locks: Vec::new(),
+        }));
+    }
+
+    let locks = presence::check_write(&state, &editor, &preview.entries())
+        .await
+        .map_err(locked_error)?;
+    let result = handler.execute(&command).await.map_err(repository_error)?;
+    events::schedules_written(&state, org_id, &result.entries()).await;
+
+    Ok(Json(NormalizeVariantsResponse {
+        dry_run: false,
+        normalized: result.normalized,
+        unresolved: result.unresolved,
+        locks,
+    }))
+}
+
+/// Delete a cell
+pub async fn delete(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, HandlerError> {
+    let schedule = find_schedule(&state, claims.org, id).await?;
+
+    if let Some(period_id) = schedule.period_id {
+        let period = state
+            .repositories
+            .periods
+            .find_by_id(claims.org, period_id)
+            .await
+            .map_err(repository_error)?;
+        if period.is_some_and(|p| p.is_closed()) {
+            return Err(error(
+                StatusCode::CONFLICT,
+                "PERIOD_CLOSED",
+                "Period is closed",
+            ));
+        }
+    }
+
+    let cleared = [CreateSchedule {
+        user_id: schedule.user_id,
+        shift_type_id: None,
+        date: schedule.date,
+        notes: None,
+    }];
+    presence::check_write(&state, &editor, &cleared)
+        .await
+        .map_err(locked_error)?;
+
+    state
+        .repositories
+        .schedules
+        .delete(claims.org, id)
+        .await
+        .map_err(repository_error)?;
+
+    events::schedules_written(&state, schedule.organization_id, &cleared).await;
+
+    Ok(StatusCode::NO_CONTENT)
 }
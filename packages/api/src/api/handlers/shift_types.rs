@@ -1,25 +1,392 @@
 //! Shift Type Handlers
+//!
+//! Shift codes of an organization, validated against its code grammar
+//! and the `Color` value object (`ManageShiftTypesHandler`). Hour attributes are
+//! versioned by effective date; balances of the schedules a change affects
+//! are recalculated. Remapping moves schedules from one code to another
+//! (`RemapShiftTypeHandler`).
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::infrastructure::AppState;
+use crate::api::error::{database_error, error, locked_error, repository_error, HandlerError};
+use crate::api::middleware::{Authorized, ManageShiftTypes, ReadShiftTypes};
+use crate::application::commands::manage_shift_types::{
+    DeleteShiftTypeResult, ManageShiftTypesHandler,
+};
+use crate::application::commands::remap_shift_type::{
+    RemapPreview, RemapShiftTypeCommand, RemapShiftTypeHandler,
+};
+use crate::domain::entities::edit_lock::EditLock;
+use crate::domain::entities::shift_type::{CreateShiftType, ShiftCategory, UpdateShiftType};
+use crate::domain::entities::{ShiftCodeGrammar, ShiftType};
+use crate::infrastructure::auth::jwt::Claims;
+use crate::infrastructure::persistence::planning_data;
+use crate::infrastructure::presence::{self, Editor};
+use crate::infrastructure::{events, AppState};
 
-pub async fn list(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShiftTypeListQuery {
+    /// Include deactivated types (default: false)
+    #[serde(default)]
+    pub include_inactive: bool,
 }
 
-pub async fn get(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// List the shift types of an organization, in display order
+pub async fn list(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadShiftTypes>,
+    Query(query): Query<ShiftTypeListQuery>,
+) -> Result<Json<Vec<ShiftType>>, HandlerError> {
+    let org_id = claims.org;
+
+    let shift_types = if query.include_inactive {
+        state.repositories.shift_types.find_all(org_id).await
+    } else {
+        state.repositories.shift_types.find_active(org_id).await
+    }
+    .map_err(repository_error)?;
+
+    Ok(Json(shift_types))
+}
+
+/// Code grammar of the organization (families, prefixes, standalone codes)
+pub async fn get_grammar(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadShiftTypes>,
+) -> Result<Json<ShiftCodeGrammar>, HandlerError> {
+    let org_id = claims.org;
+
+    let grammar = state
+        .repositories
+        .shift_types
+        .find_code_grammar(org_id)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(grammar))
+}
+
+/// Replace the code grammar of the organization
+///
+/// Existing shift types are kept; the grammar applies to new codes,
+/// imports, night credits and holiday detection.
+pub async fn update_grammar(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Json(grammar): Json<ShiftCodeGrammar>,
+) -> Result<Json<ShiftCodeGrammar>, HandlerError> {
+    let org_id = claims.org;
+
+    grammar.validate().map_err(|message| {
+        error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "VALIDATION_ERROR",
+            &message,
+        )
+    })?;
+    planning_data::save_shift_code_grammar(&state.db, org_id, &grammar)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(grammar))
+}
+
+/// Get a shift type
+pub async fn get(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadShiftTypes>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ShiftType>, HandlerError> {
+    let shift_type = state
+        .repositories
+        .shift_types
+        .find_by_id(claims.org, id)
+        .await
+        .map_err(repository_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Shift type not found"))?;
+
+    Ok(Json(shift_type))
 }
 
-pub async fn create(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Create a shift type
+pub async fn create(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Json(request): Json<CreateShiftType>,
+) -> Result<(StatusCode, Json<ShiftType>), HandlerError> {
+    let org_id = claims.org;
+
+    let shift_type = ManageShiftTypesHandler::new(state.repositories.clone())
+        .create(org_id, request)
+        .await
+        .map_err(repository_error)?;
+
+    Ok((StatusCode::CREATED, Json(shift_type)))
 }
 
-pub async fn update(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Update a shift type
+pub async fn update(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateShiftType>,
+) -> Result<Json<ShiftType>, HandlerError> {
+    let result = ManageShiftTypesHandler::new(state.repositories.clone())
+        .update(claims.org, id, request)
+        .await
+        .map_err(repository_error)?;
+
+    events::recalculate_balances(&state, claims.org, &result.changed_schedules).await;
+
+    Ok(Json(result.shift_type))
 }
 
-pub async fn delete(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShiftTypeVersionResponse {
+    pub effective_from: NaiveDate,
+    /// Last day of the version (None for the current one)
+    pub effective_to: Option<NaiveDate>,
+    pub category: ShiftCategory,
+    pub duration_hours: f64,
+    pub night_hours: f64,
+    pub is_countable: bool,
+    pub requires_recovery: bool,
+    pub is_holiday_indicator: bool,
+    pub is_rest_day: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShiftTypeHistoryResponse {
+    pub shift_type: ShiftType,
+    /// Oldest first
+    pub versions: Vec<ShiftTypeVersionResponse>,
+}
+
+/// Definitions of a shift type over time
+pub async fn history(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadShiftTypes>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ShiftTypeHistoryResponse>, HandlerError> {
+    let (shift_type, versions) = ManageShiftTypesHandler::new(state.repositories.clone())
+        .history(claims.org, id)
+        .await
+        .map_err(repository_error)?;
+
+    let ends: Vec<_> = versions
+        .iter()
+        .skip(1)
+        .map(|v| Some(v.effective_from - Duration::days(1)))
+        .chain([None])
+        .collect();
+    let versions = versions
+        .into_iter()
+        .zip(ends)
+        .map(|(v, effective_to)| ShiftTypeVersionResponse {
+            effective_from: v.effective_from,
+            effective_to,
+            category: v.category,
+            duration_hours: v.duration_hours,
+            night_hours: v.night_hours,
+            is_countable: v.is_countable,
+            requires_recovery: v.requires_recovery,
+            is_holiday_indicator: v.is_holiday_indicator,
+            is_rest_day: v.is_rest_day,
+        })
+        .collect();
+
+    Ok(Json(ShiftTypeHistoryResponse {
+        shift_type,
+        versions,
+    }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeactivatedResponse {
+    pub shift_type: ShiftType,
+    /// Schedules still using the type
+    pub schedule_count: i64,
+}
+
+/// Delete a shift type
+///
+/// 204 when removed; 200 with the deactivated type when schedules still
+/// use it.
+pub async fn delete(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Path(id): Path<Uuid>,
+) -> Result<Response, HandlerError> {
+    let result = ManageShiftTypesHandler::new(state.repositories.clone())
+        .delete(claims.org, id)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(match result {
+        DeleteShiftTypeResult::Deleted => StatusCode::NO_CONTENT.into_response(),
+        DeleteShiftTypeResult::Deactivated {
+            shift_type,
+            schedule_count,
+        } => Json(DeactivatedResponse {
+            shift_type: *shift_type,
+            schedule_count,
+        })
+        .into_response(),
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderRequest {
+    /// Shift type IDs in their new order (unlisted types follow)
+    pub ids: Vec<Uuid>,
+}
+
+/// Reorder the shift types of an organization
+pub async fn reorder(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Json(request): Json<ReorderRequest>,
+) -> Result<Json<Vec<ShiftType>>, HandlerError> {
+    let org_id = claims.org;
+
+    let shift_types = ManageShiftTypesHandler::new(state.repositories.clone())
+        .reorder(org_id, &request.ids)
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(shift_types))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemapRequest {
+    /// Shift type replacing this one
+    pub target_id: Uuid,
+    /// First date remapped (all dates when omitted)
+    pub start_date: Option<NaiveDate>,
+    /// Last date remapped, inclusive (all dates when omitted)
+    pub end_date: Option<NaiveDate>,
+}
+
+impl RemapRequest {
+    fn command(self, claims: &Claims, source_id: Uuid) -> RemapShiftTypeCommand {
+        RemapShiftTypeCommand {
+            organization_id: claims.org,
+            source_id,
+            target_id: self.target_id,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            updated_by: Some(claims.sub),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedBalanceResponse {
+    pub user_id: Uuid,
+    pub period_id: Uuid,
+    pub period_label: String,
+    pub year: i32,
+    pub total_hours_before: f64,
+    pub total_hours_after: f64,
+    pub night_hours_before: f64,
+    pub night_hours_after: f64,
+    pub is_valid_before: bool,
+    pub is_valid_after: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemapResponse {
+    pub source: ShiftType,
+    pub target: ShiftType,
+    pub schedule_count: usize,
+    pub balances: Vec<AffectedBalanceResponse>,
+    /// Edit locks of other planners on the remapped cells
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<EditLock>,
+}
+
+impl From<RemapPreview> for RemapResponse {
+    fn from(preview: RemapPreview) -> Self {
+        Self {
+            schedule_count: preview.schedules.len(),
+            balances: preview
+                .balances
+                .into_iter()
+                .map(|b| AffectedBalanceResponse {
+                    user_id: b.before.user_id,
+                    period_id: b.period.id,
+                    period_label: b.period.label(),
+                    year: b.period.year,
+                    total_hours_before: b.before.total_hours,
+                    total_hours_after: b.after.total_hours,
+                    night_hours_before: b.before.night_hours,
+                    night_hours_after: b.after.night_hours,
+                    is_valid_before: b.before.is_valid,
+                    is_valid_after: b.after.is_valid,
+                })
+                .collect(),
+            source: preview.source,
+            target: preview.target,
+            locks: Vec::new(),
+        }
+    }
+}
+
+/// Preview the remap of a shift type's schedules to another type
+pub async fn remap_preview(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RemapRequest>,
+) -> Result<Json<RemapResponse>, HandlerError> {
+    let preview = RemapShiftTypeHandler::new(state.repositories.clone())
+        .preview(&request.command(&claims, id))
+        .await
+        .map_err(repository_error)?;
+
+    Ok(Json(preview.into()))
+}
+
+/// Remap a shift type's schedules to another type
+///
+/// Recorded in the audit log. Cells under the edit locks of other planners
+/// are refused; the remapped cells are published and their balances
+/// recalculated.
+pub async fn remap(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageShiftTypes>,
+    editor: Editor,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RemapRequest>,
+) -> Result<Json<RemapResponse>, HandlerError> {
+    let handler = RemapShiftTypeHandler::new(state.repositories.clone());
+    let command = request.command(&claims, id);
+    let preview = handler.preview(&command).await.map_err(repository_error)?;
+
+    let locks = presence::check_write(&state, &editor, &preview.entries())
+        .await
+        .map_err(locked_error)?;
+    let remapped = handler.execute(&command).await.map_err(repository_error)?;
+    events::schedules_written(&state, claims.org, &remapped.entries()).await;
+
+    Ok(Json(RemapResponse {
+        locks,
+        ..remapped.into()
+    }))
 }
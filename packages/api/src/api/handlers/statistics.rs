@@ -1,17 +1,547 @@
 //! Statistics Handlers
 
-use axum::{extract::State, http::StatusCode};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::api::error::{database_error, error, forbidden, HandlerError};
+use crate::api::middleware::{Authorized, ReadStatistics, ReadTeamStatistics};
+use crate::application::queries::{
+    LeaveBalance, PeriodStatisticsResponse, PeriodSummary, ShiftCount, UserBalanceResponse,
+    ValidationIssue,
+};
+use crate::domain::services::dashboard_builder::YearlyDashboard;
+use crate::domain::services::fairness_analyzer::{
+    FairnessMetrics, FairnessOutlier, MetricDistribution,
+};
+use crate::domain::services::monthly_stats::{MonthStats, PeriodMonthSplit};
+use crate::domain::services::{
+    BalanceCalculator, DashboardBuilder, FairnessAnalyzer, MonthlyStats, QuotaValidator,
+};
+use crate::infrastructure::persistence::{hour_bank, planning_data};
 use crate::infrastructure::AppState;
 
-pub async fn dashboard(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Resolve a year or a range of its periods to dates
+///
+/// Without a period range, the whole year is covered (periods P1-P13,
+/// or the calendar year when periods have not been generated).
+async fn year_range(
+    state: &AppState,
+    org_id: Uuid,
+    year: i32,
+    from_period: Option<i32>,
+    to_period: Option<i32>,
+) -> Result<(NaiveDate, NaiveDate), HandlerError> {
+    let periods = planning_data::find_periods(&state.db, org_id, year)
+        .await
+        .map_err(database_error)?;
+
+    let from = from_period.unwrap_or(1);
+    let to = to_period.unwrap_or(13);
+    let selected: Vec<_> = periods
+        .iter()
+        .filter(|p| p.number >= from && p.number <= to)
+        .collect();
+
+    match (selected.first(), selected.last()) {
+        (Some(first), Some(last)) => Ok((first.start_date, last.end_date)),
+        _ if from_period.is_none() && to_period.is_none() => Ok((
+            NaiveDate::from_ymd_opt(year, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(year, 12, 31).unwrap(),
+        )),
+        _ => Err(error(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            "No periods found for this range",
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairnessQuery {
+    pub year: Option<i32>,
+    /// First period number (1-13)
+    pub from_period: Option<i32>,
+    /// Last period number (1-13)
+    pub to_period: Option<i32>,
+    /// Outlier threshold in standard deviations (default 1.5)
+    pub threshold: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentFairnessResponse {
+    pub user_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub metrics: FairnessMetrics,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairnessResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub agents: Vec<AgentFairnessResponse>,
+    pub distribution: Vec<MetricDistribution>,
+    pub outliers: Vec<FairnessOutlier>,
+}
+
+/// Fairness of nights, weekends, holidays and strikes over a year or period range
+pub async fn fairness(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadTeamStatistics>,
+    Query(query): Query<FairnessQuery>,
+) -> Result<Json<FairnessResponse>, HandlerError> {
+    let org_id = claims.org;
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+    let (start, end) = year_range(&state, org_id, year, query.from_period, query.to_period).await?;
+
+    let agents = planning_data::find_active_agents(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, None)
+        .await
+        .map_err(database_error)?;
+    let holidays = planning_data::find_holiday_dates(&state.db, org_id, start, end)
+        .await
+        .map_err(database_error)?;
+    let grammar = planning_data::find_shift_code_grammar(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+
+    let analyzer = query
+        .threshold
+        .map(FairnessAnalyzer::with_threshold)
+        .unwrap_or_default()
+        .with_grammar(grammar)
+        .with_history(history);
+    let ids: Vec<Uuid> = agents.iter().map(|a| a.id).collect();
+    let report = analyzer.analyze(&ids, &schedules, &shift_types, &holidays);
+
+    let agents = agents
+        .into_iter()
+        .zip(report.agents)
+        .map(|(agent, fairness)| AgentFairnessResponse {
+            user_id: agent.id,
+            first_name: agent.first_name,
+            last_name: agent.last_name,
+            metrics: fairness.metrics,
+        })
+        .collect();
+
+    Ok(Json(FairnessResponse {
+        start_date: start,
+        end_date: end,
+        agents,
+        distribution: report.distribution,
+        outliers: report.outliers,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardQuery {
+    pub year: Option<i32>,
+    /// Restrict to one agent (team dashboard when omitted)
+    pub user_id: Option<Uuid>,
+}
+
+/// Yearly dashboard (TABLEAU DE BORD)
+pub async fn dashboard(
+    State(state): State<AppState>,
+    auth: Authorized<ReadStatistics>,
+    Query(mut query): Query<DashboardQuery>,
+) -> Result<Json<YearlyDashboard>, HandlerError> {
+    let org_id = auth.claims.org;
+    query.user_id = auth.user_filter(query.user_id).map_err(forbidden)?;
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let periods = planning_data::find_periods(&state.db, org_id, year)
+        .await
+        .map_err(database_error)?;
+    let (start, end) = DashboardBuilder::date_range(year, &periods);
+
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, query.user_id)
+        .await
+        .map_err(database_error)?;
+
+    let leave = planning_data::find_leave_entitlements(&state.db, org_id, query.user_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(DashboardBuilder::build(
+        year,
+        &periods,
+        &schedules,
+        &shift_types,
+        &history,
+        &leave,
+    )))
 }
 
-pub async fn period(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodStatisticsParams {
+    pub user_id: Option<Uuid>,
 }
 
-pub async fn user(State(_state): State<AppState>) -> StatusCode {
-    StatusCode::NOT_IMPLEMENTED
+/// Period statistics: compliance, hours and shift distribution
+pub async fn period(
+    State(state): State<AppState>,
+    auth: Authorized<ReadStatistics>,
+    Path(id): Path<Uuid>,
+    Query(mut params): Query<PeriodStatisticsParams>,
+) -> Result<Json<PeriodStatisticsResponse>, HandlerError> {
+    let org_id = auth.claims.org;
+    params.user_id = auth.user_filter(params.user_id).map_err(forbidden)?;
+    let period = planning_data::find_period(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+    let agents: Vec<_> = planning_data::find_active_agents(&state.db, org_id)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .filter(|a| params.user_id.is_none_or(|id| id == a.id))
+        .collect();
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(
+        &state.db,
+        org_id,
+        period.start_date,
+        period.end_date,
+        params.user_id,
+    )
+    .await
+    .map_err(database_error)?;
+    let holidays =
+        planning_data::find_holiday_dates(&state.db, org_id, period.start_date, period.end_date)
+            .await
+            .map_err(database_error)?;
+
+    let hour_bank_delta = if period.is_closed() {
+        let (delta,): (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT SUM(delta)::FLOAT8
+            FROM hour_bank_entries
+            WHERE organization_id = $1 AND period_id = $2
+            AND ($3::UUID IS NULL OR user_id = $3)
+            "#,
+        )
+        .bind(org_id)
+        .bind(period.id)
+        .bind(params.user_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(database_error)?;
+        Some(delta.unwrap_or(0.0))
+    } else {
+        None
+    };
+
+    let calculator = BalanceCalculator::new().with_history(history.clone());
+    let validator = QuotaValidator {
+        max_hours: period.hour_quota as f64,
+        ..QuotaValidator::new()
+    };
+
+    let mut compliant_agents = 0;
+    let mut total_hours = 0.0;
+    let mut total_night_hours = 0.0;
+    let mut validation_issues = Vec::new();
+
+    for agent in &agents {
+        let balance = calculator.calculate(&period, agent.id, &schedules, &shift_types, &holidays);
+        total_hours += balance.total_hours;
+        total_night_hours += balance.night_hours;
+
+        if balance.is_valid {
+            compliant_agents += 1;
+        } else {
+            let result = validator.validate(&balance);
+            validation_issues.push(ValidationIssue {
+                user_id: agent.id,
+                user_name: format!("{} {}", agent.first_name, agent.last_name),
+                errors: result.error_messages(),
+                warnings: result.warning_messages(),
+            });
+        }
+    }
+
+    let agent_schedules: Vec<_> = schedules
+        .iter()
+        .filter(|s| agents.iter().any(|a| a.id == s.user_id))
+        .cloned()
+        .collect();
+    let shift_distribution = ShiftCount::distribution(&agent_schedules, &shift_types, &history);
+
+    let total_agents = agents.len() as i32;
+    let compliance_rate = if total_agents > 0 {
+        compliant_agents as f64 / total_agents as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(Json(PeriodStatisticsResponse {
+        period_number: period.number,
+        total_agents,
+        compliant_agents,
+        compliance_rate,
+        total_hours,
+        hour_bank_delta,
+        total_night_hours,
+        shift_distribution,
+        validation_issues,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserStatisticsParams {
+    pub year: Option<i32>,
+}
+
+/// Agent statistics for a year: leave balances and per-period summary
+pub async fn user(
+    State(state): State<AppState>,
+    auth: Authorized<ReadStatistics>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<UserStatisticsParams>,
+) -> Result<Json<UserBalanceResponse>, HandlerError> {
+    let year = params.year.unwrap_or_else(|| Utc::now().year());
+
+    let org_id = auth.claims.org;
+    if !auth.covers(id) {
+        return Err(error(StatusCode::NOT_FOUND, "NOT_FOUND", "User not found"));
+    }
+
+    let user: Option<(i32, i32, i32, i32)> = sqlx::query_as(
+        r#"
+        SELECT cn_entitlement, jc_entitlement, cn_carryover, jc_carryover
+        FROM users
+        WHERE organization_id = $1 AND id = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    let (cn_entitlement, jc_entitlement, cn_carryover, jc_carryover) =
+        user.ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "User not found"))?;
+
+    let periods = planning_data::find_periods(&state.db, org_id, year)
+        .await
+        .map_err(database_error)?;
+    let (start, end) = DashboardBuilder::date_range(year, &periods);
+
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, Some(id))
+        .await
+        .map_err(database_error)?;
+    let holidays = planning_data::find_holiday_dates(&state.db, org_id, start, end)
+        .await
+        .map_err(database_error)?;
+
+    let deltas: Vec<(Uuid, f64)> = sqlx::query_as(
+        "SELECT period_id, delta::FLOAT8 FROM hour_bank_entries WHERE organization_id = $1 AND user_id = $2",
+    )
+    .bind(org_id)
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    let calculator = BalanceCalculator::new().with_history(history);
+    let summaries = periods
+        .iter()
+        .map(|p| {
+            let balance = calculator.calculate(p, id, &schedules, &shift_types, &holidays);
+            PeriodSummary {
+                period_id: p.id,
+                period_number: p.number,
+                total_hours: balance.total_hours,
+                hour_bank_delta: deltas.iter().find(|(pid, _)| *pid == p.id).map(|d| d.1),
+                night_hours: balance.night_hours,
+                ch_count: balance.ch_count,
+                rh_count: balance.rh_count,
+                cv_count: balance.cv_count,
+                is_valid: balance.is_valid,
+            }
+        })
+        .collect();
+
+    // Leave taken over the calendar year
+    let taken = |code: &str| {
+        schedules
+            .iter()
+            .filter(|s| s.date.year() == year)
+            .filter_map(|s| s.shift_type_id)
+            .filter(|st_id| {
+                shift_types
+                    .iter()
+                    .any(|st| st.id == *st_id && st.code == code)
+            })
+            .count() as i32
+    };
+    let leave_balance = |entitlement: i32, carryover: i32, used: i32| LeaveBalance {
+        entitlement,
+        carryover,
+        total: entitlement + carryover,
+        used,
+        remaining: entitlement + carryover - used,
+    };
+
+    let hour_bank_balance = hour_bank::find_balance(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(UserBalanceResponse {
+        user_id: id,
+        year,
+        cn: leave_balance(cn_entitlement, cn_carryover, taken("CN")),
+        jc: leave_balance(jc_entitlement, jc_carryover, taken("JC")),
+        periods: summaries,
+        hour_bank_balance,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyQuery {
+    pub year: Option<i32>,
+    /// Month (1-12, default: current month)
+    pub month: Option<u32>,
+    pub user_id: Option<Uuid>,
+}
+
+/// Agent ids of a statistics request (one agent or all active agents)
+async fn agent_ids(
+    state: &AppState,
+    org_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<Uuid>, HandlerError> {
+    if let Some(id) = user_id {
+        return Ok(vec![id]);
+    }
+
+    Ok(planning_data::find_active_agents(&state.db, org_id)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|a| a.id)
+        .collect())
+}
+
+/// Statistics of a calendar month
+pub async fn monthly(
+    State(state): State<AppState>,
+    auth: Authorized<ReadStatistics>,
+    Query(mut query): Query<MonthlyQuery>,
+) -> Result<Json<MonthStats>, HandlerError> {
+    let org_id = auth.claims.org;
+    query.user_id = auth.user_filter(query.user_id).map_err(forbidden)?;
+    let today = Utc::now().date_naive();
+    let year = query.year.unwrap_or_else(|| today.year());
+    let month = query.month.unwrap_or_else(|| today.month());
+
+    let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+        error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Month must be between 1 and 12",
+        )
+    })?;
+    let end = start + chrono::Months::new(1) - chrono::Duration::days(1);
+
+    let agents = agent_ids(&state, org_id, query.user_id).await?;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(&state.db, org_id, start, end, query.user_id)
+        .await
+        .map_err(database_error)?;
+
+    Ok(Json(MonthlyStats::month(
+        year,
+        month,
+        &agents,
+        &schedules,
+        &shift_types,
+        &history,
+    )))
+}
+
+/// Period statistics split by calendar month
+pub async fn period_months(
+    State(state): State<AppState>,
+    auth: Authorized<ReadStatistics>,
+    Path(id): Path<Uuid>,
+    Query(mut params): Query<PeriodStatisticsParams>,
+) -> Result<Json<PeriodMonthSplit>, HandlerError> {
+    let org_id = auth.claims.org;
+    params.user_id = auth.user_filter(params.user_id).map_err(forbidden)?;
+    let period = planning_data::find_period(&state.db, org_id, id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+    let agents = agent_ids(&state, org_id, params.user_id).await?;
+    let shift_types = planning_data::find_shift_types(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let history = planning_data::find_shift_type_history(&state.db, org_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(
+        &state.db,
+        org_id,
+        period.start_date,
+        period.end_date,
+        params.user_id,
+    )
+    .await
+    .map_err(database_error)?;
+
+    Ok(Json(MonthlyStats::split_period(
+        &period,
+        &agents,
+        &schedules,
+        &shift_types,
+        &history,
+    )))
 }
@@ -0,0 +1,150 @@
+//! Fix Suggestion Handlers
+//!
+//! Ranked quota fix suggestions for an agent within a period.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::error::{database_error, error, HandlerError};
+use crate::api::handlers::schedules::{self, RejectedEntry};
+use crate::api::middleware::{Authorized, WriteSchedules};
+use crate::application::commands::create_schedule::CreateScheduleCommand;
+use crate::domain::entities::edit_lock::EditLock;
+use crate::domain::entities::Period;
+use crate::domain::services::fix_suggester::FixSuggestion;
+use crate::domain::services::FixSuggester;
+use crate::infrastructure::persistence::planning_data;
+use crate::infrastructure::presence::Editor;
+use crate::infrastructure::AppState;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestionQuery {
+    pub user_id: Uuid,
+}
+
+/// Compute ranked suggestions for an agent in a period
+async fn compute(
+    state: &AppState,
+    org_id: Uuid,
+    period_id: Uuid,
+    user_id: Uuid,
+) -> Result<(Period, Vec<FixSuggestion>), HandlerError> {
+    let period = planning_data::find_period(&state.db, org_id, period_id)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "NOT_FOUND", "Period not found"))?;
+
+    let shift_types = planning_data::find_shift_types(&state.db, period.organization_id)
+        .await
+        .map_err(database_error)?;
+    let schedules = planning_data::find_schedules(
+        &state.db,
+        period.organization_id,
+        period.start_date,
+        period.end_date,
+        Some(user_id),
+    )
+    .await
+    .map_err(database_error)?;
+    let holidays = planning_data::find_holiday_dates(
+        &state.db,
+        period.organization_id,
+        period.start_date,
+        period.end_date,
+    )
+    .await
+    .map_err(database_error)?;
+
+    let suggestions =
+        FixSuggester::new().suggest(&period, user_id, &schedules, &shift_types, &holidays);
+
+    Ok((period, suggestions))
+}
+
+/// List ranked fix suggestions for an agent in a period
+pub async fn list(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    Path(period_id): Path<Uuid>,
+    Query(query): Query<SuggestionQuery>,
+) -> Result<Json<Vec<FixSuggestion>>, HandlerError> {
+    let (_, suggestions) = compute(&state, claims.org, period_id, query.user_id).await?;
+    Ok(Json(suggestions))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplySuggestionsRequest {
+    pub user_id: Uuid,
+    /// Ranks to apply (all suggestions when omitted)
+    pub ranks: Option<Vec<usize>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplySuggestionsResponse {
+    pub applied: Vec<FixSuggestion>,
+    /// Suggested entries not written, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<RejectedEntry>,
+    /// Edit locks of other planners on the written cells
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<EditLock>,
+}
+
+/// Apply fix suggestions in one call
+///
+/// Suggestions are recomputed from the current planning so that a stale
+/// client cannot write edits that no longer make sense.
+pub async fn apply(
+    State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<WriteSchedules>,
+    editor: Editor,
+    Path(period_id): Path<Uuid>,
+    Json(body): Json<ApplySuggestionsRequest>,
+) -> Result<Json<ApplySuggestionsResponse>, HandlerError> {
+    let (period, suggestions) = compute(&state, claims.org, period_id, body.user_id).await?;
+
+    if period.is_closed() {
+        return Err(error(
+            StatusCode::CONFLICT,
+            "PERIOD_CLOSED",
+            "Period is closed",
+        ));
+    }
+
+    let applied: Vec<FixSuggestion> = match &body.ranks {
+        Some(ranks) => suggestions
+            .into_iter()
+            .filter(|s| ranks.contains(&s.rank))
+            .collect(),
+        None => suggestions,
+    };
+
+    if applied.is_empty() {
+        return Ok(Json(ApplySuggestionsResponse {
+            applied,
+            rejected: Vec::new(),
+            locks: Vec::new(),
+        }));
+    }
+
+    let entries: Vec<_> = applied
+        .iter()
+        .map(FixSuggestion::to_create_schedule)
+        .collect();
+    let command = CreateScheduleCommand::bulk(period.organization_id, Some(claims.sub), entries);
+    let (result, locks) = schedules::write(&state, command, &editor).await?;
+
+    Ok(Json(ApplySuggestionsResponse {
+        applied,
+        rejected: result.errors.into_iter().map(RejectedEntry::from).collect(),
+        locks,
+    }))
+}
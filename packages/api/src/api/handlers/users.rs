@@ -9,13 +9,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use crate::infrastructure::{auth::password::hash_password, AppState};
-
-#[derive(Serialize)]
-pub struct ErrorResponse {
-    pub code: String,
-    pub message: String,
-}
+use crate::api::error::ErrorResponse;
+use crate::api::middleware::{Authorized, ManageUsers, ReadBalances, ReadUsers};
+use crate::infrastructure::{auth::password::hash_password, persistence::hour_bank, AppState};
 
 #[derive(Serialize, FromRow)]
 #[serde(rename_all = "camelCase")]
@@ -57,9 +53,43 @@ struct UserRow {
     role_name: Option<String>,
 }
 
+/// Reject a role of another organization
+async fn check_role(
+    state: &AppState,
+    org_id: Uuid,
+    role_id: Uuid,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let role: Option<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM roles WHERE id = $1 AND organization_id = $2")
+            .bind(role_id)
+            .bind(org_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        code: "DATABASE_ERROR".to_string(),
+                        message: "Failed to fetch role".to_string(),
+                    }),
+                )
+            })?;
+
+    role.map(|_| ()).ok_or_else(|| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                code: "VALIDATION_ERROR".to_string(),
+                message: "Unknown role".to_string(),
+            }),
+        )
+    })
+}
+
 /// List users
 pub async fn list(
     State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadUsers>,
 ) -> Result<Json<Vec<UserResponse>>, (StatusCode, Json<ErrorResponse>)> {
     let users: Vec<UserRow> = sqlx::query_as(
         r#"
@@ -71,9 +101,11 @@ pub async fn list(
             r.name as role_name
         FROM users u
         LEFT JOIN roles r ON u.role_id = r.id
+        WHERE u.organization_id = $1
         ORDER BY u.last_name, u.first_name
         "#,
     )
+    .bind(claims.org)
     .fetch_all(&state.db)
     .await
     .map_err(|e| {
@@ -114,6 +146,7 @@ pub async fn list(
 /// Get user by ID
 pub async fn get(
     State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ReadUsers>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<UserResponse>, (StatusCode, Json<ErrorResponse>)> {
     let user: Option<UserRow> = sqlx::query_as(
@@ -126,10 +159,11 @@ pub async fn get(
             r.name as role_name
         FROM users u
         LEFT JOIN roles r ON u.role_id = r.id
-        WHERE u.id = $1
+        WHERE u.id = $1 AND u.organization_id = $2
         "#,
     )
     .bind(id)
+    .bind(claims.org)
     .fetch_optional(&state.db)
     .await
     .map_err(|e| {
@@ -175,7 +209,6 @@ pub async fn get(
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateUserRequest {
-    pub organization_id: Option<Uuid>,
     pub role_id: Option<Uuid>,
     pub email: String,
     pub password: String,
@@ -188,6 +221,7 @@ pub struct CreateUserRequest {
 /// Create user
 pub async fn create(
     State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageUsers>,
     Json(body): Json<CreateUserRequest>,
 ) -> Result<(StatusCode, Json<UserResponse>), (StatusCode, Json<ErrorResponse>)> {
     // Hash password
@@ -202,38 +236,12 @@ pub async fn create(
         )
     })?;
 
-    // Get default organization if not provided
-    let org_id = if let Some(id) = body.organization_id {
-        id
-    } else {
-        // Get first organization
-        let org: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM organizations LIMIT 1")
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|_| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse {
-                        code: "DATABASE_ERROR".to_string(),
-                        message: "Failed to fetch organization".to_string(),
-                    }),
-                )
-            })?;
-
-        org.ok_or_else(|| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse {
-                    code: "NO_ORGANIZATION".to_string(),
-                    message: "No organization exists. Create one first.".to_string(),
-                }),
-            )
-        })?
-        .0
-    };
+    // Users are created in the caller's organization
+    let org_id = claims.org;
 
     // Get default role if not provided (agent role)
     let role_id = if let Some(id) = body.role_id {
+        check_role(&state, org_id, id).await?;
         Some(id)
     } else {
         let role: Option<(Uuid,)> = sqlx::query_as(
@@ -336,12 +344,17 @@ pub struct UpdateUserRequest {
 /// Update user
 pub async fn update(
     State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageUsers>,
     Path(id): Path<Uuid>,
     Json(body): Json<UpdateUserRequest>,
 ) -> Result<Json<UserResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(role_id) = body.role_id {
+        check_role(&state, claims.org, role_id).await?;
+    }
+
     // Build dynamic update query
     let mut updates = Vec::new();
-    let mut param_idx = 2; // $1 is the user id
+    let mut param_idx = 3; // $1 is the user id, $2 the organization id
 
     if body.role_id.is_some() {
         updates.push(format!("role_id = ${}", param_idx));
@@ -388,7 +401,7 @@ pub async fn update(
     let query = format!(
         r#"
         UPDATE users SET {}
-        WHERE id = $1
+        WHERE id = $1 AND organization_id = $2
         RETURNING
             id, organization_id, role_id, email,
             first_name, last_name, matricule, avatar_url, phone,
@@ -399,7 +412,9 @@ pub async fn update(
         updates.join(", ")
     );
 
-    let mut query_builder = sqlx::query_as::<_, UserRow>(&query).bind(id);
+    let mut query_builder = sqlx::query_as::<_, UserRow>(&query)
+        .bind(id)
+        .bind(claims.org);
 
     if let Some(role_id) = body.role_id {
         query_builder = query_builder.bind(role_id);
@@ -436,7 +451,7 @@ pub async fn update(
     }
 
     let user: UserRow = query_builder
-        .fetch_one(&state.db)
+        .fetch_optional(&state.db)
         .await
         .map_err(|e| {
             tracing::error!("Database error updating user: {:?}", e);
@@ -447,6 +462,15 @@ pub async fn update(
                     message: "Failed to update user".to_string(),
                 }),
             )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    code: "NOT_FOUND".to_string(),
+                    message: "User not found".to_string(),
+                }),
+            )
         })?;
 
     Ok(Json(UserResponse {
@@ -471,10 +495,12 @@ pub async fn update(
 /// Delete user
 pub async fn delete(
     State(state): State<AppState>,
+    Authorized { claims, .. }: Authorized<ManageUsers>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    let result = sqlx::query("DELETE FROM users WHERE id = $1")
+    let result = sqlx::query("DELETE FROM users WHERE id = $1 AND organization_id = $2")
         .bind(id)
+        .bind(claims.org)
         .execute(&state.db)
         .await
         .map_err(|e| {
@@ -511,18 +537,22 @@ pub struct UserBalanceResponse {
     pub jc_total: i32,
     pub jc_used: i32,
     pub jc_remaining: i32,
+    /// Hour bank balance carried over from closed periods
+    pub hour_bank_balance: f64,
 }
 
 /// Get user balance
 pub async fn balance(
     State(state): State<AppState>,
+    auth: Authorized<ReadBalances>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<UserBalanceResponse>, (StatusCode, Json<ErrorResponse>)> {
     // Get user entitlements
     let user: Option<(i32, i32, i32, i32)> = sqlx::query_as(
-        "SELECT cn_entitlement, jc_entitlement, cn_carryover, jc_carryover FROM users WHERE id = $1",
+        "SELECT cn_entitlement, jc_entitlement, cn_carryover, jc_carryover FROM users WHERE id = $1 AND organization_id = $2",
     )
     .bind(id)
+    .bind(auth.claims.org)
     .fetch_optional(&state.db)
     .await
     .map_err(|_| {
@@ -533,7 +563,9 @@ pub async fn balance(
                 message: "Failed to fetch user".to_string(),
             }),
         )
-    })?;
+    })?
+    // Callers limited to their own balance do not see other users
+    .filter(|_| auth.covers(id));
 
     let (cn_entitlement, jc_entitlement, cn_carryover, jc_carryover) = user.ok_or_else(|| {
         (
@@ -590,6 +622,18 @@ pub async fn balance(
         )
     })?;
 
+    let hour_bank_balance = hour_bank::find_balance(&state.db, auth.claims.org, id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: "Failed to calculate balance".to_string(),
+                }),
+            )
+        })?;
+
     let cn_total = cn_entitlement + cn_carryover;
     let jc_total = jc_entitlement + jc_carryover;
 
@@ -601,5 +645,6 @@ pub async fn balance(
         jc_total,
         jc_used: jc_used.0 as i32,
         jc_remaining: jc_total - jc_used.0 as i32,
+        hour_bank_balance,
     }))
 }
@@ -3,18 +3,42 @@
 //! JWT validation and user extraction.
 
 use axum::{
-    extract::{Request, State},
-    http::{header, StatusCode},
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, StatusCode},
     middleware::Next,
     response::Response,
 };
 
+use crate::infrastructure::auth::jwt::{Claims, JwtService};
 use crate::infrastructure::AppState;
 
+/// Authenticated caller, from the claims of its access token
+///
+/// Handlers scope every query to `claims.org`: an organization never sees
+/// the data of another one, whatever IDs a request carries.
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub Claims);
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Claims>()
+            .cloned()
+            .map(AuthUser)
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
 /// Extract and validate JWT from Authorization header
 pub async fn auth_middleware(
-    State(_state): State<AppState>,
-    request: Request,
+    State(state): State<AppState>,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     // Extract Authorization header
@@ -23,13 +47,121 @@ pub async fn auth_middleware(
         .get(header::AUTHORIZATION)
         .and_then(|h| h.to_str().ok());
 
-    let _token = match auth_header {
+    let token = match auth_header {
         Some(h) if h.starts_with("Bearer ") => &h[7..],
         _ => return Err(StatusCode::UNAUTHORIZED),
     };
 
-    // TODO: Validate token and extract claims
-    // TODO: Add claims to request extensions
+    let jwt_service = JwtService::new(
+        &state.settings.jwt.secret,
+        state.settings.jwt.access_expiry_secs,
+        state.settings.jwt.refresh_expiry_secs,
+    );
+    let claims = jwt_service
+        .validate_access_token(token)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    request.extensions_mut().insert(claims);
 
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{body::Body, routing::get, Router};
+    use sqlx::postgres::PgPoolOptions;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::infrastructure::config::{
+        CorsSettings, DatabaseSettings, JwtSettings, ServerSettings, Settings,
+    };
+
+    fn state() -> AppState {
+        let settings = Settings {
+            server: ServerSettings {
+                host: "127.0.0.1".to_string(),
+                port: 3001,
+            },
+            database: DatabaseSettings {
+                url: "postgres://localhost/planningos".to_string(),
+                max_connections: 1,
+            },
+            jwt: JwtSettings {
+                secret: "test-secret".to_string(),
+                access_expiry_secs: 900,
+                refresh_expiry_secs: 604800,
+            },
+            cors: CorsSettings { origins: vec![] },
+        };
+        let db = PgPoolOptions::new()
+            .connect_lazy(&settings.database.url)
+            .unwrap();
+        AppState::new(db, Arc::new(settings))
+    }
+
+    async fn call(token: Option<&str>) -> (StatusCode, String) {
+        let state = state();
+        let app = Router::new()
+            .route(
+                "/me",
+                get(|AuthUser(claims): AuthUser| async move { claims.org.to_string() }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+
+        let mut request = Request::builder().uri("/me");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_or_invalid_tokens() {
+        let service = JwtService::new("test-secret", 900, 604800);
+        let tokens = service
+            .create_tokens(Uuid::new_v4(), Uuid::new_v4(), "admin", Uuid::new_v4())
+            .unwrap();
+        let forged = JwtService::new("other-secret", 900, 604800)
+            .create_tokens(Uuid::new_v4(), Uuid::new_v4(), "admin", Uuid::new_v4())
+            .unwrap();
+
+        assert_eq!(call(None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            call(Some(&tokens.refresh_token)).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(Some(&forged.access_token)).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stores_claims_of_valid_tokens() {
+        let org_id = Uuid::new_v4();
+        let tokens = JwtService::new("test-secret", 900, 604800)
+            .create_tokens(Uuid::new_v4(), org_id, "planner", Uuid::new_v4())
+            .unwrap();
+
+        let (status, body) = call(Some(&tokens.access_token)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, org_id.to_string());
+    }
+}
@@ -3,5 +3,7 @@
 //! Authentication, logging, and other middleware.
 
 pub mod auth;
+pub mod permissions;
 
 pub use auth::*;
+pub use permissions::*;
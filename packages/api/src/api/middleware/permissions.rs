@@ -0,0 +1,493 @@
+//! Permission Guards
+//!
+//! `Authorized<G>` extracts the caller like `AuthUser` and checks that its
+//! role grants the permission of the guard `G`. Permissions are read from
+//! the role on each request, so role changes apply without a new token.
+//! Users without a role get the agent permissions.
+//!
+//! `Editor` extracts the caller as a presence editor, from the same user
+//! row.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::api::error::{forbidden, repository_error};
+use crate::api::middleware::AuthUser;
+use crate::domain::entities::user::UserRole;
+use crate::domain::entities::User;
+use crate::domain::value_objects::{Permission, PermissionScope, Permissions};
+use crate::infrastructure::auth::jwt::Claims;
+use crate::infrastructure::presence::Editor;
+use crate::infrastructure::AppState;
+
+/// Permission required by a route
+pub trait Guard {
+    const PERMISSION: Permission;
+    /// Accept grants limited to the caller's own rows
+    const OWN_SCOPE: bool = false;
+}
+
+/// `users:read`
+pub struct ReadUsers;
+impl Guard for ReadUsers {
+    const PERMISSION: Permission = Permission::UsersRead;
+}
+
+/// `users:manage`
+pub struct ManageUsers;
+impl Guard for ManageUsers {
+    const PERMISSION: Permission = Permission::UsersManage;
+}
+
+/// `roles:read`
+pub struct ReadRoles;
+impl Guard for ReadRoles {
+    const PERMISSION: Permission = Permission::RolesRead;
+}
+
+/// `roles:manage`
+pub struct ManageRoles;
+impl Guard for ManageRoles {
+    const PERMISSION: Permission = Permission::RolesManage;
+}
+
+/// `schedules:read`, own rows accepted
+pub struct ReadSchedules;
+impl Guard for ReadSchedules {
+    const PERMISSION: Permission = Permission::SchedulesRead;
+    const OWN_SCOPE: bool = true;
+}
+
+/// `schedules:read` on every agent (team views)
+pub struct ReadTeamSchedules;
+impl Guard for ReadTeamSchedules {
+    const PERMISSION: Permission = Permission::SchedulesRead;
+}
+
+/// `schedules:write`
+pub struct WriteSchedules;
+impl Guard for WriteSchedules {
+    const PERMISSION: Permission = Permission::SchedulesWrite;
+}
+
+/// `shift_types:read`
+pub struct ReadShiftTypes;
+impl Guard for ReadShiftTypes {
+    const PERMISSION: Permission = Permission::ShiftTypesRead;
+}
+
+/// `shift_types:manage`
+pub struct ManageShiftTypes;
+impl Guard for ManageShiftTypes {
+    const PERMISSION: Permission = Permission::ShiftTypesManage;
+}
+
+/// `periods:read`
+pub struct ReadPeriods;
+impl Guard for ReadPeriods {
+    const PERMISSION: Permission = Permission::PeriodsRead;
+}
+
+/// `periods:write`
+pub struct WritePeriods;
+impl Guard for WritePeriods {
+    const PERMISSION: Permission = Permission::PeriodsWrite;
+}
+
+/// `periods:lock`
+pub struct LockPeriods;
+impl Guard for LockPeriods {
+    const PERMISSION: Permission = Permission::PeriodsLock;
+}
+
+/// `balances:read`, own rows accepted
+pub struct ReadBalances;
+impl Guard for ReadBalances {
+    const PERMISSION: Permission = Permission::BalancesRead;
+    const OWN_SCOPE: bool = true;
+}
+
+/// `holidays:read`
+pub struct ReadHolidays;
+impl Guard for ReadHolidays {
+    const PERMISSION: Permission = Permission::HolidaysRead;
+}
+
+/// `holidays:manage`
+pub struct ManageHolidays;
+impl Guard for ManageHolidays {
+    const PERMISSION: Permission = Permission::HolidaysManage;
+}
+
+/// `statistics:read`, own rows accepted
+pub struct ReadStatistics;
+impl Guard for ReadStatistics {
+    const PERMISSION: Permission = Permission::StatisticsRead;
+    const OWN_SCOPE: bool = true;
+}
+
+/// `statistics:read` on every agent (team statistics)
+pub struct ReadTeamStatistics;
+impl Guard for ReadTeamStatistics {
+    const PERMISSION: Permission = Permission::StatisticsRead;
+}
+
+/// `calendar:manage`, own feeds accepted
+pub struct ManageCalendar;
+impl Guard for ManageCalendar {
+    const PERMISSION: Permission = Permission::CalendarManage;
+    const OWN_SCOPE: bool = true;
+}
+
+/// `payroll:manage`
+pub struct ManagePayroll;
+impl Guard for ManagePayroll {
+    const PERMISSION: Permission = Permission::PayrollManage;
+}
+
+/// Request outside the caller's permissions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Forbidden(pub Permission);
+
+impl fmt::Display for Forbidden {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Missing permission {}", self.0)
+    }
+}
+
+impl IntoResponse for Forbidden {
+    fn into_response(self) -> Response {
+        forbidden(self).into_response()
+    }
+}
+
+/// Caller whose role grants the permission of `G`
+pub struct Authorized<G> {
+    pub claims: Claims,
+    /// Rows the permission covers
+    pub scope: PermissionScope,
+    guard: PhantomData<G>,
+}
+
+impl<G: Guard> Authorized<G> {
+    /// Check if the caller may access the rows of a user
+    pub fn covers(&self, user_id: Uuid) -> bool {
+        self.scope == PermissionScope::All || user_id == self.claims.sub
+    }
+
+    /// User filter of a request, as allowed to the caller
+    ///
+    /// Callers limited to their own rows get their own user when no
+    /// filter is given, and may not name another user.
+    pub fn user_filter(&self, requested: Option<Uuid>) -> Result<Option<Uuid>, Forbidden> {
+        match (self.scope, requested) {
+            (PermissionScope::All, requested) => Ok(requested),
+            (PermissionScope::Own, None) => Ok(Some(self.claims.sub)),
+            (PermissionScope::Own, Some(id)) if id == self.claims.sub => Ok(Some(id)),
+            (PermissionScope::Own, Some(_)) => Err(Forbidden(G::PERMISSION)),
+        }
+    }
+}
+
+/// Active user of the caller (cached for the request)
+async fn user(parts: &mut Parts, state: &AppState, claims: &Claims) -> Result<User, Response> {
+    if let Some(user) = parts.extensions.get::<User>() {
+        return Ok(user.clone());
+    }
+
+    // Tokens of deleted or deactivated users stay valid until they expire
+    let user = state
+        .repositories
+        .users
+        .find_by_id(claims.org, claims.sub)
+        .await
+        .map_err(|e| repository_error(e).into_response())?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| StatusCode::UNAUTHORIZED.into_response())?;
+
+    parts.extensions.insert(user.clone());
+    Ok(user)
+}
+
+/// Permissions of the caller's role (cached for the request)
+async fn permissions(
+    parts: &mut Parts,
+    state: &AppState,
+    claims: &Claims,
+) -> Result<Permissions, Response> {
+    if let Some(permissions) = parts.extensions.get::<Permissions>() {
+        return Ok(permissions.clone());
+    }
+
+    let user = user(parts, state, claims).await?;
+    let role = match user.role_id {
+        Some(role_id) => state
+            .repositories
+            .roles
+            .find_by_id(claims.org, role_id)
+            .await
+            .map_err(|e| repository_error(e).into_response())?,
+        None => None,
+    };
+    let permissions = match role {
+        Some(role) => role.granted(),
+        None => Permissions::from_stored(UserRole::Agent.default_permissions()),
+    };
+
+    parts.extensions.insert(permissions.clone());
+    Ok(permissions)
+}
+
+impl<G: Guard> FromRequestParts<AppState> for Authorized<G> {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let AuthUser(claims) = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let scope = match permissions(parts, state, &claims)
+            .await?
+            .scope(G::PERMISSION)
+        {
+            Some(PermissionScope::Own) if !G::OWN_SCOPE => None,
+            scope => scope,
+        }
+        .ok_or_else(|| Forbidden(G::PERMISSION).into_response())?;
+
+        Ok(Self {
+            claims,
+            scope,
+            guard: PhantomData,
+        })
+    }
+}
+
+impl FromRequestParts<AppState> for Editor {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let AuthUser(claims) = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        Ok(Editor::from(&user(parts, state, &claims).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        extract::Query,
+        http::{header, Request},
+        routing::get,
+        Router,
+    };
+    use serde::Deserialize;
+    use sqlx::postgres::PgPoolOptions;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::middleware::auth_middleware;
+    use crate::application::testing::TestHarness;
+    use crate::domain::entities::User;
+    use crate::infrastructure::auth::jwt::JwtService;
+    use crate::infrastructure::config::{
+        CorsSettings, DatabaseSettings, JwtSettings, ServerSettings, Settings,
+    };
+
+    fn state(harness: &TestHarness) -> AppState {
+        let settings = Settings {
+            server: ServerSettings {
+                host: "127.0.0.1".to_string(),
+                port: 3001,
+            },
+            database: DatabaseSettings {
+                url: "postgres://localhost/planningos".to_string(),
+                max_connections: 1,
+            },
+            jwt: JwtSettings {
+                secret: "test-secret".to_string(),
+                access_expiry_secs: 900,
+                refresh_expiry_secs: 604800,
+            },
+            cors: CorsSettings { origins: vec![] },
+        };
+        let db = PgPoolOptions::new()
+            .connect_lazy(&settings.database.url)
+            .unwrap();
+        AppState {
+            repositories: harness.repositories(),
+            ..AppState::new(db, Arc::new(settings))
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Filter {
+        user_id: Option<Uuid>,
+    }
+
+    /// Call a guarded route as a user; returns the status and body
+    async fn call(harness: &TestHarness, user: &User, uri: &str) -> (StatusCode, String) {
+        let state = state(harness);
+        let app = Router::new()
+            .route("/users", get(|_: Authorized<ManageUsers>| async { "ok" }))
+            .route(
+                "/schedules",
+                get(
+                    |auth: Authorized<ReadSchedules>, Query(filter): Query<Filter>| async move {
+                        auth.user_filter(filter.user_id)
+                            .map(|id| id.map(|id| id.to_string()).unwrap_or_default())
+                    },
+                ),
+            )
+            .route(
+                "/team",
+                get(|_: Authorized<ReadTeamSchedules>| async { "ok" }),
+            )
+            .route(
+                "/editor",
+                get(|editor: Editor| async move { editor.name().to_string() }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+
+        let token = JwtService::new("test-secret", 900, 604800)
+            .create_tokens(user.id, user.organization_id, "agent", Uuid::new_v4())
+            .unwrap()
+            .access_token;
+        let request = Request::builder()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    async fn user_with_role(harness: &TestHarness, name: &str, role: Option<Uuid>) -> User {
+        let user = harness.agent(name, "Test").await;
+        harness
+            .repositories()
+            .users
+            .update(&User {
+                role_id: role,
+                ..user
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_agents_only_read_their_own_rows() {
+        let harness = TestHarness::new().await;
+        let agent = user_with_role(&harness, "Marie", Some(harness.role(UserRole::Agent).id)).await;
+        let other = harness.agent("Luc", "Martin").await;
+
+        assert_eq!(
+            call(&harness, &agent, "/users").await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(&harness, &agent, "/team").await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(&harness, &agent, "/schedules").await,
+            (StatusCode::OK, agent.id.to_string())
+        );
+        assert_eq!(
+            call(&harness, &agent, &format!("/schedules?userId={}", other.id))
+                .await
+                .0,
+            StatusCode::FORBIDDEN
+        );
+
+        // Users without a role get the agent permissions
+        assert_eq!(
+            call(&harness, &other, "/schedules").await,
+            (StatusCode::OK, other.id.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_roles_grant_their_permissions() {
+        let harness = TestHarness::new().await;
+        let planner =
+            user_with_role(&harness, "Anne", Some(harness.role(UserRole::Planner).id)).await;
+        let admin = user_with_role(&harness, "Paul", Some(harness.role(UserRole::Admin).id)).await;
+
+        assert_eq!(
+            call(&harness, &planner, "/users").await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(call(&harness, &planner, "/team").await.0, StatusCode::OK);
+        assert_eq!(
+            call(
+                &harness,
+                &planner,
+                &format!("/schedules?userId={}", admin.id)
+            )
+            .await,
+            (StatusCode::OK, admin.id.to_string())
+        );
+        assert_eq!(call(&harness, &planner, "/schedules").await.1, "");
+        assert_eq!(call(&harness, &admin, "/users").await.0, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_editor_is_the_token_user() {
+        let harness = TestHarness::new().await;
+        let planner =
+            user_with_role(&harness, "Anne", Some(harness.role(UserRole::Planner).id)).await;
+
+        assert_eq!(
+            call(&harness, &planner, "/editor").await,
+            (StatusCode::OK, planner.full_name())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inactive_users_are_rejected() {
+        let harness = TestHarness::new().await;
+        let admin = user_with_role(&harness, "Paul", Some(harness.role(UserRole::Admin).id)).await;
+        harness
+            .repositories()
+            .users
+            .update(&User {
+                is_active: false,
+                ..admin.clone()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            call(&harness, &admin, "/users").await.0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
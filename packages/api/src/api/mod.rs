@@ -3,6 +3,7 @@
 //! HTTP handlers, routes, and middleware.
 
 pub mod dto;
+pub mod error;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
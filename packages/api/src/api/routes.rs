@@ -3,6 +3,7 @@
 //! Route definitions for the API.
 
 use axum::{
+    middleware,
     routing::{delete, get, patch, post},
     Router,
 };
@@ -10,14 +11,20 @@ use axum::{
 use crate::infrastructure::AppState;
 
 use super::handlers;
+use super::middleware::auth_middleware;
 
 /// Build all API v1 routes
-pub fn api_routes() -> Router<AppState> {
+///
+/// Every route requires an access token, except the public ones (login,
+/// token refresh and calendar feeds).
+pub fn api_routes(state: AppState) -> Router<AppState> {
     Router::new()
         // Auth routes
         .nest("/auth", auth_routes())
         // User routes
         .nest("/users", user_routes())
+        // Role routes
+        .nest("/roles", role_routes())
         // Shift type routes
         .nest("/shift-types", shift_type_routes())
         // Period routes
@@ -28,14 +35,35 @@ pub fn api_routes() -> Router<AppState> {
         .nest("/statistics", statistics_routes())
         // Holiday routes
         .nest("/holidays", holiday_routes())
+        // Calendar feed routes
+        .nest("/calendar", calendar_routes())
+        // Live event routes
+        .nest("/events", event_routes())
+        // Export routes
+        .nest("/exports", export_routes())
+        // Import routes
+        .nest("/imports", import_routes())
+        // Payroll routes
+        .nest("/payroll", payroll_routes())
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+        // Routes without access token
+        .merge(public_routes())
+}
+
+/// Public routes
+fn public_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/login", post(handlers::auth::login))
+        .route("/auth/refresh", post(handlers::auth::refresh))
+        // Calendar apps authenticate with the secret of the feed URL
+        .route("/calendar/feed/{token}", get(handlers::calendar::feed))
 }
 
 /// Authentication routes
 fn auth_routes() -> Router<AppState> {
     Router::new()
-        .route("/login", post(handlers::auth::login))
         .route("/logout", post(handlers::auth::logout))
-        .route("/refresh", post(handlers::auth::refresh))
+        .route("/logout-all", post(handlers::auth::logout_all))
         .route("/me", get(handlers::auth::me))
 }
 
@@ -52,6 +80,19 @@ fn user_routes() -> Router<AppState> {
         .route("/{id}/balance", get(handlers::users::balance))
 }
 
+/// Role management routes
+fn role_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(handlers::roles::list).post(handlers::roles::create))
+        .route("/permissions", get(handlers::roles::permissions))
+        .route(
+            "/{id}",
+            get(handlers::roles::get)
+                .patch(handlers::roles::update)
+                .delete(handlers::roles::delete),
+        )
+}
+
 /// Shift type management routes
 fn shift_type_routes() -> Router<AppState> {
     Router::new()
@@ -59,12 +100,23 @@ fn shift_type_routes() -> Router<AppState> {
             "/",
             get(handlers::shift_types::list).post(handlers::shift_types::create),
         )
+        .route("/reorder", post(handlers::shift_types::reorder))
+        .route(
+            "/grammar",
+            get(handlers::shift_types::get_grammar).put(handlers::shift_types::update_grammar),
+        )
         .route(
             "/{id}",
             get(handlers::shift_types::get)
                 .patch(handlers::shift_types::update)
                 .delete(handlers::shift_types::delete),
         )
+        .route("/{id}/history", get(handlers::shift_types::history))
+        .route("/{id}/remap", post(handlers::shift_types::remap))
+        .route(
+            "/{id}/remap/preview",
+            post(handlers::shift_types::remap_preview),
+        )
 }
 
 /// Period routes
@@ -73,6 +125,17 @@ fn period_routes() -> Router<AppState> {
         .route("/", get(handlers::periods::list))
         .route("/{id}", get(handlers::periods::get))
         .route("/{id}/balances", get(handlers::periods::balances))
+        .route("/{id}/close", post(handlers::periods::close))
+        .route("/{id}/suggestions", get(handlers::suggestions::list))
+        .route("/{id}/suggestions/apply", post(handlers::suggestions::apply))
+        .route(
+            "/{id}/presence",
+            get(handlers::presence::get)
+                .post(handlers::presence::heartbeat)
+                .delete(handlers::presence::leave),
+        )
+        .route("/{id}/locks", post(handlers::presence::acquire_lock))
+        .route("/{id}/locks/{lock_id}", delete(handlers::presence::release_lock))
         .route("/generate", post(handlers::periods::generate))
 }
 
@@ -82,6 +145,7 @@ fn schedule_routes() -> Router<AppState> {
         .route("/", get(handlers::schedules::list).post(handlers::schedules::create))
         .route("/matrix", get(handlers::schedules::matrix))
         .route("/bulk", post(handlers::schedules::bulk_update))
+        .route("/normalize-variants", post(handlers::schedules::normalize_variants))
         .route(
             "/{id}",
             get(handlers::schedules::get)
@@ -94,7 +158,10 @@ fn schedule_routes() -> Router<AppState> {
 fn statistics_routes() -> Router<AppState> {
     Router::new()
         .route("/dashboard", get(handlers::statistics::dashboard))
+        .route("/fairness", get(handlers::statistics::fairness))
+        .route("/monthly", get(handlers::statistics::monthly))
         .route("/period/{id}", get(handlers::statistics::period))
+        .route("/period/{id}/months", get(handlers::statistics::period_months))
         .route("/user/{id}", get(handlers::statistics::user))
 }
 
@@ -105,3 +172,44 @@ fn holiday_routes() -> Router<AppState> {
         .route("/{id}", delete(handlers::holidays::delete))
         .route("/generate", post(handlers::holidays::generate))
 }
+
+/// Calendar feed routes
+fn calendar_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/tokens",
+            get(handlers::calendar::list_tokens).post(handlers::calendar::create_token),
+        )
+        .route("/tokens/{id}", delete(handlers::calendar::revoke_token))
+}
+
+/// Live event routes
+fn event_routes() -> Router<AppState> {
+    Router::new().route("/planning", get(handlers::events::planning))
+}
+
+/// Export routes
+fn export_routes() -> Router<AppState> {
+    Router::new()
+        .route("/planning.xlsx", get(handlers::exports::planning_xlsx))
+        .route("/planning.pdf", get(handlers::exports::planning_pdf))
+}
+
+/// Import routes
+fn import_routes() -> Router<AppState> {
+    Router::new().route("/planning.xlsx", post(handlers::imports::planning_xlsx))
+}
+
+/// Payroll routes
+fn payroll_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/mapping",
+            get(handlers::payroll::get_mapping).put(handlers::payroll::update_mapping),
+        )
+        .route(
+            "/batches",
+            get(handlers::payroll::list_batches).post(handlers::payroll::create_batch),
+        )
+        .route("/batches/{id}/file", get(handlers::payroll::batch_file))
+}
@@ -2,16 +2,21 @@
 //!
 //! Handles schedule creation and updates with validation.
 
-use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
+use crate::application::ports::{RepoResult, Repositories};
 use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::{Period, Schedule};
 
 /// Command to create or update a schedule entry
 #[derive(Debug, Clone)]
 pub struct CreateScheduleCommand {
     pub organization_id: Uuid,
-    pub created_by: Uuid,
+    /// Author of the write (None for system writes)
+    pub created_by: Option<Uuid>,
     pub entries: Vec<CreateSchedule>,
 }
 
@@ -21,6 +26,8 @@ pub struct CreateScheduleResult {
     pub created: usize,
     pub updated: usize,
     pub errors: Vec<ScheduleError>,
+    /// Schedules written
+    pub schedules: Vec<Schedule>,
 }
 
 /// Schedule creation error
@@ -35,7 +42,7 @@ impl CreateScheduleCommand {
     /// Create a single schedule entry command
     pub fn single(
         organization_id: Uuid,
-        created_by: Uuid,
+        created_by: Option<Uuid>,
         user_id: Uuid,
         date: NaiveDate,
         shift_type_id: Option<Uuid>,
@@ -53,7 +60,11 @@ impl CreateScheduleCommand {
     }
 
     /// Create a bulk schedule command
-    pub fn bulk(organization_id: Uuid, created_by: Uuid, entries: Vec<CreateSchedule>) -> Self {
+    pub fn bulk(
+        organization_id: Uuid,
+        created_by: Option<Uuid>,
+        entries: Vec<CreateSchedule>,
+    ) -> Self {
         Self {
             organization_id,
             created_by,
@@ -61,3 +72,230 @@ impl CreateScheduleCommand {
         }
     }
 }
+
+/// Handler for schedule creation
+///
+/// Entries are checked one by one (agent, shift type, period not closed);
+/// valid entries are written together and invalid ones reported.
+pub struct CreateScheduleHandler {
+    repositories: Repositories,
+}
+
+impl CreateScheduleHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Execute the command
+    pub async fn execute(
+        &self,
+        command: &CreateScheduleCommand,
+    ) -> RepoResult<CreateScheduleResult> {
+        let org_id = command.organization_id;
+        let mut result = CreateScheduleResult {
+            created: 0,
+            updated: 0,
+            errors: Vec::new(),
+            schedules: Vec::new(),
+        };
+
+        let (Some(start), Some(end)) = (
+            command.entries.iter().map(|e| e.date).min(),
+            command.entries.iter().map(|e| e.date).max(),
+        ) else {
+            return Ok(result);
+        };
+
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+        let holidays = self
+            .repositories
+            .holidays
+            .find_by_range(org_id, start, end)
+            .await?;
+
+        let mut agents: HashMap<Uuid, bool> = HashMap::new();
+        let mut periods: Vec<Period> = Vec::new();
+        let mut pending = Vec::with_capacity(command.entries.len());
+        let now = Utc::now();
+
+        for entry in &command.entries {
+            let reject = |message: String| ScheduleError {
+                user_id: entry.user_id,
+                date: entry.date,
+                message,
+            };
+
+            let is_agent = match agents.get(&entry.user_id) {
+                Some(is_agent) => *is_agent,
+                None => {
+                    let user = self
+                        .repositories
+                        .users
+                        .find_by_id(org_id, entry.user_id)
+                        .await?;
+                    let is_agent = user.is_some();
+                    agents.insert(entry.user_id, is_agent);
+                    is_agent
+                }
+            };
+            if !is_agent {
+                result.errors.push(reject("Unknown agent".to_string()));
+                continue;
+            }
+
+            if let Some(shift_type_id) = entry.shift_type_id {
+                match shift_types.iter().find(|st| st.id == shift_type_id) {
+                    None => {
+                        result.errors.push(reject("Unknown shift type".to_string()));
+                        continue;
+                    }
+                    Some(st) if !st.is_active => {
+                        result
+                            .errors
+                            .push(reject(format!("Shift type {} is inactive", st.code)));
+                        continue;
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let period = match periods.iter().find(|p| p.contains_date(entry.date)) {
+                Some(period) => Some(period.clone()),
+                None => {
+                    let period = self
+                        .repositories
+                        .periods
+                        .find_by_date(org_id, entry.date)
+                        .await?;
+                    periods.extend(period.clone());
+                    period
+                }
+            };
+            if let Some(period) = period.as_ref().filter(|p| p.is_closed()) {
+                result
+                    .errors
+                    .push(reject(format!("Period {} is closed", period.label())));
+                continue;
+            }
+
+            let existing = self
+                .repositories
+                .schedules
+                .find_by_user_date(org_id, entry.user_id, entry.date)
+                .await?;
+            match existing {
+                Some(_) => result.updated += 1,
+                None => result.created += 1,
+            }
+
+            pending.push(Schedule {
+                id: existing.as_ref().map(|s| s.id).unwrap_or_else(Uuid::new_v4),
+                organization_id: org_id,
+                user_id: entry.user_id,
+                shift_type_id: entry.shift_type_id,
+                period_id: period.map(|p| p.id),
+                date: entry.date,
+                is_holiday: holidays.iter().any(|h| h.date == entry.date),
+                notes: entry
+                    .notes
+                    .clone()
+                    .or_else(|| existing.as_ref().and_then(|s| s.notes.clone())),
+                created_by: existing
+                    .as_ref()
+                    .map_or(command.created_by, |s| s.created_by),
+                updated_by: command.created_by,
+                created_at: existing.as_ref().map_or(now, |s| s.created_at),
+                updated_at: now,
+            });
+        }
+
+        if !pending.is_empty() {
+            result.schedules = self.repositories.schedules.bulk_upsert(&pending).await?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::TestHarness;
+    use crate::domain::entities::ShiftType;
+
+    #[tokio::test]
+    async fn test_creates_then_updates() {
+        let harness = TestHarness::new().await;
+        let agent = harness.agent("Marie", "Dupont").await;
+        let start = harness.period(1).start_date;
+
+        let result = harness.plan(agent.id, start, &["101", "RH"]).await;
+        assert_eq!((result.created, result.updated), (2, 0));
+        assert!(result
+            .schedules
+            .iter()
+            .all(|s| s.period_id == Some(harness.period(1).id)));
+
+        let result = harness.plan(agent.id, start, &["121"]).await;
+        assert_eq!((result.created, result.updated), (0, 1));
+
+        let stored = harness
+            .repositories()
+            .schedules
+            .find_by_user_date(harness.org_id, agent.id, start)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.shift_type_id, Some(harness.shift_type("121").id));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_entries() {
+        let harness = TestHarness::new().await;
+        let agent = harness.agent("Marie", "Dupont").await;
+        let repositories = harness.repositories();
+        let handler = CreateScheduleHandler::new(repositories.clone());
+
+        repositories
+            .shift_types
+            .update(&ShiftType {
+                is_active: false,
+                ..harness.shift_type("AG").clone()
+            })
+            .await
+            .unwrap();
+        harness.store.periods.close(harness.period(1).id).unwrap();
+
+        let entry = |user_id, code: &str, date| CreateSchedule {
+            user_id,
+            shift_type_id: Some(harness.shift_type(code).id),
+            date,
+            notes: None,
+        };
+        let open_day = harness.period(2).start_date;
+        let command = CreateScheduleCommand::bulk(
+            harness.org_id,
+            None,
+            vec![
+                entry(Uuid::new_v4(), "101", open_day),
+                entry(agent.id, "AG", open_day),
+                entry(agent.id, "101", harness.period(1).start_date),
+                entry(agent.id, "101", open_day),
+            ],
+        );
+
+        let result = handler.execute(&command).await.unwrap();
+
+        let messages: Vec<_> = result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Unknown agent",
+                "Shift type AG is inactive",
+                "Period P1 is closed"
+            ]
+        );
+        assert_eq!(result.created, 1);
+        assert_eq!(result.schedules.len(), 1);
+    }
+}
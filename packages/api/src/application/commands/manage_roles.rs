@@ -0,0 +1,191 @@
+//! Manage Roles Command
+//!
+//! Creates, updates and deletes the custom roles of an organization.
+//! System roles are read-only, and roles still held by users cannot be
+//! deleted.
+
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::role::{CreateRole, RoleError, UpdateRole};
+use crate::domain::entities::Role;
+
+/// Handler for role management
+pub struct ManageRolesHandler {
+    repositories: Repositories,
+}
+
+fn validation_error(e: RoleError) -> RepositoryError {
+    RepositoryError::Validation(e.to_string())
+}
+
+impl ManageRolesHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Find a role of the organization
+    pub async fn find(&self, org_id: Uuid, id: Uuid) -> RepoResult<Role> {
+        self.repositories
+            .roles
+            .find_by_id(org_id, id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound(format!("role {}", id)))
+    }
+
+    /// Create a custom role
+    pub async fn create(&self, org_id: Uuid, request: CreateRole) -> RepoResult<Role> {
+        let role = request.into_role(org_id).map_err(validation_error)?;
+        self.repositories.roles.create(&role).await
+    }
+
+    /// Rename a custom role or replace its permissions
+    pub async fn update(&self, org_id: Uuid, id: Uuid, request: UpdateRole) -> RepoResult<Role> {
+        let role = self.find(org_id, id).await?;
+        let updated = request.apply(&role).map_err(validation_error)?;
+        self.repositories.roles.update(&updated).await
+    }
+
+    /// Delete a custom role no user holds
+    pub async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let role = self.find(org_id, id).await?;
+        if role.is_system {
+            return Err(validation_error(RoleError::System(role.name)));
+        }
+
+        let holders = self.repositories.users.count_by_role(org_id, id).await?;
+        if holders > 0 {
+            return Err(RepositoryError::Validation(format!(
+                "Role {} is held by {} user(s); assign them another role first",
+                role.name, holders
+            )));
+        }
+
+        self.repositories.roles.delete(org_id, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::TestHarness;
+    use crate::domain::entities::user::UserRole;
+    use crate::domain::entities::User;
+
+    fn request(name: &str, permissions: &[&str]) -> CreateRole {
+        CreateRole {
+            name: name.to_string(),
+            display_name: "Night planner".to_string(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_permissions() {
+        let harness = TestHarness::new().await;
+        let handler = ManageRolesHandler::new(harness.repositories());
+
+        let role = handler
+            .create(
+                harness.org_id,
+                request(
+                    "Night_Planner",
+                    &["schedules:write", "schedules:read:own", "users:read"],
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(role.name, "night_planner");
+        assert_eq!(
+            role.permissions,
+            vec!["users:read", "schedules:read:own", "schedules:write"]
+        );
+
+        assert!(matches!(
+            handler
+                .create(harness.org_id, request("night_planner", &[]))
+                .await,
+            Err(RepositoryError::Duplicate(_))
+        ));
+        assert!(matches!(
+            handler
+                .create(harness.org_id, request("auditor", &["audit:read"]))
+                .await,
+            Err(RepositoryError::Validation(_))
+        ));
+        assert!(matches!(
+            handler
+                .create(harness.org_id, request("night planner", &[]))
+                .await,
+            Err(RepositoryError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_system_roles_are_read_only() {
+        let harness = TestHarness::new().await;
+        let handler = ManageRolesHandler::new(harness.repositories());
+        let admin = harness.role(UserRole::Admin);
+
+        let update = UpdateRole {
+            permissions: Some(vec![]),
+            ..UpdateRole::default()
+        };
+        assert!(matches!(
+            handler.update(harness.org_id, admin.id, update).await,
+            Err(RepositoryError::Validation(_))
+        ));
+        assert!(matches!(
+            handler.delete(harness.org_id, admin.id).await,
+            Err(RepositoryError::Validation(_))
+        ));
+        assert!(matches!(
+            handler.find(Uuid::new_v4(), admin.id).await,
+            Err(RepositoryError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_rejects_held_roles() {
+        let harness = TestHarness::new().await;
+        let handler = ManageRolesHandler::new(harness.repositories());
+        let role = handler
+            .create(
+                harness.org_id,
+                request("night_planner", &["schedules:read"]),
+            )
+            .await
+            .unwrap();
+        let agent = harness.agent("Marie", "Dupont").await;
+        let ports = harness.repositories();
+        ports
+            .users
+            .update(&User {
+                role_id: Some(role.id),
+                ..agent.clone()
+            })
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            handler.delete(harness.org_id, role.id).await,
+            Err(RepositoryError::Validation(_))
+        ));
+
+        ports
+            .users
+            .update(&User {
+                role_id: None,
+                ..agent
+            })
+            .await
+            .unwrap();
+        handler.delete(harness.org_id, role.id).await.unwrap();
+        assert!(ports
+            .roles
+            .find_by_id(harness.org_id, role.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,548 @@
+//! Manage Shift Types Command
+//!
+//! Creates, updates, reorders and deletes the shift codes of an
+//! organization. Types still used by schedules are deactivated instead of
+//! deleted, so that past plannings keep their codes.
+//!
+//! Changes to the hour attributes are recorded as a version effective from
+//! a date: schedules before that date keep the previous definition.
+
+use std::collections::{BTreeSet, HashSet};
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::shift_type::{CreateShiftType, ShiftTypeError, UpdateShiftType};
+use crate::domain::entities::{ShiftType, ShiftTypeVersion};
+use crate::domain::services::ShiftTypeHistory;
+
+/// Outcome of a shift type deletion
+#[derive(Debug, Clone)]
+pub enum DeleteShiftTypeResult {
+    /// Unused type, removed
+    Deleted,
+    /// Type still used by schedules, deactivated
+    Deactivated {
+        shift_type: Box<ShiftType>,
+        schedule_count: i64,
+    },
+}
+
+/// Outcome of a shift type update
+#[derive(Debug, Clone)]
+pub struct UpdateShiftTypeResult {
+    pub shift_type: ShiftType,
+    /// (user_id, date) of the schedules counted differently
+    pub changed_schedules: Vec<(Uuid, NaiveDate)>,
+}
+
+/// Handler for shift type management
+pub struct ManageShiftTypesHandler {
+    repositories: Repositories,
+}
+
+fn validation_error(e: ShiftTypeError) -> RepositoryError {
+    RepositoryError::Validation(e.to_string())
+}
+
+impl ManageShiftTypesHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    async fn find(&self, org_id: Uuid, id: Uuid) -> RepoResult<ShiftType> {
+        self.repositories
+            .shift_types
+            .find_by_id(org_id, id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound(format!("shift type {}", id)))
+    }
+
+    /// Create a shift type, placed last unless an order is given
+    ///
+    /// The code is checked against the organization's code grammar, which
+    /// also gives the default night credit and holiday flags.
+    pub async fn create(&self, org_id: Uuid, request: CreateShiftType) -> RepoResult<ShiftType> {
+        let grammar = self
+            .repositories
+            .shift_types
+            .find_code_grammar(org_id)
+            .await?;
+
+        let display_order = match request.display_order {
+            Some(order) => order,
+            None => self
+                .repositories
+                .shift_types
+                .find_all(org_id)
+                .await?
+                .iter()
+                .map(|st| st.display_order + 1)
+                .max()
+                .unwrap_or(0),
+        };
+
+        let shift_type = CreateShiftType {
+            display_order: Some(display_order),
+            ..request
+        }
+        .into_shift_type(org_id, &grammar)
+        .map_err(validation_error)?;
+
+        self.repositories.shift_types.create(&shift_type).await
+    }
+
+    /// Update a shift type (the code itself is immutable)
+    ///
+    /// New hour attributes apply from `effective_from` (today by default)
+    /// until the next recorded version. The change is rejected when it
+    /// would alter schedules of a closed period.
+    pub async fn update(
+        &self,
+        org_id: Uuid,
+        id: Uuid,
+        request: UpdateShiftType,
+    ) -> RepoResult<UpdateShiftTypeResult> {
+        let shift_type = self.find(org_id, id).await?;
+        let effective_from = request
+            .effective_from
+            .unwrap_or_else(|| Utc::now().date_naive());
+        let updated = request.apply(&shift_type).map_err(validation_error)?;
+
+        if ShiftTypeVersion::of(&shift_type, effective_from).matches(&updated) {
+            return Ok(UpdateShiftTypeResult {
+                shift_type: self.repositories.shift_types.update(&updated).await?,
+                changed_schedules: Vec::new(),
+            });
+        }
+
+        let org_id = shift_type.organization_id;
+        let history =
+            ShiftTypeHistory::new(self.repositories.shift_types.find_versions(org_id).await?);
+        let versions = history.versions_of(id);
+        let next = versions
+            .iter()
+            .find(|v| v.effective_from > effective_from)
+            .map(|v| v.effective_from);
+
+        let changed: Vec<_> = self
+            .repositories
+            .schedules
+            .find_by_shift_type(org_id, id, Some(effective_from))
+            .await?
+            .into_iter()
+            .filter(|s| next.is_none_or(|next| s.date < next))
+            .map(|s| (s.user_id, s.date))
+            .collect();
+        self.ensure_open(org_id, &shift_type.code, &changed).await?;
+
+        // Keep the definition in force before the change
+        if !versions.iter().any(|v| v.effective_from < effective_from) {
+            let day_before = effective_from - Duration::days(1);
+            let previous = history.definition_on(&shift_type, day_before);
+            let start = shift_type.created_at.date_naive().min(day_before);
+            self.repositories
+                .shift_types
+                .save_version(&ShiftTypeVersion::of(&previous, start))
+                .await?;
+        }
+        self.repositories
+            .shift_types
+            .save_version(&ShiftTypeVersion::of(&updated, effective_from))
+            .await?;
+
+        // The row mirrors the latest version
+        let latest = match next.and_then(|_| versions.last()) {
+            Some(latest) => latest.apply(&updated),
+            None => updated,
+        };
+
+        Ok(UpdateShiftTypeResult {
+            shift_type: self.repositories.shift_types.update(&latest).await?,
+            changed_schedules: changed,
+        })
+    }
+
+    /// Reject a change touching schedules of a closed period
+    async fn ensure_open(
+        &self,
+        org_id: Uuid,
+        code: &str,
+        schedules: &[(Uuid, NaiveDate)],
+    ) -> RepoResult<()> {
+        // A period may start in the previous year
+        let years: BTreeSet<i32> = schedules
+            .iter()
+            .flat_map(|(_, date)| [date.year() - 1, date.year()])
+            .collect();
+
+        for year in years {
+            let periods = self.repositories.periods.find_by_year(org_id, year).await?;
+            let closed = periods.iter().find(|p| {
+                p.is_closed() && schedules.iter().any(|(_, date)| p.contains_date(*date))
+            });
+            if let Some(period) = closed {
+                return Err(RepositoryError::Validation(format!(
+                    "{} is used in closed period {} of {}; choose a later effective date",
+                    code,
+                    period.label(),
+                    period.year
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Versions of a shift type, oldest first
+    ///
+    /// A type never changed has a single version: its current attributes
+    /// since its creation.
+    pub async fn history(
+        &self,
+        org_id: Uuid,
+        id: Uuid,
+    ) -> RepoResult<(ShiftType, Vec<ShiftTypeVersion>)> {
+        let shift_type = self.find(org_id, id).await?;
+        let history =
+            ShiftTypeHistory::new(self.repositories.shift_types.find_versions(org_id).await?);
+
+        let versions = match history.versions_of(id) {
+            [] => vec![ShiftTypeVersion::of(
+                &shift_type,
+                shift_type.created_at.date_naive(),
+            )],
+            versions => versions.to_vec(),
+        };
+
+        Ok((shift_type, versions))
+    }
+
+    /// Delete an unused shift type, or deactivate it
+    pub async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<DeleteShiftTypeResult> {
+        let shift_type = self.find(org_id, id).await?;
+
+        let schedule_count = self
+            .repositories
+            .schedules
+            .count_by_shift_type(org_id, id)
+            .await?;
+        if schedule_count == 0 {
+            self.repositories.shift_types.delete(org_id, id).await?;
+            return Ok(DeleteShiftTypeResult::Deleted);
+        }
+
+        let shift_type = self
+            .repositories
+            .shift_types
+            .update(&ShiftType {
+                is_active: false,
+                ..shift_type
+            })
+            .await?;
+
+        Ok(DeleteShiftTypeResult::Deactivated {
+            shift_type: Box::new(shift_type),
+            schedule_count,
+        })
+    }
+
+    /// Set `display_order` from a list of IDs
+    ///
+    /// Listed types come first, in the given order; the others follow in
+    /// their current order. Returns every type of the organization.
+    pub async fn reorder(&self, org_id: Uuid, ids: &[Uuid]) -> RepoResult<Vec<ShiftType>> {
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = ids.iter().find(|id| !seen.insert(**id)) {
+            return Err(RepositoryError::Validation(format!(
+                "Shift type {} is listed twice",
+                duplicate
+            )));
+        }
+        if let Some(unknown) = ids
+            .iter()
+            .find(|id| !shift_types.iter().any(|st| st.id == **id))
+        {
+            return Err(RepositoryError::Validation(format!(
+                "Unknown shift type {}",
+                unknown
+            )));
+        }
+
+        let ordered = ids
+            .iter()
+            .filter_map(|id| shift_types.iter().find(|st| st.id == *id))
+            .chain(shift_types.iter().filter(|st| !seen.contains(&st.id)));
+
+        let mut reordered = Vec::with_capacity(shift_types.len());
+        for (order, shift_type) in ordered.enumerate() {
+            let order = order as i32;
+            if shift_type.display_order == order {
+                reordered.push(shift_type.clone());
+                continue;
+            }
+            reordered.push(
+                self.repositories
+                    .shift_types
+                    .update(&ShiftType {
+                        display_order: order,
+                        ..shift_type.clone()
+                    })
+                    .await?,
+            );
+        }
+
+        Ok(reordered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::TestHarness;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::ShiftCodeGrammar;
+
+    fn request(code: &str) -> CreateShiftType {
+        CreateShiftType {
+            code: code.to_string(),
+            description: None,
+            category: ShiftCategory::Special,
+            color_hex: "E6D9FF".to_string(),
+            icon: None,
+            duration_hours: 9.0,
+            night_hours: Some(0.0),
+            start_time: None,
+            end_time: None,
+            is_countable: None,
+            requires_recovery: None,
+            is_holiday_indicator: None,
+            is_rest_day: None,
+            display_order: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_places_last_and_rejects_duplicates() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+
+        let created = handler
+            .create(harness.org_id, request("x_9"))
+            .await
+            .unwrap();
+        assert_eq!(created.code, "X_9");
+        assert_eq!(created.display_order, harness.shift_types.len() as i32);
+
+        assert!(matches!(
+            handler.create(harness.org_id, request("X_9")).await,
+            Err(RepositoryError::Duplicate(_))
+        ));
+        assert!(matches!(
+            handler
+                .create(
+                    harness.org_id,
+                    CreateShiftType {
+                        color_hex: "blue".to_string(),
+                        ..request("X_BLUE")
+                    }
+                )
+                .await,
+            Err(RepositoryError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_follows_the_code_grammar() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+        harness.store.shift_types.set_code_grammar(
+            harness.org_id,
+            ShiftCodeGrammar {
+                allow_custom: false,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            handler.create(harness.org_id, request("X_11")).await,
+            Err(RepositoryError::Validation(_))
+        ));
+
+        handler
+            .delete(harness.org_id, harness.shift_type("7112").id)
+            .await
+            .unwrap();
+        let holiday = handler
+            .create(
+                harness.org_id,
+                CreateShiftType {
+                    category: ShiftCategory::Intermediate,
+                    night_hours: None,
+                    ..request("7112")
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(holiday.night_hours, 2.0);
+        assert!(holiday.is_holiday_indicator);
+    }
+
+    #[tokio::test]
+    async fn test_delete_deactivates_used_types() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+
+        harness
+            .plan(agent.id, harness.period(1).start_date, &["101", "101"])
+            .await;
+
+        let used = harness.shift_type("101").id;
+        let DeleteShiftTypeResult::Deactivated {
+            shift_type,
+            schedule_count,
+        } = handler.delete(harness.org_id, used).await.unwrap()
+        else {
+            panic!("expected deactivation");
+        };
+        assert!(!shift_type.is_active);
+        assert_eq!(schedule_count, 2);
+
+        let unused = harness.shift_type("ZM").id;
+        assert!(matches!(
+            handler.delete(harness.org_id, unused).await.unwrap(),
+            DeleteShiftTypeResult::Deleted
+        ));
+        let ports = harness.repositories();
+        assert!(ports
+            .shift_types
+            .find_by_id(harness.org_id, unused)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reorder() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+        let (rh, ch) = (harness.shift_type("RH").id, harness.shift_type("CH").id);
+
+        let reordered = handler.reorder(harness.org_id, &[rh, ch]).await.unwrap();
+
+        assert_eq!(reordered.len(), harness.shift_types.len());
+        assert_eq!((reordered[0].id, reordered[0].display_order), (rh, 0));
+        assert_eq!((reordered[1].id, reordered[1].display_order), (ch, 1));
+        assert_eq!(reordered[2].code, "101");
+
+        assert!(matches!(
+            handler.reorder(harness.org_id, &[rh, rh]).await,
+            Err(RepositoryError::Validation(_))
+        ));
+        assert!(matches!(
+            handler.reorder(harness.org_id, &[Uuid::new_v4()]).await,
+            Err(RepositoryError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_past_definitions() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+        let (p1, p2, p3) = (
+            harness.period(1).clone(),
+            harness.period(2).clone(),
+            harness.period(3).clone(),
+        );
+        let intermediate = harness.shift_type("111").clone();
+
+        harness.plan(agent.id, p1.start_date, &["111"; 5]).await;
+        harness.plan(agent.id, p3.start_date, &["111"; 5]).await;
+
+        let result = handler
+            .update(
+                harness.org_id,
+                intermediate.id,
+                UpdateShiftType {
+                    duration_hours: Some(10.0),
+                    effective_from: Some(p2.start_date),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.shift_type.duration_hours, 10.0);
+        assert_eq!(result.changed_schedules.len(), 5);
+
+        let p1_balance = harness.balance(&p1, agent.id).await;
+        assert_eq!(p1_balance.total_hours, 5.0 * intermediate.duration_hours);
+        assert_eq!(harness.balance(&p3, agent.id).await.total_hours, 50.0);
+
+        let (_, versions) = handler
+            .history(harness.org_id, intermediate.id)
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].duration_hours, intermediate.duration_hours);
+        assert_eq!(
+            (versions[1].effective_from, versions[1].duration_hours),
+            (p2.start_date, 10.0)
+        );
+
+        // Cosmetic changes are not versioned
+        let result = handler
+            .update(
+                harness.org_id,
+                intermediate.id,
+                UpdateShiftType {
+                    color_hex: Some("ff4444".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(result.changed_schedules.is_empty());
+        assert_eq!(
+            handler
+                .history(harness.org_id, intermediate.id)
+                .await
+                .unwrap()
+                .1
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_changes_to_closed_periods() {
+        let harness = TestHarness::new().await;
+        let handler = ManageShiftTypesHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+        let p1 = harness.period(1).clone();
+        let night = harness.shift_type("121").id;
+
+        harness.plan(agent.id, p1.start_date, &["121"; 3]).await;
+        harness.store.periods.close(p1.id).unwrap();
+
+        let update = |effective_from| UpdateShiftType {
+            night_hours: Some(7.0),
+            effective_from: Some(effective_from),
+            ..Default::default()
+        };
+        assert!(matches!(
+            handler
+                .update(harness.org_id, night, update(p1.start_date))
+                .await,
+            Err(RepositoryError::Validation(_))
+        ));
+        assert!(handler
+            .update(harness.org_id, night, update(harness.period(2).start_date))
+            .await
+            .is_ok());
+    }
+}
@@ -3,6 +3,10 @@
 //! Write operations / mutations.
 
 pub mod create_schedule;
+pub mod manage_roles;
+pub mod manage_shift_types;
+pub mod normalize_variants;
+pub mod remap_shift_type;
 pub mod validate_period;
 
 pub use create_schedule::*;
@@ -0,0 +1,241 @@
+//! Normalize Variants Command
+//!
+//! Replaces the codes whose variant does not fit their day (7101 on a
+//! Tuesday, 101 on Christmas) with the variant of the same family that
+//! does. Cells of closed periods, and cells whose expected code is not an
+//! active shift type of the organization, are left unchanged and reported.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::Schedule;
+use crate::domain::services::day_type_validator::DayTypeMismatch;
+use crate::domain::services::DayTypeValidator;
+
+/// Command to normalize the code variants of a date range
+#[derive(Debug, Clone)]
+pub struct NormalizeVariantsCommand {
+    pub organization_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Agents to normalize (all when None)
+    pub user_ids: Option<Vec<Uuid>>,
+    /// Author of the changes (None for system writes)
+    pub updated_by: Option<Uuid>,
+}
+
+/// Mismatched cells, normalized or not
+#[derive(Debug, Clone, Default)]
+pub struct NormalizeVariantsResult {
+    /// Cells rewritten with the expected code
+    pub normalized: Vec<DayTypeMismatch>,
+    /// Cells left unchanged (closed period or expected code not configured)
+    pub unresolved: Vec<DayTypeMismatch>,
+}
+
+impl NormalizeVariantsResult {
+    /// Schedule entries of the normalized cells
+    pub fn entries(&self) -> Vec<CreateSchedule> {
+        self.normalized
+            .iter()
+            .map(|m| CreateSchedule {
+                user_id: m.user_id,
+                shift_type_id: m.expected_shift_type_id,
+                date: m.date,
+                notes: None,
+            })
+            .collect()
+    }
+}
+
+/// Handler for variant normalization
+pub struct NormalizeVariantsHandler {
+    repositories: Repositories,
+}
+
+impl NormalizeVariantsHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Find the mismatched cells without writing anything
+    pub async fn preview(
+        &self,
+        command: &NormalizeVariantsCommand,
+    ) -> RepoResult<NormalizeVariantsResult> {
+        Ok(self.plan(command).await?.0)
+    }
+
+    /// Rewrite the mismatched cells that can be normalized
+    ///
+    /// Balances are not saved here: callers recalculate those of the
+    /// normalized cells.
+    pub async fn execute(
+        &self,
+        command: &NormalizeVariantsCommand,
+    ) -> RepoResult<NormalizeVariantsResult> {
+        let (result, schedules) = self.plan(command).await?;
+        if result.normalized.is_empty() {
+            return Ok(result);
+        }
+
+        let now = Utc::now();
+        let rewritten: Vec<Schedule> = result
+            .normalized
+            .iter()
+            .filter_map(|m| {
+                let schedule = schedules.get(&(m.user_id, m.date))?;
+                Some(Schedule {
+                    shift_type_id: m.expected_shift_type_id,
+                    updated_by: command.updated_by,
+                    updated_at: now,
+                    ..schedule.clone()
+                })
+            })
+            .collect();
+        self.repositories.schedules.bulk_upsert(&rewritten).await?;
+
+        Ok(result)
+    }
+
+    /// Split the mismatches of the range, keeping the schedules by cell
+    async fn plan(
+        &self,
+        command: &NormalizeVariantsCommand,
+    ) -> RepoResult<(
+        NormalizeVariantsResult,
+        HashMap<(Uuid, NaiveDate), Schedule>,
+    )> {
+        let org_id = command.organization_id;
+        if command.start_date > command.end_date {
+            return Err(RepositoryError::Validation(
+                "startDate must not be after endDate".to_string(),
+            ));
+        }
+
+        let mut schedules = self
+            .repositories
+            .schedules
+            .find_by_org_range(org_id, command.start_date, command.end_date)
+            .await?;
+        if let Some(user_ids) = &command.user_ids {
+            schedules.retain(|s| user_ids.contains(&s.user_id));
+        }
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+        let holidays: Vec<NaiveDate> = self
+            .repositories
+            .holidays
+            .find_by_range(org_id, command.start_date, command.end_date)
+            .await?
+            .into_iter()
+            .map(|h| h.date)
+            .collect();
+        let grammar = self
+            .repositories
+            .shift_types
+            .find_code_grammar(org_id)
+            .await?;
+
+        // A period may start in the previous year
+        let mut closed = Vec::new();
+        for year in command.start_date.year() - 1..=command.end_date.year() {
+            closed.extend(
+                self.repositories
+                    .periods
+                    .find_by_year(org_id, year)
+                    .await?
+                    .into_iter()
+                    .filter(|p| p.is_closed()),
+            );
+        }
+
+        let mut result = NormalizeVariantsResult::default();
+        for mismatch in DayTypeValidator::new(grammar).validate(&schedules, &shift_types, &holidays)
+        {
+            let in_closed_period = closed.iter().any(|p| p.contains_date(mismatch.date));
+            if mismatch.expected_shift_type_id.is_some() && !in_closed_period {
+                result.normalized.push(mismatch);
+            } else {
+                result.unresolved.push(mismatch);
+            }
+        }
+
+        let schedules = schedules
+            .into_iter()
+            .map(|s| ((s.user_id, s.date), s))
+            .collect();
+
+        Ok((result, schedules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::TestHarness;
+
+    #[tokio::test]
+    async fn test_normalizes_variants_outside_closed_periods() {
+        let harness = TestHarness::new().await;
+        let handler = NormalizeVariantsHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+        let (p1, p2) = (harness.period(1).clone(), harness.period(2).clone());
+        // P2 2026 starts on Monday 9 February
+        harness.holiday(p2.start_date + chrono::Duration::days(1), "Holiday");
+        harness.plan(agent.id, p1.start_date, &["7101"]).await;
+        harness
+            .plan(
+                agent.id,
+                p2.start_date,
+                &["7101", "101", "RH", "X_AM", "111", "112", "121"],
+            )
+            .await;
+        harness.store.periods.close(p1.id).unwrap();
+
+        let command = NormalizeVariantsCommand {
+            organization_id: harness.org_id,
+            start_date: p1.start_date,
+            end_date: p2.end_date,
+            user_ids: None,
+            updated_by: None,
+        };
+        let preview = handler.preview(&command).await.unwrap();
+        assert_eq!(preview.unresolved.len(), 1);
+        assert_eq!(preview.unresolved[0].date, p1.start_date);
+        let codes: Vec<_> = preview
+            .normalized
+            .iter()
+            .map(|m| (m.code.as_str(), m.expected_code.as_str()))
+            .collect();
+        // Tuesday is the holiday, Sunday 15 February holds 121
+        assert_eq!(
+            codes,
+            vec![("7101", "101"), ("101", "7101"), ("121", "6121")]
+        );
+
+        handler.execute(&command).await.unwrap();
+
+        assert!(handler
+            .preview(&command)
+            .await
+            .unwrap()
+            .normalized
+            .is_empty());
+        let holiday = harness
+            .repositories()
+            .schedules
+            .find_by_user_date(
+                harness.org_id,
+                agent.id,
+                p2.start_date + chrono::Duration::days(1),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(holiday.shift_type_id, Some(harness.shift_type("7101").id));
+    }
+}
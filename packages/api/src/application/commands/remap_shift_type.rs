@@ -0,0 +1,428 @@
+//! Remap Shift Type Command
+//!
+//! Moves every schedule using a shift type to another one, optionally
+//! within a date range, to retire or merge codes. A preview gives the
+//! schedules concerned and the balances before and after; applying the
+//! remap records it in the audit log.
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::audit_log::AuditAction;
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::{AuditLog, Period, Schedule, ShiftType};
+use crate::domain::services::{BalanceCalculator, ShiftTypeHistory};
+
+/// Command to remap the schedules of a shift type
+#[derive(Debug, Clone)]
+pub struct RemapShiftTypeCommand {
+    pub organization_id: Uuid,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    /// First date remapped (no lower bound when omitted)
+    pub start_date: Option<NaiveDate>,
+    /// Last date remapped, inclusive (no upper bound when omitted)
+    pub end_date: Option<NaiveDate>,
+    /// Author of the remap (None for system writes)
+    pub updated_by: Option<Uuid>,
+}
+
+/// Balance of an agent for a period, before and after the remap
+#[derive(Debug, Clone)]
+pub struct AffectedBalance {
+    pub period: Period,
+    pub before: PeriodBalance,
+    pub after: PeriodBalance,
+}
+
+/// Schedules and balances concerned by a remap
+#[derive(Debug, Clone)]
+pub struct RemapPreview {
+    pub source: ShiftType,
+    pub target: ShiftType,
+    /// Schedules using the source type in the range
+    pub schedules: Vec<Schedule>,
+    pub balances: Vec<AffectedBalance>,
+}
+
+impl RemapPreview {
+    /// Schedule entries of the remapped cells
+    pub fn entries(&self) -> Vec<CreateSchedule> {
+        self.schedules
+            .iter()
+            .map(|s| CreateSchedule {
+                user_id: s.user_id,
+                shift_type_id: Some(self.target.id),
+                date: s.date,
+                notes: s.notes.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Handler for shift type remapping
+pub struct RemapShiftTypeHandler {
+    repositories: Repositories,
+}
+
+impl RemapShiftTypeHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    async fn find(&self, org_id: Uuid, id: Uuid) -> RepoResult<ShiftType> {
+        self.repositories
+            .shift_types
+            .find_by_id(org_id, id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound(format!("shift type {}", id)))
+    }
+
+    /// Compute the remap without writing anything
+    ///
+    /// Fails when a schedule concerned lies in a closed period.
+    pub async fn preview(&self, command: &RemapShiftTypeCommand) -> RepoResult<RemapPreview> {
+        let org_id = command.organization_id;
+        let source = self.find(org_id, command.source_id).await?;
+        let target = self.find(org_id, command.target_id).await?;
+
+        if source.id == target.id {
+            return Err(RepositoryError::Validation(
+                "Choose another shift type as target".to_string(),
+            ));
+        }
+        if !target.is_active {
+            return Err(RepositoryError::Validation(format!(
+                "{} is inactive",
+                target.code
+            )));
+        }
+        if let (Some(start), Some(end)) = (command.start_date, command.end_date) {
+            if start > end {
+                return Err(RepositoryError::Validation(
+                    "startDate must not be after endDate".to_string(),
+                ));
+            }
+        }
+
+        let schedules: Vec<Schedule> = self
+            .repositories
+            .schedules
+            .find_by_shift_type(org_id, source.id, command.start_date)
+            .await?
+            .into_iter()
+            .filter(|s| command.end_date.is_none_or(|end| s.date <= end))
+            .collect();
+
+        let periods = self.periods_of(org_id, &schedules).await?;
+        if let Some(closed) = periods.iter().find(|p| p.is_closed()) {
+            return Err(RepositoryError::Validation(format!(
+                "{} is used in closed period {} of {}; narrow the date range",
+                source.code,
+                closed.label(),
+                closed.year
+            )));
+        }
+
+        let balances = self.balances(org_id, &target, &schedules, &periods).await?;
+
+        Ok(RemapPreview {
+            source,
+            target,
+            schedules,
+            balances,
+        })
+    }
+
+    /// Apply the remap and record it in the audit log
+    ///
+    /// Balances are not saved here: callers recalculate those of the
+    /// remapped schedules.
+    pub async fn execute(&self, command: &RemapShiftTypeCommand) -> RepoResult<RemapPreview> {
+        let preview = self.preview(command).await?;
+        if preview.schedules.is_empty() {
+            return Ok(preview);
+        }
+
+        let now = Utc::now();
+        let remapped: Vec<Schedule> = preview
+            .schedules
+            .iter()
+            .map(|s| Schedule {
+                shift_type_id: Some(preview.target.id),
+                updated_by: command.updated_by,
+                updated_at: now,
+                ..s.clone()
+            })
+            .collect();
+        self.repositories.schedules.bulk_upsert(&remapped).await?;
+
+        let entry = AuditLog::new(
+            preview.source.organization_id,
+            command.updated_by,
+            AuditAction::Update,
+            "shift_type_remap",
+            preview.source.id,
+        )
+        .with_values(
+            Some(json!({
+                "shiftTypeId": preview.source.id,
+                "code": preview.source.code,
+            })),
+            Some(json!({
+                "shiftTypeId": preview.target.id,
+                "code": preview.target.code,
+                "startDate": command.start_date,
+                "endDate": command.end_date,
+                "schedules": remapped.len(),
+            })),
+        );
+        self.repositories.audit_logs.record(&entry).await?;
+
+        Ok(preview)
+    }
+
+    /// Periods containing at least one of the schedules
+    async fn periods_of(&self, org_id: Uuid, schedules: &[Schedule]) -> RepoResult<Vec<Period>> {
+        // A period may start in the previous year
+        let years: BTreeSet<i32> = schedules
+            .iter()
+            .flat_map(|s| [s.date.year() - 1, s.date.year()])
+            .collect();
+
+        let mut periods = Vec::new();
+        for year in years {
+            periods.extend(
+                self.repositories
+                    .periods
+                    .find_by_year(org_id, year)
+                    .await?
+                    .into_iter()
+                    .filter(|p| schedules.iter().any(|s| p.contains_date(s.date))),
+            );
+        }
+        periods.sort_by_key(|p| p.start_date);
+        periods.dedup_by_key(|p| p.id);
+
+        Ok(periods)
+    }
+
+    /// Balances of the agents concerned, before and after the remap
+    async fn balances(
+        &self,
+        org_id: Uuid,
+        target: &ShiftType,
+        remapped: &[Schedule],
+        periods: &[Period],
+    ) -> RepoResult<Vec<AffectedBalance>> {
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+        let calculator = BalanceCalculator::new().with_history(ShiftTypeHistory::new(
+            self.repositories.shift_types.find_versions(org_id).await?,
+        ));
+
+        let mut balances = Vec::new();
+        for period in periods {
+            let holidays: Vec<_> = self
+                .repositories
+                .holidays
+                .find_by_range(org_id, period.start_date, period.end_date)
+                .await?
+                .into_iter()
+                .map(|h| h.date)
+                .collect();
+            let users: BTreeSet<Uuid> = remapped
+                .iter()
+                .filter(|s| period.contains_date(s.date))
+                .map(|s| s.user_id)
+                .collect();
+
+            for user_id in users {
+                let before = self
+                    .repositories
+                    .schedules
+                    .find_by_user_range(org_id, user_id, period.start_date, period.end_date)
+                    .await?;
+                let after: Vec<Schedule> = before
+                    .iter()
+                    .map(|s| {
+                        let shift_type_id = if remapped.iter().any(|r| r.id == s.id) {
+                            Some(target.id)
+                        } else {
+                            s.shift_type_id
+                        };
+                        Schedule {
+                            shift_type_id,
+                            ..s.clone()
+                        }
+                    })
+                    .collect();
+
+                balances.push(AffectedBalance {
+                    period: period.clone(),
+                    before: calculator.calculate(period, user_id, &before, &shift_types, &holidays),
+                    after: calculator.calculate(period, user_id, &after, &shift_types, &holidays),
+                });
+            }
+        }
+
+        Ok(balances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::create_schedule::{
+        CreateScheduleCommand, CreateScheduleHandler,
+    };
+    use crate::application::testing::{fixtures, TestHarness};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::infrastructure::persistence::repositories;
+    use crate::infrastructure::persistence::testing::TestDatabase;
+
+    fn command(harness: &TestHarness, source_id: Uuid, target_id: Uuid) -> RemapShiftTypeCommand {
+        RemapShiftTypeCommand {
+            organization_id: harness.org_id,
+            source_id,
+            target_id,
+            start_date: None,
+            end_date: None,
+            updated_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_then_remap_within_range() {
+        let harness = TestHarness::new().await;
+        let handler = RemapShiftTypeHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+        let (p1, p2) = (harness.period(1).clone(), harness.period(2).clone());
+        let (source, target) = (
+            harness.shift_type("X_10").clone(),
+            harness.shift_type("101"),
+        );
+
+        harness.plan(agent.id, p1.start_date, &["X_10"; 3]).await;
+        harness.plan(agent.id, p2.start_date, &["X_10"; 2]).await;
+
+        let command = RemapShiftTypeCommand {
+            start_date: Some(p1.start_date + chrono::Duration::days(1)),
+            ..command(&harness, source.id, target.id)
+        };
+        let preview = handler.preview(&command).await.unwrap();
+        assert_eq!(preview.schedules.len(), 4);
+        assert!(preview
+            .entries()
+            .iter()
+            .all(|e| e.shift_type_id == Some(target.id)));
+        assert_eq!(preview.balances.len(), 2);
+        let p1_balance = &preview.balances[0];
+        assert_eq!(p1_balance.period.id, p1.id);
+        assert_eq!(p1_balance.before.total_hours, 3.0 * source.duration_hours);
+        assert_eq!(
+            p1_balance.after.total_hours,
+            source.duration_hours + 2.0 * target.duration_hours
+        );
+        // Nothing written yet
+        assert_eq!(harness.balance(&p1, agent.id).await.total_hours, 30.0);
+
+        handler.execute(&command).await.unwrap();
+
+        let remapped = harness.balance(&p1, agent.id).await;
+        assert_eq!(remapped.total_hours, p1_balance.after.total_hours);
+        let entries = harness.store.audit_logs.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Update);
+        assert_eq!(entries[0].entity_id, Some(source.id));
+        assert_eq!(entries[0].new_value.as_ref().unwrap()["schedules"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_remaps() {
+        let harness = TestHarness::new().await;
+        let handler = RemapShiftTypeHandler::new(harness.repositories());
+        let agent = harness.agent("Marie", "Dupont").await;
+        let p1 = harness.period(1).clone();
+        let (source, target) = (harness.shift_type("X_10").id, harness.shift_type("101").id);
+
+        let same = handler.preview(&command(&harness, source, source)).await;
+        assert!(matches!(same, Err(RepositoryError::Validation(_))));
+
+        harness.plan(agent.id, p1.start_date, &["X_10"]).await;
+        harness.store.periods.close(p1.id).unwrap();
+        let closed = handler.execute(&command(&harness, source, target)).await;
+        assert!(matches!(closed, Err(RepositoryError::Validation(_))));
+        assert!(harness.store.audit_logs.entries().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn test_remap_without_start_date_on_postgres() {
+        let test_db = TestDatabase::new().await;
+        let org_id = test_db.organization().await;
+        let ports = repositories::postgres(&test_db.db);
+        let handler = RemapShiftTypeHandler::new(ports.clone());
+
+        let source = ports
+            .shift_types
+            .create(&ShiftType {
+                organization_id: org_id,
+                ..fixtures::shift_type("X_10", ShiftCategory::Special, 10.0, 0.0)
+            })
+            .await
+            .unwrap();
+        let target = ports
+            .shift_types
+            .create(&ShiftType {
+                organization_id: org_id,
+                ..fixtures::shift_type("101", ShiftCategory::Standard, 8.0, 2.0)
+            })
+            .await
+            .unwrap();
+        let agent = ports
+            .users
+            .create(&fixtures::agent(org_id, "Marie", "Dupont"))
+            .await
+            .unwrap();
+        let p1 = ports.periods.generate_for_year(org_id, 2026).await.unwrap()[0].clone();
+        let entries = (0..3)
+            .map(|day| CreateSchedule {
+                user_id: agent.id,
+                shift_type_id: Some(source.id),
+                date: p1.start_date + chrono::Duration::days(day),
+                notes: None,
+            })
+            .collect();
+        CreateScheduleHandler::new(ports.clone())
+            .execute(&CreateScheduleCommand::bulk(org_id, None, entries))
+            .await
+            .unwrap();
+
+        // No lower bound: every schedule of the source type
+        let command = RemapShiftTypeCommand {
+            organization_id: org_id,
+            source_id: source.id,
+            target_id: target.id,
+            start_date: None,
+            end_date: None,
+            updated_by: None,
+        };
+        let preview = handler.preview(&command).await.unwrap();
+        assert_eq!(preview.schedules.len(), 3);
+
+        handler.execute(&command).await.unwrap();
+        let remaining = ports
+            .schedules
+            .find_by_shift_type(org_id, source.id, None)
+            .await
+            .unwrap();
+        assert!(remaining.is_empty());
+
+        test_db.drop().await;
+    }
+}
@@ -37,3 +37,43 @@ impl Default for ValidatePeriodHandler {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::{TestHarness, VALID_PERIOD};
+
+    #[tokio::test]
+    async fn test_planned_period_is_valid() {
+        let harness = TestHarness::new().await;
+        let agent = harness.agent("Marie", "Dupont").await;
+        let period = harness.period(4).clone();
+
+        harness
+            .plan(agent.id, period.start_date, &VALID_PERIOD)
+            .await;
+        let balance = harness.balance(&period, agent.id).await;
+
+        assert_eq!(balance.total_hours, 152.0);
+        assert!(ValidatePeriodHandler::new().execute(&balance).is_valid);
+        assert!(balance.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_holiday_worked_requires_recovery() {
+        let harness = TestHarness::new().await;
+        let agent = harness.agent("Marie", "Dupont").await;
+        let period = harness.period(4).clone();
+        harness.holiday(period.start_date, "Holiday");
+
+        let mut codes = VALID_PERIOD;
+        codes[0] = "7101";
+        harness.plan(agent.id, period.start_date, &codes).await;
+        let balance = harness.balance(&period, agent.id).await;
+
+        let result = ValidatePeriodHandler::new().execute(&balance);
+        assert_eq!(balance.holidays_worked, 1);
+        assert!(!result.is_valid);
+        assert!(result.error_messages()[0].contains("RR"));
+    }
+}
@@ -6,3 +6,6 @@
 pub mod commands;
 pub mod ports;
 pub mod queries;
+
+#[cfg(test)]
+pub mod testing;
@@ -2,11 +2,16 @@
 //!
 //! Trait definitions for data access.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use uuid::Uuid;
 
-use crate::domain::entities::{Period, Schedule, ShiftType, User};
+use crate::domain::entities::{
+    AuditLog, Period, Role, Schedule, ShiftCodeGrammar, ShiftType, ShiftTypeVersion, User,
+};
+use crate::domain::services::holiday_calculator::Holiday;
 
 /// Result type for repository operations
 pub type RepoResult<T> = Result<T, RepositoryError>;
@@ -30,8 +35,8 @@ pub enum RepositoryError {
 /// User repository port
 #[async_trait]
 pub trait UserRepository: Send + Sync {
-    /// Find user by ID
-    async fn find_by_id(&self, id: Uuid) -> RepoResult<Option<User>>;
+    /// Find user by ID in organization
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<User>>;
 
     /// Find user by email
     async fn find_by_email(&self, org_id: Uuid, email: &str) -> RepoResult<Option<User>>;
@@ -48,22 +53,50 @@ pub trait UserRepository: Send + Sync {
     /// Update user
     async fn update(&self, user: &User) -> RepoResult<User>;
 
-    /// Delete user (soft delete)
-    async fn delete(&self, id: Uuid) -> RepoResult<()>;
+    /// Delete user in organization (soft delete)
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()>;
+
+    /// Count users holding a role
+    async fn count_by_role(&self, org_id: Uuid, role_id: Uuid) -> RepoResult<i64>;
+}
+
+/// Role repository port
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+    /// Find role by ID in organization
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Role>>;
+
+    /// Find all roles in organization, system roles first
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<Role>>;
+
+    /// Create role
+    async fn create(&self, role: &Role) -> RepoResult<Role>;
+
+    /// Update role
+    async fn update(&self, role: &Role) -> RepoResult<Role>;
+
+    /// Delete role in organization
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()>;
 }
 
 /// Schedule repository port
 #[async_trait]
 pub trait ScheduleRepository: Send + Sync {
-    /// Find schedule by ID
-    async fn find_by_id(&self, id: Uuid) -> RepoResult<Option<Schedule>>;
+    /// Find schedule by ID in organization
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Schedule>>;
 
-    /// Find schedule for user on date
-    async fn find_by_user_date(&self, user_id: Uuid, date: NaiveDate) -> RepoResult<Option<Schedule>>;
+    /// Find schedule for user of organization on date
+    async fn find_by_user_date(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> RepoResult<Option<Schedule>>;
 
-    /// Find all schedules for user in date range
+    /// Find all schedules for user of organization in date range
     async fn find_by_user_range(
         &self,
+        org_id: Uuid,
         user_id: Uuid,
         start: NaiveDate,
         end: NaiveDate,
@@ -83,15 +116,26 @@ pub trait ScheduleRepository: Send + Sync {
     /// Bulk upsert schedules
     async fn bulk_upsert(&self, schedules: &[Schedule]) -> RepoResult<Vec<Schedule>>;
 
-    /// Delete schedule
-    async fn delete(&self, id: Uuid) -> RepoResult<()>;
+    /// Delete schedule in organization
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()>;
+
+    /// Count schedules using a shift type in organization
+    async fn count_by_shift_type(&self, org_id: Uuid, shift_type_id: Uuid) -> RepoResult<i64>;
+
+    /// Find schedules using a shift type in organization, from a date if given
+    async fn find_by_shift_type(
+        &self,
+        org_id: Uuid,
+        shift_type_id: Uuid,
+        from: Option<NaiveDate>,
+    ) -> RepoResult<Vec<Schedule>>;
 }
 
 /// ShiftType repository port
 #[async_trait]
 pub trait ShiftTypeRepository: Send + Sync {
-    /// Find shift type by ID
-    async fn find_by_id(&self, id: Uuid) -> RepoResult<Option<ShiftType>>;
+    /// Find shift type by ID in organization
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<ShiftType>>;
 
     /// Find shift type by code
     async fn find_by_code(&self, org_id: Uuid, code: &str) -> RepoResult<Option<ShiftType>>;
@@ -108,15 +152,25 @@ pub trait ShiftTypeRepository: Send + Sync {
     /// Update shift type
     async fn update(&self, shift_type: &ShiftType) -> RepoResult<ShiftType>;
 
-    /// Delete shift type (soft delete)
-    async fn delete(&self, id: Uuid) -> RepoResult<()>;
+    /// Delete shift type in organization (callers deactivate types still
+    /// in use instead)
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()>;
+
+    /// Find the versions of the shift types in organization
+    async fn find_versions(&self, org_id: Uuid) -> RepoResult<Vec<ShiftTypeVersion>>;
+
+    /// Save a version (replaces the one with the same effective date)
+    async fn save_version(&self, version: &ShiftTypeVersion) -> RepoResult<ShiftTypeVersion>;
+
+    /// Find the code grammar of an organization (defaults when not configured)
+    async fn find_code_grammar(&self, org_id: Uuid) -> RepoResult<ShiftCodeGrammar>;
 }
 
 /// Period repository port
 #[async_trait]
 pub trait PeriodRepository: Send + Sync {
-    /// Find period by ID
-    async fn find_by_id(&self, id: Uuid) -> RepoResult<Option<Period>>;
+    /// Find period by ID in organization
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Period>>;
 
     /// Find period containing date
     async fn find_by_date(&self, org_id: Uuid, date: NaiveDate) -> RepoResult<Option<Period>>;
@@ -130,3 +184,34 @@ pub trait PeriodRepository: Send + Sync {
     /// Generate periods for year
     async fn generate_for_year(&self, org_id: Uuid, year: i32) -> RepoResult<Vec<Period>>;
 }
+
+/// Holiday repository port
+#[async_trait]
+pub trait HolidayRepository: Send + Sync {
+    /// Find holidays of an organization in a date range
+    async fn find_by_range(
+        &self,
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Holiday>>;
+}
+
+/// Audit log repository port
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Record an entry
+    async fn record(&self, entry: &AuditLog) -> RepoResult<AuditLog>;
+}
+
+/// Set of repositories used by the application services
+#[derive(Clone)]
+pub struct Repositories {
+    pub users: Arc<dyn UserRepository>,
+    pub roles: Arc<dyn RoleRepository>,
+    pub schedules: Arc<dyn ScheduleRepository>,
+    pub shift_types: Arc<dyn ShiftTypeRepository>,
+    pub periods: Arc<dyn PeriodRepository>,
+    pub holidays: Arc<dyn HolidayRepository>,
+    pub audit_logs: Arc<dyn AuditLogRepository>,
+}
@@ -0,0 +1,191 @@
+//! Get Period Balances Query
+//!
+//! Computes the balance of every agent for a period from the stored
+//! schedules, together with its quota validation. Shift types count as
+//! defined on each schedule date.
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::application::commands::validate_period::ValidatePeriodHandler;
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::{Period, User};
+use crate::domain::services::quota_validator::ValidationResult;
+use crate::domain::services::{BalanceCalculator, ShiftTypeHistory};
+
+/// Query for the balances of a period
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetPeriodBalancesQuery {
+    pub organization_id: Uuid,
+    pub period_id: Uuid,
+}
+
+/// Balance and validation of one agent
+#[derive(Debug, Clone)]
+pub struct AgentPeriodBalance {
+    pub user: User,
+    pub balance: PeriodBalance,
+    pub validation: ValidationResult,
+}
+
+/// Balances of a period
+#[derive(Debug, Clone)]
+pub struct PeriodBalancesResult {
+    pub period: Period,
+    pub agents: Vec<AgentPeriodBalance>,
+}
+
+/// Handler for period balances
+pub struct GetPeriodBalancesHandler {
+    repositories: Repositories,
+    validator: ValidatePeriodHandler,
+}
+
+impl GetPeriodBalancesHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self {
+            repositories,
+            validator: ValidatePeriodHandler::new(),
+        }
+    }
+
+    /// Balances of the active agents, and of former agents planned in the period
+    pub async fn execute(
+        &self,
+        query: &GetPeriodBalancesQuery,
+    ) -> RepoResult<PeriodBalancesResult> {
+        let period = self
+            .repositories
+            .periods
+            .find_by_id(query.organization_id, query.period_id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound(format!("period {}", query.period_id)))?;
+        let org_id = query.organization_id;
+
+        let users = self.repositories.users.find_all(org_id).await?;
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+        let calculator = BalanceCalculator::new().with_history(ShiftTypeHistory::new(
+            self.repositories.shift_types.find_versions(org_id).await?,
+        ));
+        let schedules = self
+            .repositories
+            .schedules
+            .find_by_org_range(org_id, period.start_date, period.end_date)
+            .await?;
+        let holidays: Vec<_> = self
+            .repositories
+            .holidays
+            .find_by_range(org_id, period.start_date, period.end_date)
+            .await?
+            .into_iter()
+            .map(|h| h.date)
+            .collect();
+
+        let agents = users
+            .into_iter()
+            .filter(|u| u.is_active || schedules.iter().any(|s| s.user_id == u.id))
+            .map(|user| {
+                let balance =
+                    calculator.calculate(&period, user.id, &schedules, &shift_types, &holidays);
+                let validation = self.validator.execute(&balance);
+                AgentPeriodBalance {
+                    user,
+                    balance,
+                    validation,
+                }
+            })
+            .collect();
+
+        Ok(PeriodBalancesResult { period, agents })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::{TestHarness, VALID_PERIOD};
+
+    #[tokio::test]
+    async fn test_balances_of_planned_and_empty_agents() {
+        let harness = TestHarness::new().await;
+        let dupont = harness.agent("Marie", "Dupont").await;
+        let martin = harness.agent("Luc", "Martin").await;
+        let period = harness.period(5).clone();
+
+        harness
+            .plan(dupont.id, period.start_date, &VALID_PERIOD)
+            .await;
+
+        let result = GetPeriodBalancesHandler::new(harness.repositories())
+            .execute(&GetPeriodBalancesQuery {
+                organization_id: harness.org_id,
+                period_id: period.id,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.period.id, period.id);
+        assert_eq!(result.agents.len(), 2);
+
+        let planned = &result.agents[0];
+        assert_eq!(planned.user.id, dupont.id);
+        assert_eq!(planned.balance.total_hours, 152.0);
+        assert!(planned.validation.is_valid);
+        assert!(!planned.validation.has_warnings());
+
+        let empty = &result.agents[1];
+        assert_eq!(empty.user.id, martin.id);
+        assert_eq!(empty.balance.total_hours, 0.0);
+        assert!(empty.validation.is_valid);
+        assert_eq!(empty.validation.warnings.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_deactivated_agents_with_schedules() {
+        let harness = TestHarness::new().await;
+        let dupont = harness.agent("Marie", "Dupont").await;
+        let martin = harness.agent("Luc", "Martin").await;
+        let period = harness.period(1).clone();
+
+        harness.plan(dupont.id, period.start_date, &["101"]).await;
+        let ports = harness.repositories();
+        ports.users.delete(harness.org_id, dupont.id).await.unwrap();
+        ports.users.delete(harness.org_id, martin.id).await.unwrap();
+
+        let result = GetPeriodBalancesHandler::new(ports)
+            .execute(&GetPeriodBalancesQuery {
+                organization_id: harness.org_id,
+                period_id: period.id,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.agents.len(), 1);
+        assert_eq!(result.agents[0].user.id, dupont.id);
+        assert_eq!(result.agents[0].balance.total_hours, 8.0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_period() {
+        let harness = TestHarness::new().await;
+        let handler = GetPeriodBalancesHandler::new(harness.repositories());
+
+        let result = handler
+            .execute(&GetPeriodBalancesQuery {
+                organization_id: harness.org_id,
+                period_id: Uuid::new_v4(),
+            })
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound(_))));
+
+        // Periods of another organization are not found either
+        let result = handler
+            .execute(&GetPeriodBalancesQuery {
+                organization_id: Uuid::new_v4(),
+                period_id: harness.period(1).id,
+            })
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound(_))));
+    }
+}
@@ -1,11 +1,19 @@
 //! Get Planning Query
 //!
-//! Retrieves planning matrix data for display.
+//! Retrieves planning matrix data for display. Cells holding a code
+//! variant that does not fit their day (see `DayTypeValidator`) carry the
+//! expected code.
 
-use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::application::ports::{RepoResult, Repositories, RepositoryError};
+use crate::domain::entities::Period;
+use crate::domain::services::DayTypeValidator;
+
 /// Query parameters for planning matrix
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetPlanningQuery {
@@ -57,7 +65,10 @@ pub struct CellData {
     pub color_hex: Option<String>,
     pub is_holiday: bool,
     pub is_weekend: bool,
+    /// ISO weekday (1 = Monday)
     pub day_of_week: u8,
+    /// Variant fitting the day, when the cell holds another one
+    pub expected_code: Option<String>,
 }
 
 /// Holiday information
@@ -97,3 +108,206 @@ impl GetPlanningQuery {
         }
     }
 }
+
+impl From<&Period> for PeriodInfo {
+    fn from(period: &Period) -> Self {
+        Self {
+            id: period.id,
+            number: period.number,
+            start_date: period.start_date,
+            end_date: period.end_date,
+        }
+    }
+}
+
+/// Handler for the planning matrix
+pub struct GetPlanningHandler {
+    repositories: Repositories,
+}
+
+impl GetPlanningHandler {
+    pub fn new(repositories: Repositories) -> Self {
+        Self { repositories }
+    }
+
+    /// Build the matrix: one row per active agent, one cell per day
+    ///
+    /// The period is the requested one, or the one containing the first day.
+    pub async fn execute(&self, query: &GetPlanningQuery) -> RepoResult<PlanningMatrixResponse> {
+        let org_id = query.organization_id;
+        if query.start_date > query.end_date {
+            return Err(RepositoryError::Validation(
+                "start_date must not be after end_date".to_string(),
+            ));
+        }
+
+        let period = match query.period_id {
+            Some(period_id) => Some(
+                self.repositories
+                    .periods
+                    .find_by_id(org_id, period_id)
+                    .await?
+                    .ok_or_else(|| RepositoryError::NotFound(format!("period {}", period_id)))?,
+            ),
+            None => {
+                self.repositories
+                    .periods
+                    .find_by_date(org_id, query.start_date)
+                    .await?
+            }
+        };
+
+        let mut agents = self.repositories.users.find_active(org_id).await?;
+        if let Some(user_ids) = &query.user_ids {
+            agents.retain(|u| user_ids.contains(&u.id));
+        }
+
+        let shift_types = self.repositories.shift_types.find_all(org_id).await?;
+        let schedules = self
+            .repositories
+            .schedules
+            .find_by_org_range(org_id, query.start_date, query.end_date)
+            .await?;
+        let holidays = self
+            .repositories
+            .holidays
+            .find_by_range(org_id, query.start_date, query.end_date)
+            .await?;
+        let grammar = self
+            .repositories
+            .shift_types
+            .find_code_grammar(org_id)
+            .await?;
+
+        let holiday_dates: Vec<NaiveDate> = holidays.iter().map(|h| h.date).collect();
+        let mismatches: HashMap<_, _> = DayTypeValidator::new(grammar)
+            .validate(&schedules, &shift_types, &holiday_dates)
+            .into_iter()
+            .map(|m| ((m.user_id, m.date), m.expected_code))
+            .collect();
+        let shift_types: HashMap<_, _> = shift_types.into_iter().map(|st| (st.id, st)).collect();
+        let schedules: HashMap<_, _> = schedules
+            .into_iter()
+            .map(|s| ((s.user_id, s.date), s))
+            .collect();
+
+        let days: Vec<NaiveDate> = query
+            .start_date
+            .iter_days()
+            .take_while(|d| *d <= query.end_date)
+            .collect();
+
+        let agents = agents
+            .into_iter()
+            .map(|agent| AgentRow {
+                cells: days
+                    .iter()
+                    .map(|&date| {
+                        let schedule = schedules.get(&(agent.id, date));
+                        let shift_type = schedule
+                            .and_then(|s| s.shift_type_id)
+                            .and_then(|id| shift_types.get(&id));
+
+                        CellData {
+                            date,
+                            schedule_id: schedule.map(|s| s.id),
+                            shift_code: shift_type.map(|st| st.code.clone()),
+                            color_hex: shift_type.map(|st| st.color_hex.clone()),
+                            is_holiday: holiday_dates.contains(&date),
+                            is_weekend: matches!(date.weekday(), Weekday::Sat | Weekday::Sun),
+                            day_of_week: date.weekday().number_from_monday() as u8,
+                            expected_code: mismatches.get(&(agent.id, date)).cloned(),
+                        }
+                    })
+                    .collect(),
+                user_id: agent.id,
+                first_name: agent.first_name,
+                last_name: agent.last_name,
+                matricule: agent.matricule,
+            })
+            .collect();
+
+        Ok(PlanningMatrixResponse {
+            start_date: query.start_date,
+            end_date: query.end_date,
+            period_info: period.as_ref().map(PeriodInfo::from),
+            agents,
+            holidays: holidays
+                .into_iter()
+                .map(|h| HolidayInfo {
+                    date: h.date,
+                    name: h.name,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::TestHarness;
+
+    #[tokio::test]
+    async fn test_matrix_cells_and_flags() {
+        let harness = TestHarness::new().await;
+        let dupont = harness.agent("Marie", "Dupont").await;
+        let martin = harness.agent("Luc", "Martin").await;
+        let period = harness.period(3).clone();
+        // P3 2026 starts on Monday 9 March
+        harness.holiday(period.start_date, "Holiday");
+        harness
+            .plan(dupont.id, period.start_date, &["7101", "RR", "7101"])
+            .await;
+
+        let matrix = GetPlanningHandler::new(harness.repositories())
+            .execute(&GetPlanningQuery::for_period(
+                harness.org_id,
+                period.id,
+                period.start_date,
+                period.end_date,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(matrix.period_info.as_ref().unwrap().number, 3);
+        assert_eq!(matrix.holidays.len(), 1);
+        assert_eq!(matrix.agents.len(), 2);
+
+        let row = &matrix.agents[0];
+        assert_eq!(row.user_id, dupont.id);
+        assert_eq!(row.cells.len(), 28);
+        assert_eq!(row.cells[0].shift_code.as_deref(), Some("7101"));
+        assert_eq!(row.cells[0].color_hex.as_deref(), Some("FFD9E6"));
+        assert!(row.cells[0].is_holiday);
+        assert!(row.cells[0].expected_code.is_none());
+        assert_eq!(row.cells[2].expected_code.as_deref(), Some("101"));
+        assert_eq!(row.cells[0].day_of_week, 1);
+        assert!(row.cells[5].is_weekend && row.cells[6].is_weekend);
+        assert!(row.cells[3].schedule_id.is_none());
+
+        assert_eq!(matrix.agents[1].user_id, martin.id);
+        assert!(matrix.agents[1]
+            .cells
+            .iter()
+            .all(|c| c.shift_code.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_inverted_range() {
+        let harness = TestHarness::new().await;
+        let day = harness.period(1).start_date;
+
+        let result = GetPlanningHandler::new(harness.repositories())
+            .execute(&GetPlanningQuery {
+                organization_id: harness.org_id,
+                start_date: day,
+                end_date: day - chrono::Duration::days(1),
+                user_ids: None,
+                period_id: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(RepositoryError::Validation(_))));
+    }
+}
@@ -5,6 +5,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::entities::{Schedule, ShiftType};
+use crate::domain::services::ShiftTypeHistory;
+
 /// Query for period statistics
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetPeriodStatisticsQuery {
@@ -21,6 +24,8 @@ pub struct PeriodStatisticsResponse {
     pub compliant_agents: i32,
     pub compliance_rate: f64,
     pub total_hours: f64,
+    /// Net hour bank movement of the period (closed periods only)
+    pub hour_bank_delta: Option<f64>,
     pub total_night_hours: f64,
     pub shift_distribution: Vec<ShiftCount>,
     pub validation_issues: Vec<ValidationIssue>,
@@ -36,6 +41,39 @@ pub struct ShiftCount {
     pub night_hours: f64,
 }
 
+impl ShiftCount {
+    /// Count the shift types used in `schedules`, in `shift_types` order
+    ///
+    /// Hours come from the definition in force on each schedule date.
+    pub fn distribution(
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+    ) -> Vec<ShiftCount> {
+        shift_types
+            .iter()
+            .filter_map(|st| {
+                let definitions: Vec<_> = schedules
+                    .iter()
+                    .filter(|s| s.shift_type_id == Some(st.id))
+                    .map(|s| history.definition_on(st, s.date))
+                    .collect();
+                (!definitions.is_empty()).then(|| ShiftCount {
+                    code: st.code.clone(),
+                    description: st.description.clone(),
+                    count: definitions.len() as i32,
+                    hours: definitions
+                        .iter()
+                        .filter(|d| d.is_countable)
+                        .map(|d| d.duration_hours)
+                        .sum(),
+                    night_hours: definitions.iter().map(|d| d.night_hours).sum(),
+                })
+            })
+            .collect()
+    }
+}
+
 /// Validation issue summary
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidationIssue {
@@ -63,6 +101,8 @@ pub struct UserBalanceResponse {
     pub jc: LeaveBalance,
     /// Summary by period
     pub periods: Vec<PeriodSummary>,
+    /// Hour bank balance carried over from closed periods
+    pub hour_bank_balance: f64,
 }
 
 /// Leave balance details
@@ -86,9 +126,92 @@ pub struct PeriodSummary {
     pub period_id: Uuid,
     pub period_number: i32,
     pub total_hours: f64,
+    /// Hours above (+) or below (-) quota (closed periods only)
+    pub hour_bank_delta: Option<f64>,
     pub night_hours: f64,
     pub ch_count: i32,
     pub rh_count: i32,
     pub cv_count: i32,
     pub is_valid: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::{TestHarness, VALID_PERIOD};
+    use crate::domain::entities::ShiftTypeVersion;
+    use crate::domain::services::MonthlyStats;
+
+    #[tokio::test]
+    async fn test_period_statistics_from_planned_schedules() {
+        let harness = TestHarness::new().await;
+        let dupont = harness.agent("Marie", "Dupont").await;
+        let martin = harness.agent("Luc", "Martin").await;
+        let period = harness.period(2).clone();
+
+        harness
+            .plan(dupont.id, period.start_date, &VALID_PERIOD)
+            .await;
+        harness
+            .plan(martin.id, period.start_date, &["121"; 5])
+            .await;
+
+        let schedules = harness
+            .repositories()
+            .schedules
+            .find_by_org_range(harness.org_id, period.start_date, period.end_date)
+            .await
+            .unwrap();
+        let split = MonthlyStats::split_period(
+            &period,
+            &[dupont.id, martin.id],
+            &schedules,
+            &harness.shift_types,
+            &ShiftTypeHistory::default(),
+        );
+
+        // P2 2026 runs from 9 February to 8 March
+        assert!(split.is_cross_month);
+        assert_eq!(split.months.len(), 2);
+        assert_eq!(split.total[0].total_hours, 152.0);
+        assert_eq!(split.total[1].total_hours, 40.0);
+        assert_eq!(split.total[1].night_hours, 40.0);
+        assert_eq!(
+            split.months[0].agents[0].total_hours + split.months[1].agents[0].total_hours,
+            152.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shift_distribution_follows_versions() {
+        let harness = TestHarness::new().await;
+        let agent = harness.agent("Marie", "Dupont").await;
+        let period = harness.period(1).clone();
+        let change = period.start_date + chrono::Duration::days(2);
+        let shift_type = harness.shift_type("101").clone();
+
+        harness.plan(agent.id, period.start_date, &["101"; 4]).await;
+        let schedules = harness
+            .repositories()
+            .schedules
+            .find_by_org_range(harness.org_id, period.start_date, period.end_date)
+            .await
+            .unwrap();
+        // 101 lasted 7h with 1h of night until `change`
+        let history = ShiftTypeHistory::new(vec![
+            ShiftTypeVersion {
+                duration_hours: 7.0,
+                night_hours: 1.0,
+                ..ShiftTypeVersion::of(&shift_type, period.start_date)
+            },
+            ShiftTypeVersion::of(&shift_type, change),
+        ]);
+
+        let distribution = ShiftCount::distribution(&schedules, &harness.shift_types, &history);
+
+        assert_eq!(distribution.len(), 1);
+        assert_eq!(distribution[0].count, 4);
+        assert_eq!(distribution[0].hours, 2.0 * 7.0 + 2.0 * 8.0);
+        assert_eq!(distribution[0].night_hours, 2.0 * 1.0 + 2.0 * 2.0);
+    }
+}
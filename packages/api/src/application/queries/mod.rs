@@ -2,6 +2,7 @@
 //!
 //! Read operations.
 
+pub mod get_period_balances;
 pub mod get_planning;
 pub mod get_statistics;
 
@@ -0,0 +1,330 @@
+//! Application Test Harness
+//!
+//! An organization seeded in the in-memory repositories with the system
+//! roles and default shift codes (seeds/001_initial_data.sql) and the
+//! periods of 2026, so that application flows can be tested end to end
+//! without a database.
+//!
+//! The read models of the handlers (`planning_data`, `balances`) query the
+//! pool rather than the repository ports, so the harness does not cover
+//! them: their tests run on Postgres (`persistence::testing::TestDatabase`).
+
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::application::commands::create_schedule::{
+    CreateScheduleCommand, CreateScheduleHandler, CreateScheduleResult,
+};
+use crate::application::ports::Repositories;
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::shift_type::ShiftCategory;
+use crate::domain::entities::user::UserRole;
+use crate::domain::entities::{Period, Role, ShiftType, User};
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::domain::services::{BalanceCalculator, ShiftTypeHistory};
+use crate::infrastructure::persistence::memory::InMemoryRepositories;
+
+/// Default shift codes: code, category, color, duration, night hours
+const DEFAULT_SHIFT_TYPES: &[(&str, ShiftCategory, &str, f64, f64)] = &[
+    ("101", ShiftCategory::Standard, "FFD9E6", 8.0, 2.0),
+    ("102", ShiftCategory::Standard, "FFE6F0", 8.0, 2.0),
+    ("6101", ShiftCategory::Standard, "FFD9E6", 8.0, 2.0),
+    ("6102", ShiftCategory::Standard, "FFE6F0", 8.0, 2.0),
+    ("7101", ShiftCategory::Standard, "FFD9E6", 8.0, 2.0),
+    ("7102", ShiftCategory::Standard, "FFE6F0", 8.0, 2.0),
+    ("111", ShiftCategory::Intermediate, "D9E6FF", 8.0, 2.0),
+    ("112", ShiftCategory::Intermediate, "E6F0FF", 8.0, 2.0),
+    ("6111", ShiftCategory::Intermediate, "D9E6FF", 8.0, 2.0),
+    ("6112", ShiftCategory::Intermediate, "E6F0FF", 8.0, 2.0),
+    ("7111", ShiftCategory::Intermediate, "D9E6FF", 8.0, 2.0),
+    ("7112", ShiftCategory::Intermediate, "E6F0FF", 8.0, 2.0),
+    ("121", ShiftCategory::Night, "FFE6CC", 8.0, 8.0),
+    ("6121", ShiftCategory::Night, "FFE6CC", 8.0, 8.0),
+    ("7121", ShiftCategory::Night, "FFE6CC", 8.0, 8.0),
+    ("X_AM", ShiftCategory::Partial, "D9F2D9", 8.0, 2.0),
+    ("X_PM", ShiftCategory::Partial, "D9F2D9", 8.0, 2.0),
+    ("X_10", ShiftCategory::Special, "E6D9FF", 10.0, 0.0),
+    ("AG", ShiftCategory::Special, "FF4444", 8.0, 0.0),
+    ("RH", ShiftCategory::Rest, "CCCCCC", 0.0, 0.0),
+    ("CH", ShiftCategory::Rest, "D5D5D5", 0.0, 0.0),
+    ("RR", ShiftCategory::Rest, "C9C9C9", 0.0, 0.0),
+    ("ZM", ShiftCategory::Rest, "F0F0F0", 0.0, 0.0),
+    ("CN", ShiftCategory::Leave, "FFFFCC", 8.0, 0.0),
+    ("JC", ShiftCategory::Leave, "FFFFCC", 8.0, 0.0),
+    ("CV", ShiftCategory::Leave, "96D1CC", 0.0, 0.0),
+];
+
+/// Seeded organization over in-memory repositories
+pub struct TestHarness {
+    pub org_id: Uuid,
+    pub store: InMemoryRepositories,
+    pub roles: Vec<Role>,
+    pub shift_types: Vec<ShiftType>,
+    pub periods: Vec<Period>,
+}
+
+impl TestHarness {
+    /// Organization anchored on 2026-01-12 with the default codes and 2026 periods
+    pub async fn new() -> Self {
+        let org_id = Uuid::new_v4();
+        let store = InMemoryRepositories::new();
+        let ports = store.ports();
+
+        let mut roles = Vec::new();
+        for role in [UserRole::Admin, UserRole::Planner, UserRole::Agent] {
+            let role = Role {
+                id: Uuid::new_v4(),
+                organization_id: org_id,
+                name: role.to_string(),
+                display_name: role.to_string(),
+                permissions: role
+                    .default_permissions()
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect(),
+                is_system: true,
+                created_at: Utc::now(),
+            };
+            roles.push(ports.roles.create(&role).await.unwrap());
+        }
+
+        let mut shift_types = Vec::new();
+        for (order, (code, category, color, hours, night)) in DEFAULT_SHIFT_TYPES.iter().enumerate()
+        {
+            let is_rest = matches!(category, ShiftCategory::Rest) || *code == "CV";
+            let shift_type = ShiftType {
+                organization_id: org_id,
+                color_hex: color.to_string(),
+                is_countable: !is_rest,
+                requires_recovery: code.starts_with('7'),
+                is_rest_day: is_rest,
+                display_order: order as i32,
+                ..fixtures::shift_type(code, *category, *hours, *night)
+            };
+            shift_types.push(ports.shift_types.create(&shift_type).await.unwrap());
+        }
+
+        store
+            .periods
+            .set_anchor(org_id, NaiveDate::from_ymd_opt(2026, 1, 12).unwrap());
+        let periods = ports.periods.generate_for_year(org_id, 2026).await.unwrap();
+
+        Self {
+            org_id,
+            store,
+            roles,
+            shift_types,
+            periods,
+        }
+    }
+
+    /// Repository ports of the harness
+    pub fn repositories(&self) -> Repositories {
+        self.store.ports()
+    }
+
+    /// Create an active agent
+    pub async fn agent(&self, first_name: &str, last_name: &str) -> User {
+        let user = fixtures::agent(self.org_id, first_name, last_name);
+        self.repositories().users.create(&user).await.unwrap()
+    }
+
+    /// System role
+    pub fn role(&self, role: UserRole) -> &Role {
+        let name = role.to_string();
+        self.roles.iter().find(|r| r.name == name).unwrap()
+    }
+
+    /// Shift type of a default code
+    pub fn shift_type(&self, code: &str) -> &ShiftType {
+        self.shift_types
+            .iter()
+            .find(|st| st.code == code)
+            .unwrap_or_else(|| panic!("unknown code {}", code))
+    }
+
+    /// Period of 2026 by number (1-13)
+    pub fn period(&self, number: i32) -> &Period {
+        &self.periods[number as usize - 1]
+    }
+
+    /// Register a public holiday
+    pub fn holiday(&self, date: NaiveDate, name: &str) {
+        self.store.holidays.insert(
+            self.org_id,
+            Holiday {
+                date,
+                name: name.to_string(),
+                is_moveable: false,
+            },
+        );
+    }
+
+    /// Write consecutive days of an agent from `start` ("" leaves a day empty)
+    pub async fn plan(
+        &self,
+        user_id: Uuid,
+        start: NaiveDate,
+        codes: &[&str],
+    ) -> CreateScheduleResult {
+        let entries = codes
+            .iter()
+            .enumerate()
+            .map(|(i, code)| CreateSchedule {
+                user_id,
+                shift_type_id: (!code.is_empty()).then(|| self.shift_type(code).id),
+                date: start + Duration::days(i as i64),
+                notes: None,
+            })
+            .collect();
+
+        CreateScheduleHandler::new(self.repositories())
+            .execute(&CreateScheduleCommand::bulk(self.org_id, None, entries))
+            .await
+            .unwrap()
+    }
+
+    /// Balance of an agent for a period, from the stored schedules and shift types
+    pub async fn balance(&self, period: &Period, user_id: Uuid) -> PeriodBalance {
+        let ports = self.repositories();
+        let schedules = ports
+            .schedules
+            .find_by_user_range(self.org_id, user_id, period.start_date, period.end_date)
+            .await
+            .unwrap();
+        let holidays: Vec<_> = ports
+            .holidays
+            .find_by_range(self.org_id, period.start_date, period.end_date)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|h| h.date)
+            .collect();
+
+        let shift_types = ports.shift_types.find_all(self.org_id).await.unwrap();
+        let history =
+            ShiftTypeHistory::new(ports.shift_types.find_versions(self.org_id).await.unwrap());
+
+        BalanceCalculator::new().with_history(history).calculate(
+            period,
+            user_id,
+            &schedules,
+            &shift_types,
+            &holidays,
+        )
+    }
+}
+
+/// A valid 28-day period: 19 worked days, 4 RH, 4 CH and 1 CV
+pub const VALID_PERIOD: [&str; 28] = [
+    "101", "101", "102", "102", "RH", "CH", "111", //
+    "112", "121", "121", "RH", "CH", "101", "101", //
+    "102", "102", "111", "RH", "CH", "112", "121", //
+    "101", "102", "101", "RH", "CH", "CV", "102",
+];
+
+/// Standalone entities for domain service tests, outside any organization
+pub mod fixtures {
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::{Period, Schedule, ShiftType, User};
+    use crate::domain::services::PeriodCalculator;
+
+    /// Active agent of an organization, with 20 CN and 10 JC
+    pub fn agent(org_id: Uuid, first_name: &str, last_name: &str) -> User {
+        User {
+            id: Uuid::new_v4(),
+            organization_id: org_id,
+            role_id: None,
+            email: format!("{}.{}@planningos.test", first_name, last_name).to_lowercase(),
+            password_hash: String::new(),
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            matricule: None,
+            avatar_url: None,
+            phone: None,
+            cn_entitlement: 20,
+            jc_entitlement: 10,
+            cn_carryover: 0,
+            jc_carryover: 0,
+            is_active: true,
+            email_verified_at: None,
+            last_login_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Shift type of the nil organization
+    ///
+    /// Countable when it has hours, a holiday indicator when its code starts
+    /// with 7, a rest day when it is in the rest category.
+    pub fn shift_type(code: &str, category: ShiftCategory, hours: f64, night: f64) -> ShiftType {
+        ShiftType {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            code: code.to_string(),
+            description: None,
+            category,
+            color_hex: "FFFFFF".to_string(),
+            icon: None,
+            duration_hours: hours,
+            night_hours: night,
+            start_time: None,
+            end_time: None,
+            is_countable: hours > 0.0,
+            requires_recovery: false,
+            is_holiday_indicator: code.starts_with('7'),
+            is_rest_day: matches!(category, ShiftCategory::Rest),
+            display_order: 0,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Open period 1 of 2026 (2026-01-12 to 2026-02-08, 160h) of the nil organization
+    pub fn period() -> Period {
+        periods(2026).swap_remove(0)
+    }
+
+    /// Open periods of a year of the nil organization, anchored on 2026-01-12
+    pub fn periods(year: i32) -> Vec<Period> {
+        PeriodCalculator::new()
+            .calculate_periods(year)
+            .into_iter()
+            .map(|p| Period {
+                id: Uuid::new_v4(),
+                organization_id: Uuid::nil(),
+                year,
+                number: p.number as i32,
+                start_date: p.start_date,
+                end_date: p.end_date,
+                hour_quota: p.hour_quota,
+                closed_at: None,
+                created_at: Utc::now(),
+            })
+            .collect()
+    }
+
+    /// Schedule of an agent of the nil organization
+    pub fn schedule(user_id: Uuid, date: NaiveDate, shift_type: &ShiftType) -> Schedule {
+        Schedule {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            user_id,
+            shift_type_id: Some(shift_type.id),
+            period_id: None,
+            date,
+            is_holiday: false,
+            notes: None,
+            created_by: None,
+            updated_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Audit Log Entity
+//!
+//! Trail of administrative changes, with the state before and after.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Kind of audited change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "audit_action", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Login,
+    Logout,
+}
+
+/// Audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditLog {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    /// Author of the change (None for system changes)
+    pub user_id: Option<Uuid>,
+
+    pub action: AuditAction,
+    /// Kind of entity changed, e.g. "shift_type"
+    pub entity_type: String,
+    pub entity_id: Option<Uuid>,
+
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    /// Entry for a change of an entity of an organization
+    pub fn new(
+        organization_id: Uuid,
+        user_id: Option<Uuid>,
+        action: AuditAction,
+        entity_type: &str,
+        entity_id: Uuid,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            organization_id: Some(organization_id),
+            user_id,
+            action,
+            entity_type: entity_type.to_string(),
+            entity_id: Some(entity_id),
+            old_value: None,
+            new_value: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the state before and after the change
+    pub fn with_values(
+        self,
+        old_value: Option<serde_json::Value>,
+        new_value: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            old_value,
+            new_value,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Calendar Token Entity
+//!
+//! Secret URL token giving read access to an ICS feed.
+//! The secret itself is only shown once, at creation.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Calendar feed subscription token
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CalendarToken {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// Agent of the feed (None for the team feed)
+    pub user_id: Option<Uuid>,
+    pub label: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl CalendarToken {
+    /// Check if this token gives access to the team feed
+    pub fn is_team_feed(&self) -> bool {
+        self.user_id.is_none()
+    }
+
+    /// Check if this token was revoked
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
@@ -0,0 +1,166 @@
+//! Edit Lock Entity
+//!
+//! Presence of planners on a period and soft, expiring edit locks.
+//! A lock covers a whole period or some agent rows of it; writes by
+//! other planners on covered cells are warned about or blocked depending
+//! on the organization policy.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What happens when a planner writes cells locked by someone else
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditLockMode {
+    /// The write goes through and reports the locks
+    #[default]
+    Warn,
+    /// The write is rejected
+    Block,
+}
+
+/// Edit lock policy (organizations.config -> editLocks)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EditLockPolicy {
+    pub mode: EditLockMode,
+    /// Default lock duration
+    pub ttl_minutes: i64,
+    /// Longest lock a planner may request
+    pub max_ttl_minutes: i64,
+}
+
+impl Default for EditLockPolicy {
+    fn default() -> Self {
+        Self {
+            mode: EditLockMode::Warn,
+            ttl_minutes: 15,
+            max_ttl_minutes: 120,
+        }
+    }
+}
+
+impl EditLockPolicy {
+    /// Lock duration for a requested TTL (default and maximum applied)
+    pub fn ttl(&self, requested_minutes: Option<i64>) -> Duration {
+        let minutes = requested_minutes
+            .unwrap_or(self.ttl_minutes)
+            .clamp(1, self.max_ttl_minutes.max(1));
+        Duration::minutes(minutes)
+    }
+}
+
+/// Planner currently editing a period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Presence {
+    pub editor_id: Uuid,
+    pub editor_name: String,
+    pub period_id: Uuid,
+    /// Agent rows being edited (empty for the whole period)
+    pub user_ids: Vec<Uuid>,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl Presence {
+    /// Presence is dropped without a heartbeat for this long
+    pub const TIMEOUT_SECONDS: i64 = 60;
+
+    /// Check if the last heartbeat is too old
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now - self.last_seen > Duration::seconds(Self::TIMEOUT_SECONDS)
+    }
+}
+
+/// Soft edit lock on a period or some of its agent rows
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EditLock {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub period_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Locked agent rows (empty for the whole period)
+    pub user_ids: Vec<Uuid>,
+    pub holder_id: Uuid,
+    pub holder_name: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl EditLock {
+    /// Check if the lock has expired
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Check if the lock covers a cell
+    pub fn covers(&self, user_id: Uuid, date: NaiveDate) -> bool {
+        date >= self.start_date
+            && date <= self.end_date
+            && (self.user_ids.is_empty() || self.user_ids.contains(&user_id))
+    }
+
+    /// Check if two locks of the same period share cells
+    pub fn overlaps(&self, period_id: Uuid, user_ids: &[Uuid]) -> bool {
+        self.period_id == period_id
+            && (self.user_ids.is_empty()
+                || user_ids.is_empty()
+                || self.user_ids.iter().any(|id| user_ids.contains(id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(user_ids: Vec<Uuid>) -> EditLock {
+        EditLock {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            period_id: Uuid::nil(),
+            start_date: NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 4, 5).unwrap(),
+            user_ids,
+            holder_id: Uuid::new_v4(),
+            holder_name: "Planner".to_string(),
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::minutes(15),
+        }
+    }
+
+    #[test]
+    fn test_covers_period_and_rows() {
+        let agent = Uuid::new_v4();
+        let inside = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        let outside = NaiveDate::from_ymd_opt(2026, 4, 6).unwrap();
+
+        let period = lock(Vec::new());
+        assert!(period.covers(agent, inside));
+        assert!(!period.covers(agent, outside));
+
+        let rows = lock(vec![agent]);
+        assert!(rows.covers(agent, inside));
+        assert!(!rows.covers(Uuid::new_v4(), inside));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(lock(Vec::new()).overlaps(Uuid::nil(), &[a]));
+        assert!(lock(vec![a]).overlaps(Uuid::nil(), &[]));
+        assert!(lock(vec![a, b]).overlaps(Uuid::nil(), &[b]));
+        assert!(!lock(vec![a]).overlaps(Uuid::nil(), &[b]));
+        assert!(!lock(Vec::new()).overlaps(Uuid::new_v4(), &[]));
+    }
+
+    #[test]
+    fn test_policy_ttl() {
+        let policy = EditLockPolicy::default();
+
+        assert_eq!(policy.ttl(None), Duration::minutes(15));
+        assert_eq!(policy.ttl(Some(600)), Duration::minutes(120));
+        assert_eq!(policy.ttl(Some(0)), Duration::minutes(1));
+    }
+}
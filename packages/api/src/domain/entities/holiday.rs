@@ -0,0 +1,29 @@
+//! Holiday Entity
+//!
+//! Public holiday of an organization. Generated holidays come from
+//! `HolidayCalculator`; custom ones are entered by hand.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Stored public holiday
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PublicHoliday {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+
+    pub date: NaiveDate,
+    pub name: String,
+    /// Easter-derived holiday
+    pub is_moveable: bool,
+    /// Entered by hand rather than generated
+    pub is_custom: bool,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl PublicHoliday {
+    /// Maximum name length (holidays.name)
+    pub const MAX_NAME_LENGTH: usize = 100;
+}
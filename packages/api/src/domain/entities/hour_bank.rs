@@ -0,0 +1,64 @@
+//! Hour Bank Entity
+//!
+//! Ledger of hours above or below the period quota.
+//! One entry per agent is recorded when a period is closed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What happens to credit above the maximum balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HourBankExcess {
+    /// Hours above the cap are paid out
+    #[default]
+    Payout,
+    /// Hours above the cap are lost
+    Forfeit,
+}
+
+/// Hour bank policy (organizations.config -> hourBank)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HourBankPolicy {
+    /// Maximum credit carried over to the next period
+    pub max_balance: f64,
+    /// Maximum debt carried over (negative); lower debt is written off
+    pub min_balance: f64,
+    /// Rule for credit above `max_balance`
+    pub excess: HourBankExcess,
+}
+
+impl Default for HourBankPolicy {
+    fn default() -> Self {
+        Self {
+            max_balance: 16.0,
+            min_balance: -16.0,
+            excess: HourBankExcess::Payout,
+        }
+    }
+}
+
+/// Hour bank ledger entry
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HourBankEntry {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub period_id: Uuid,
+
+    // Period figures at closing
+    pub total_hours: f64,
+    pub hour_quota: i32,
+    /// Hours above (+) or below (-) the quota
+    pub delta: f64,
+
+    // Policy outcome
+    pub paid_out: f64,
+    pub forfeited: f64,
+    /// Running balance carried over to the next period
+    pub balance: f64,
+
+    pub created_at: DateTime<Utc>,
+}
@@ -2,12 +2,27 @@
 //!
 //! Core business objects with identity and lifecycle.
 
+pub mod audit_log;
+pub mod calendar_token;
+pub mod edit_lock;
+pub mod holiday;
+pub mod hour_bank;
+pub mod payroll;
 pub mod period;
+pub mod role;
 pub mod schedule;
+pub mod shift_code_grammar;
 pub mod shift_type;
 pub mod user;
 
+pub use audit_log::AuditLog;
+pub use calendar_token::CalendarToken;
+pub use holiday::PublicHoliday;
+pub use hour_bank::HourBankEntry;
+pub use payroll::PayrollBatch;
 pub use period::Period;
+pub use role::Role;
 pub use schedule::Schedule;
-pub use shift_type::ShiftType;
+pub use shift_code_grammar::ShiftCodeGrammar;
+pub use shift_type::{ShiftType, ShiftTypeVersion};
 pub use user::User;
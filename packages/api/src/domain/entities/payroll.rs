@@ -0,0 +1,163 @@
+//! Payroll Entity
+//!
+//! Wage code mapping and export batches of closed periods.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payroll figure exported for each agent and period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayrollItem {
+    /// Hours of countable shifts
+    WorkedHours,
+    NightHours,
+    /// Days worked on a holiday code (e.g. 7xxx)
+    HolidayWorkDays,
+    /// Days worked on a Sunday
+    SundayWorkDays,
+    /// AG strike days
+    StrikeDays,
+    /// Leave days taken (CN, JC, CV)
+    LeaveDays,
+}
+
+impl PayrollItem {
+    /// Every item, in export order
+    pub const ALL: [PayrollItem; 6] = [
+        PayrollItem::WorkedHours,
+        PayrollItem::NightHours,
+        PayrollItem::HolidayWorkDays,
+        PayrollItem::SundayWorkDays,
+        PayrollItem::StrikeDays,
+        PayrollItem::LeaveDays,
+    ];
+
+    /// Label written in the CSV export
+    pub fn label(&self) -> &'static str {
+        match self {
+            PayrollItem::WorkedHours => "Heures travaillées",
+            PayrollItem::NightHours => "Heures de nuit",
+            PayrollItem::HolidayWorkDays => "Jours fériés travaillés",
+            PayrollItem::SundayWorkDays => "Dimanches travaillés",
+            PayrollItem::StrikeDays => "Jours de grève",
+            PayrollItem::LeaveDays => "Jours de congé",
+        }
+    }
+}
+
+/// Wage codes of the payroll items (organizations.config -> payroll)
+///
+/// An empty wage code leaves the item out of the export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PayrollMapping {
+    pub worked_hours: String,
+    pub night_hours: String,
+    pub holiday_work_days: String,
+    pub sunday_work_days: String,
+    pub strike_days: String,
+    pub leave_days: String,
+    /// Extra wage codes counting the days of a shift code (e.g. "CV" -> "3200")
+    pub codes: BTreeMap<String, String>,
+}
+
+impl Default for PayrollMapping {
+    fn default() -> Self {
+        Self {
+            worked_hours: "HTRAV".to_string(),
+            night_hours: "HNUIT".to_string(),
+            holiday_work_days: "JFER".to_string(),
+            sunday_work_days: "JDIM".to_string(),
+            strike_days: "GREVE".to_string(),
+            leave_days: "CONGE".to_string(),
+            codes: BTreeMap::new(),
+        }
+    }
+}
+
+impl PayrollMapping {
+    /// Wage code of an item
+    pub fn wage_code(&self, item: PayrollItem) -> &str {
+        match item {
+            PayrollItem::WorkedHours => &self.worked_hours,
+            PayrollItem::NightHours => &self.night_hours,
+            PayrollItem::HolidayWorkDays => &self.holiday_work_days,
+            PayrollItem::SundayWorkDays => &self.sunday_work_days,
+            PayrollItem::StrikeDays => &self.strike_days,
+            PayrollItem::LeaveDays => &self.leave_days,
+        }
+    }
+}
+
+/// Payroll totals of an agent for a period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PayrollTotals {
+    pub period_id: Uuid,
+    pub user_id: Uuid,
+    pub worked_hours: f64,
+    pub night_hours: f64,
+    pub holiday_work_days: i32,
+    pub sunday_work_days: i32,
+    pub strike_days: i32,
+    pub leave_days: i32,
+    /// Days per shift code
+    pub codes: BTreeMap<String, i32>,
+}
+
+impl PayrollTotals {
+    /// Quantity of an item
+    pub fn quantity(&self, item: PayrollItem) -> f64 {
+        match item {
+            PayrollItem::WorkedHours => self.worked_hours,
+            PayrollItem::NightHours => self.night_hours,
+            PayrollItem::HolidayWorkDays => self.holiday_work_days as f64,
+            PayrollItem::SundayWorkDays => self.sunday_work_days as f64,
+            PayrollItem::StrikeDays => self.strike_days as f64,
+            PayrollItem::LeaveDays => self.leave_days as f64,
+        }
+    }
+}
+
+/// Line of a payroll export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PayrollLine {
+    pub user_id: Uuid,
+    pub matricule: Option<String>,
+    pub agent_name: String,
+    pub year: i32,
+    pub period_number: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub wage_code: String,
+    pub label: String,
+    pub quantity: f64,
+}
+
+/// Payroll file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum PayrollFormat {
+    /// Semicolon separated values with a header row
+    #[default]
+    Csv,
+    /// Fixed-width records
+    Fixed,
+}
+
+/// Payroll export batch
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PayrollBatch {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub format: PayrollFormat,
+    pub line_count: i32,
+    /// Periods sent in the batch
+    pub period_ids: Vec<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
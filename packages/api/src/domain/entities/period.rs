@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Period entity (P1-P13)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Period {
     pub id: Uuid,
     pub organization_id: Uuid,
@@ -20,6 +20,8 @@ pub struct Period {
     pub end_date: NaiveDate,
     /// Maximum hours for this period (default: 160)
     pub hour_quota: i32,
+    /// Set when the period is closed (hour bank recorded)
+    pub closed_at: Option<DateTime<Utc>>,
 
     pub created_at: DateTime<Utc>,
 }
@@ -30,6 +32,11 @@ impl Period {
         format!("P{}", self.number)
     }
 
+    /// Check if the period has been closed
+    pub fn is_closed(&self) -> bool {
+        self.closed_at.is_some()
+    }
+
     /// Get duration in days (always 28)
     pub fn duration_days(&self) -> i64 {
         (self.end_date - self.start_date).num_days() + 1
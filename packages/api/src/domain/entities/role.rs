@@ -0,0 +1,151 @@
+//! Role Entity
+//!
+//! Named set of permissions of an organization. System roles (admin,
+//! planner, agent) are seeded and read-only; organizations add custom
+//! roles next to them.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::value_objects::permission::PermissionError;
+use crate::domain::value_objects::Permissions;
+
+/// Role entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// Identifier, unique in the organization (`night_planner`)
+    pub name: String,
+    pub display_name: String,
+    /// Granted permissions (`schedules:read`, `balances:read:own`)
+    pub permissions: Vec<String>,
+    pub is_system: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Role {
+    /// Maximum name length (roles.name)
+    pub const MAX_NAME_LENGTH: usize = 50;
+    /// Maximum display name length (roles.display_name)
+    pub const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+    /// Granted permissions, names outside the catalogue ignored
+    pub fn granted(&self) -> Permissions {
+        Permissions::from_stored(&self.permissions)
+    }
+}
+
+/// Role for creation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRole {
+    pub name: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+/// Role update payload
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRole {
+    pub display_name: Option<String>,
+    pub permissions: Option<Vec<String>>,
+}
+
+/// Role validation error
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoleError {
+    /// Malformed name
+    Name(String),
+    /// Empty or too long display name
+    DisplayName,
+    /// Permission outside the catalogue
+    Permission(PermissionError),
+    /// System roles are read-only
+    System(String),
+}
+
+impl fmt::Display for RoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoleError::Name(name) => write!(
+                f,
+                "Invalid role name: {} (1 to {} lowercase letters, digits or underscores)",
+                name,
+                Role::MAX_NAME_LENGTH
+            ),
+            RoleError::DisplayName => write!(
+                f,
+                "Display name must have 1 to {} characters",
+                Role::MAX_DISPLAY_NAME_LENGTH
+            ),
+            RoleError::Permission(e) => write!(f, "{}", e),
+            RoleError::System(name) => write!(f, "System role {} cannot be changed", name),
+        }
+    }
+}
+
+impl std::error::Error for RoleError {}
+
+fn display_name(display_name: &str) -> Result<String, RoleError> {
+    let display_name = display_name.trim();
+    if display_name.is_empty() || display_name.chars().count() > Role::MAX_DISPLAY_NAME_LENGTH {
+        return Err(RoleError::DisplayName);
+    }
+    Ok(display_name.to_string())
+}
+
+impl CreateRole {
+    /// Build a validated custom role (name lowercased, permissions
+    /// normalized)
+    pub fn into_role(self, organization_id: Uuid) -> Result<Role, RoleError> {
+        let name = self.name.trim().to_lowercase();
+        let well_formed = !name.is_empty()
+            && name.len() <= Role::MAX_NAME_LENGTH
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !well_formed {
+            return Err(RoleError::Name(name));
+        }
+
+        Ok(Role {
+            id: Uuid::new_v4(),
+            organization_id,
+            name,
+            display_name: display_name(&self.display_name)?,
+            permissions: Permissions::normalize(&self.permissions)
+                .map_err(RoleError::Permission)?,
+            is_system: false,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+impl UpdateRole {
+    /// Apply the changes to a custom role
+    pub fn apply(self, role: &Role) -> Result<Role, RoleError> {
+        if role.is_system {
+            return Err(RoleError::System(role.name.clone()));
+        }
+
+        Ok(Role {
+            display_name: match self.display_name {
+                Some(name) => display_name(&name)?,
+                None => role.display_name.clone(),
+            },
+            permissions: match self.permissions {
+                Some(permissions) => {
+                    Permissions::normalize(&permissions).map_err(RoleError::Permission)?
+                }
+                None => role.permissions.clone(),
+            },
+            ..role.clone()
+        })
+    }
+}
@@ -0,0 +1,306 @@
+//! Shift Code Grammar Entity
+//!
+//! Code families of an organization (organizations.config -> shiftCodes):
+//! - A family is a weekday base code with its night credit (e.g. 101)
+//! - Prefixes derive the variants of every family (6101 on Sundays,
+//!   7101 on holidays), with the night credit of the base code
+//! - Standalone codes (X_AM, RH, CN...) have no variants
+//!
+//! The default grammar is the one of generate.py (LISTE_PRESTATIONS and
+//! LISTE_REPOS).
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::shift_code::ShiftCodeError;
+use crate::domain::value_objects::ShiftCode;
+
+/// Day a prefixed variant is worked on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeVariant {
+    Sunday,
+    Holiday,
+}
+
+/// Prefix deriving a variant of the family codes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodePrefix {
+    pub prefix: String,
+    pub variant: CodeVariant,
+}
+
+/// Code with its night credit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeDefinition {
+    pub code: String,
+    #[serde(default)]
+    pub night_hours: f64,
+}
+
+/// Code grammar of an organization (organizations.config -> shiftCodes)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShiftCodeGrammar {
+    /// Weekday base codes, each with its prefixed variants
+    pub families: Vec<CodeDefinition>,
+    pub prefixes: Vec<CodePrefix>,
+    /// Codes without variants
+    pub standalone: Vec<CodeDefinition>,
+    /// Accept well-formed codes outside the grammar (night credit 0h)
+    pub allow_custom: bool,
+}
+
+/// Known code broken down by the grammar
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedCode {
+    pub code: ShiftCode,
+    /// Base code of the family (None for standalone codes)
+    pub family: Option<String>,
+    /// Prefixed variant (None for base and standalone codes)
+    pub variant: Option<CodeVariant>,
+    pub night_hours: f64,
+}
+
+fn definition(code: &str, night_hours: f64) -> CodeDefinition {
+    CodeDefinition {
+        code: code.to_string(),
+        night_hours,
+    }
+}
+
+impl Default for ShiftCodeGrammar {
+    fn default() -> Self {
+        Self {
+            families: vec![
+                definition("101", 2.0),
+                definition("102", 2.0),
+                definition("111", 2.0),
+                definition("112", 2.0),
+                definition("121", 8.0),
+            ],
+            prefixes: vec![
+                CodePrefix {
+                    prefix: "6".to_string(),
+                    variant: CodeVariant::Sunday,
+                },
+                CodePrefix {
+                    prefix: "7".to_string(),
+                    variant: CodeVariant::Holiday,
+                },
+            ],
+            standalone: vec![
+                definition("X_AM", 2.0),
+                definition("X_PM", 2.0),
+                definition("X_10", 0.0),
+                definition("AG", 0.0),
+                definition("CN", 0.0),
+                definition("JC", 0.0),
+                definition("RH", 0.0),
+                definition("CH", 0.0),
+                definition("RR", 0.0),
+                definition("CV", 0.0),
+                definition("ZM", 0.0),
+            ],
+            allow_custom: true,
+        }
+    }
+}
+
+impl ShiftCodeGrammar {
+    /// Longest night credit of a code, in hours
+    pub const MAX_NIGHT_HOURS: f64 = 24.0;
+
+    /// Break a code down (None for malformed codes and codes outside the grammar)
+    pub fn classify(&self, code: &str) -> Option<ParsedCode> {
+        let code = ShiftCode::parse(code).ok()?;
+        let find = |codes: &[CodeDefinition], code: &str| {
+            codes.iter().find(|d| d.code == code).map(|d| d.night_hours)
+        };
+        let parsed = |family: Option<&str>, variant, night_hours| ParsedCode {
+            code: code.clone(),
+            family: family.map(str::to_string),
+            variant,
+            night_hours,
+        };
+
+        if let Some(night_hours) = find(&self.standalone, code.as_str()) {
+            return Some(parsed(None, None, night_hours));
+        }
+        if let Some(night_hours) = find(&self.families, code.as_str()) {
+            return Some(parsed(Some(code.as_str()), None, night_hours));
+        }
+        self.prefixes.iter().find_map(|p| {
+            let base = code.as_str().strip_prefix(p.prefix.as_str())?;
+            let night_hours = find(&self.families, base)?;
+            Some(parsed(Some(base), Some(p.variant), night_hours))
+        })
+    }
+
+    /// Validate a code for a new shift type
+    ///
+    /// Codes outside the grammar are accepted only with `allow_custom`.
+    pub fn parse(&self, code: &str) -> Result<ShiftCode, ShiftCodeError> {
+        let code = ShiftCode::parse(code)?;
+        if self.allow_custom || self.is_known(code.as_str()) {
+            Ok(code)
+        } else {
+            Err(ShiftCodeError::Unknown(code.into_inner()))
+        }
+    }
+
+    /// Check if a code belongs to the grammar
+    pub fn is_known(&self, code: &str) -> bool {
+        self.classify(code).is_some()
+    }
+
+    /// Night credit of a code (0h outside the grammar)
+    pub fn night_hours(&self, code: &str) -> f64 {
+        self.classify(code).map_or(0.0, |c| c.night_hours)
+    }
+
+    /// Check if a code is the holiday variant of a family
+    pub fn is_holiday_variant(&self, code: &str) -> bool {
+        self.classify(code)
+            .is_some_and(|c| c.variant == Some(CodeVariant::Holiday))
+    }
+
+    /// Check if the grammar has a prefix for a variant
+    pub fn has_variant(&self, variant: CodeVariant) -> bool {
+        self.prefixes.iter().any(|p| p.variant == variant)
+    }
+
+    /// Code of a family for a variant (the base code for None)
+    pub fn variant_code(&self, family: &str, variant: Option<CodeVariant>) -> Option<String> {
+        if !self.families.iter().any(|d| d.code == family) {
+            return None;
+        }
+        match variant {
+            None => Some(family.to_string()),
+            Some(variant) => self
+                .prefixes
+                .iter()
+                .find(|p| p.variant == variant)
+                .map(|p| format!("{}{}", p.prefix, family)),
+        }
+    }
+
+    /// Check that codes are well-formed and that no code is defined twice
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        let mut define = |code: String| -> Result<(), String> {
+            match ShiftCode::parse(code.as_str()) {
+                Ok(parsed) if parsed.as_str() == code => {}
+                _ => {
+                    return Err(format!(
+                        "Invalid code '{}' (1-{} uppercase letters, digits or _)",
+                        code,
+                        ShiftCode::MAX_LENGTH
+                    ))
+                }
+            }
+            if !seen.insert(code.clone()) {
+                return Err(format!("Code {} is defined twice", code));
+            }
+            Ok(())
+        };
+
+        for d in self.families.iter().chain(&self.standalone) {
+            if !(0.0..=Self::MAX_NIGHT_HOURS).contains(&d.night_hours) {
+                return Err(format!(
+                    "Night hours of {} must be between 0 and {}h",
+                    d.code,
+                    Self::MAX_NIGHT_HOURS
+                ));
+            }
+            define(d.code.clone())?;
+        }
+
+        let mut prefixes = HashSet::new();
+        for p in &self.prefixes {
+            if p.prefix.is_empty() || !prefixes.insert(p.prefix.as_str()) {
+                return Err(format!("Prefix '{}' is empty or repeated", p.prefix));
+            }
+            for family in &self.families {
+                define(format!("{}{}", p.prefix, family.code))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_grammar() {
+        let grammar = ShiftCodeGrammar::default();
+        assert!(grammar.validate().is_ok());
+
+        let night = grammar.classify("7121").unwrap();
+        assert_eq!(night.family.as_deref(), Some("121"));
+        assert_eq!(night.variant, Some(CodeVariant::Holiday));
+        assert_eq!(night.night_hours, 8.0);
+
+        assert_eq!(grammar.night_hours("6101"), 2.0);
+        assert_eq!(grammar.night_hours("x_am"), 2.0);
+        assert_eq!(grammar.night_hours("AG"), 0.0);
+        assert!(grammar.is_holiday_variant("7101"));
+        assert!(!grammar.is_holiday_variant("101"));
+        assert!(!grammar.is_holiday_variant("6101"));
+        assert!(grammar.is_known("CN"));
+        assert!(!grammar.is_known("INVALID"));
+        assert!(!grammar.is_known("8101"));
+
+        assert_eq!(
+            grammar.variant_code("112", Some(CodeVariant::Sunday)),
+            Some("6112".to_string())
+        );
+        assert_eq!(grammar.variant_code("112", None), Some("112".to_string()));
+        assert_eq!(grammar.variant_code("X_AM", None), None);
+    }
+
+    #[test]
+    fn test_custom_families() {
+        let grammar = ShiftCodeGrammar {
+            families: vec![definition("N1", 9.0), definition("J1", 0.0)],
+            prefixes: vec![CodePrefix {
+                prefix: "F".to_string(),
+                variant: CodeVariant::Holiday,
+            }],
+            standalone: vec![definition("RH", 0.0)],
+            allow_custom: false,
+        };
+        assert!(grammar.validate().is_ok());
+
+        assert!(grammar.is_holiday_variant("FN1"));
+        assert_eq!(grammar.night_hours("FN1"), 9.0);
+        // The 7 prefix means nothing here
+        assert!(!grammar.is_known("7101"));
+        assert_eq!(grammar.parse("j1").unwrap().as_str(), "J1");
+        assert_eq!(
+            grammar.parse("X_10"),
+            Err(ShiftCodeError::Unknown("X_10".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rejects_ambiguous_grammars() {
+        let mut grammar = ShiftCodeGrammar::default();
+        grammar.standalone.push(definition("7101", 0.0));
+        assert!(grammar.validate().unwrap_err().contains("7101"));
+
+        let mut grammar = ShiftCodeGrammar::default();
+        grammar.prefixes[1].prefix = "6".to_string();
+        assert!(grammar.validate().is_err());
+
+        let mut grammar = ShiftCodeGrammar::default();
+        grammar.families[0].code = "1-01".to_string();
+        assert!(grammar.validate().is_err());
+    }
+}
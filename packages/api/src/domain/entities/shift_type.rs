@@ -3,10 +3,17 @@
 //! Represents a configurable prestation or repos type.
 //! Derived from generate.py: LISTE_PRESTATIONS + LISTE_REPOS
 
-use chrono::{DateTime, Utc};
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::entities::ShiftCodeGrammar;
+use crate::domain::value_objects::color::ColorError;
+use crate::domain::value_objects::shift_code::ShiftCodeError;
+use crate::domain::value_objects::{Color, ShiftCode};
+
 /// Shift category (from generate.py logic)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "shift_category", rename_all = "lowercase")]
@@ -35,7 +42,7 @@ impl ShiftCategory {
 }
 
 /// Shift type entity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ShiftType {
     pub id: Uuid,
     pub organization_id: Uuid,
@@ -53,6 +60,10 @@ pub struct ShiftType {
     pub duration_hours: f64,
     pub night_hours: f64,
 
+    // Timing (local times, None for all-day codes)
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+
     // Behavior flags
     pub is_countable: bool,
     pub requires_recovery: bool,
@@ -79,51 +90,433 @@ impl ShiftType {
         matches!(self.category, ShiftCategory::Night)
     }
 
-    /// Check if this is a 7xxx holiday code
-    pub fn is_holiday_work(&self) -> bool {
-        self.is_holiday_indicator || self.code.starts_with('7')
+    /// Check if this is a worked shift (not a rest day or leave)
+    pub fn is_worked(&self) -> bool {
+        !matches!(self.category, ShiftCategory::Rest | ShiftCategory::Leave)
     }
 
-    /// Get night hours based on category (from generate.py)
-    /// - Night codes (121, 6121, 7121): 8h
-    /// - Standard/Intermediate: 2h
-    /// - Others: 0h
-    pub fn calculated_night_hours(&self) -> f64 {
-        match self.category {
-            ShiftCategory::Night => 8.0,
-            ShiftCategory::Standard | ShiftCategory::Intermediate | ShiftCategory::Partial => 2.0,
-            _ => 0.0,
-        }
+    /// Check if this code marks holiday work (flagged, or holiday variant
+    /// of a family in the organization's grammar)
+    pub fn is_holiday_work(&self, grammar: &ShiftCodeGrammar) -> bool {
+        self.is_holiday_indicator || grammar.is_holiday_variant(&self.code)
     }
 }
 
 /// ShiftType for creation
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateShiftType {
-    pub organization_id: Uuid,
     pub code: String,
     pub description: Option<String>,
     pub category: ShiftCategory,
     pub color_hex: String,
     pub icon: Option<String>,
     pub duration_hours: f64,
-    pub night_hours: f64,
+    /// Night credit of the code in the grammar when omitted
+    pub night_hours: Option<f64>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
     pub is_countable: Option<bool>,
     pub requires_recovery: Option<bool>,
     pub is_holiday_indicator: Option<bool>,
+    pub is_rest_day: Option<bool>,
     pub display_order: Option<i32>,
 }
 
 /// ShiftType update payload
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateShiftType {
     pub description: Option<String>,
+    pub category: Option<ShiftCategory>,
     pub color_hex: Option<String>,
     pub icon: Option<String>,
     pub duration_hours: Option<f64>,
     pub night_hours: Option<f64>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
     pub is_countable: Option<bool>,
     pub requires_recovery: Option<bool>,
+    pub is_holiday_indicator: Option<bool>,
+    pub is_rest_day: Option<bool>,
     pub display_order: Option<i32>,
     pub is_active: Option<bool>,
+    /// First day the new hour attributes apply (defaults to today)
+    pub effective_from: Option<NaiveDate>,
+}
+
+/// Hour attributes of a shift type in force from a date
+///
+/// Balances and statistics of a schedule use the version in force on its
+/// date, so that changing a code does not rewrite past periods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ShiftTypeVersion {
+    pub id: Uuid,
+    pub shift_type_id: Uuid,
+    pub organization_id: Uuid,
+    pub effective_from: NaiveDate,
+
+    pub category: ShiftCategory,
+    pub duration_hours: f64,
+    pub night_hours: f64,
+    pub is_countable: bool,
+    pub requires_recovery: bool,
+    pub is_holiday_indicator: bool,
+    pub is_rest_day: bool,
+
+    pub created_at: DateTime<Utc>,
+}
+
+/// Shift type validation error
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShiftTypeError {
+    /// Malformed code
+    Code(ShiftCodeError),
+    /// Malformed color
+    Color(ColorError),
+    /// Durations out of range
+    Hours(String),
+    /// Category and flags contradict each other
+    Flags(String),
+}
+
+impl fmt::Display for ShiftTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShiftTypeError::Code(e) => write!(f, "{}", e),
+            ShiftTypeError::Color(e) => write!(f, "{}", e),
+            ShiftTypeError::Hours(message) | ShiftTypeError::Flags(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShiftTypeError {}
+
+impl ShiftType {
+    /// Longest shift accepted, in hours
+    pub const MAX_DURATION_HOURS: f64 = 24.0;
+
+    /// Check code, color, hours and the consistency of category and flags
+    pub fn validate(&self) -> Result<(), ShiftTypeError> {
+        ShiftCode::parse(self.code.as_str()).map_err(ShiftTypeError::Code)?;
+        Color::new(self.color_hex.as_str()).map_err(ShiftTypeError::Color)?;
+
+        if !(0.0..=Self::MAX_DURATION_HOURS).contains(&self.duration_hours) {
+            return Err(ShiftTypeError::Hours(format!(
+                "Duration must be between 0 and {}h",
+                Self::MAX_DURATION_HOURS
+            )));
+        }
+        if !(0.0..=self.duration_hours).contains(&self.night_hours) {
+            return Err(ShiftTypeError::Hours(
+                "Night hours must be between 0 and the duration".to_string(),
+            ));
+        }
+        if self.start_time.is_some() != self.end_time.is_some() {
+            return Err(ShiftTypeError::Hours(
+                "Start and end times go together".to_string(),
+            ));
+        }
+
+        if self.category == ShiftCategory::Rest {
+            if self.duration_hours != 0.0 {
+                return Err(ShiftTypeError::Flags("Rest codes last 0h".to_string()));
+            }
+            if !self.is_rest_day || self.is_countable {
+                return Err(ShiftTypeError::Flags(
+                    "Rest codes are rest days and not countable".to_string(),
+                ));
+            }
+        }
+        if self.is_rest_day && self.is_worked() {
+            return Err(ShiftTypeError::Flags(
+                "Only rest and leave codes can be rest days".to_string(),
+            ));
+        }
+        if (self.requires_recovery || self.is_holiday_indicator) && !self.is_worked() {
+            return Err(ShiftTypeError::Flags(
+                "Only worked codes can mark holiday work".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl CreateShiftType {
+    /// Build a validated shift type (code uppercased, color without #)
+    ///
+    /// Flags default from the category: rest codes are rest days, and
+    /// rest days are not countable. The night credit and holiday flags
+    /// default from the organization's code grammar.
+    pub fn into_shift_type(
+        self,
+        organization_id: Uuid,
+        grammar: &ShiftCodeGrammar,
+    ) -> Result<ShiftType, ShiftTypeError> {
+        let code = grammar
+            .parse(&self.code)
+            .map_err(ShiftTypeError::Code)?
+            .into_inner();
+        let holiday_work = grammar.is_holiday_variant(&code)
+            && !matches!(self.category, ShiftCategory::Rest | ShiftCategory::Leave);
+        let night_hours = self
+            .night_hours
+            .unwrap_or_else(|| grammar.night_hours(&code));
+        let color = Color::new(self.color_hex).map_err(ShiftTypeError::Color)?;
+        let is_rest_day = self
+            .is_rest_day
+            .unwrap_or(self.category == ShiftCategory::Rest);
+
+        let shift_type = ShiftType {
+            id: Uuid::new_v4(),
+            organization_id,
+            code,
+            description: self.description,
+            category: self.category,
+            color_hex: color.hex().to_string(),
+            icon: self.icon,
+            duration_hours: self.duration_hours,
+            night_hours,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            is_countable: self
+                .is_countable
+                .unwrap_or(self.category.is_countable() && !is_rest_day),
+            requires_recovery: self.requires_recovery.unwrap_or(holiday_work),
+            is_holiday_indicator: self.is_holiday_indicator.unwrap_or(holiday_work),
+            is_rest_day,
+            display_order: self.display_order.unwrap_or(0),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        shift_type.validate()?;
+
+        Ok(shift_type)
+    }
+}
+
+impl UpdateShiftType {
+    /// Apply the changes to a shift type and validate the result
+    pub fn apply(self, shift_type: &ShiftType) -> Result<ShiftType, ShiftTypeError> {
+        let color_hex = match self.color_hex {
+            Some(hex) => Color::new(hex)
+                .map_err(ShiftTypeError::Color)?
+                .hex()
+                .to_string(),
+            None => shift_type.color_hex.clone(),
+        };
+
+        let updated = ShiftType {
+            description: self.description.or_else(|| shift_type.description.clone()),
+            category: self.category.unwrap_or(shift_type.category),
+            color_hex,
+            icon: self.icon.or_else(|| shift_type.icon.clone()),
+            duration_hours: self.duration_hours.unwrap_or(shift_type.duration_hours),
+            night_hours: self.night_hours.unwrap_or(shift_type.night_hours),
+            start_time: self.start_time.or(shift_type.start_time),
+            end_time: self.end_time.or(shift_type.end_time),
+            is_countable: self.is_countable.unwrap_or(shift_type.is_countable),
+            requires_recovery: self
+                .requires_recovery
+                .unwrap_or(shift_type.requires_recovery),
+            is_holiday_indicator: self
+                .is_holiday_indicator
+                .unwrap_or(shift_type.is_holiday_indicator),
+            is_rest_day: self.is_rest_day.unwrap_or(shift_type.is_rest_day),
+            display_order: self.display_order.unwrap_or(shift_type.display_order),
+            is_active: self.is_active.unwrap_or(shift_type.is_active),
+            ..shift_type.clone()
+        };
+        updated.validate()?;
+
+        Ok(updated)
+    }
+}
+
+impl ShiftTypeVersion {
+    /// Version holding the current attributes of a shift type
+    pub fn of(shift_type: &ShiftType, effective_from: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            shift_type_id: shift_type.id,
+            organization_id: shift_type.organization_id,
+            effective_from,
+            category: shift_type.category,
+            duration_hours: shift_type.duration_hours,
+            night_hours: shift_type.night_hours,
+            is_countable: shift_type.is_countable,
+            requires_recovery: shift_type.requires_recovery,
+            is_holiday_indicator: shift_type.is_holiday_indicator,
+            is_rest_day: shift_type.is_rest_day,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Shift type with the attributes of this version
+    pub fn apply(&self, shift_type: &ShiftType) -> ShiftType {
+        ShiftType {
+            category: self.category,
+            duration_hours: self.duration_hours,
+            night_hours: self.night_hours,
+            is_countable: self.is_countable,
+            requires_recovery: self.requires_recovery,
+            is_holiday_indicator: self.is_holiday_indicator,
+            is_rest_day: self.is_rest_day,
+            ..shift_type.clone()
+        }
+    }
+
+    /// Check if the shift type has the attributes of this version
+    pub fn matches(&self, shift_type: &ShiftType) -> bool {
+        self.category == shift_type.category
+            && self.duration_hours == shift_type.duration_hours
+            && self.night_hours == shift_type.night_hours
+            && self.is_countable == shift_type.is_countable
+            && self.requires_recovery == shift_type.requires_recovery
+            && self.is_holiday_indicator == shift_type.is_holiday_indicator
+            && self.is_rest_day == shift_type.is_rest_day
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(code: &str, category: ShiftCategory, duration_hours: f64) -> CreateShiftType {
+        CreateShiftType {
+            code: code.to_string(),
+            description: None,
+            category,
+            color_hex: "#d9e6ff".to_string(),
+            icon: None,
+            duration_hours,
+            night_hours: Some(0.0),
+            start_time: None,
+            end_time: None,
+            is_countable: None,
+            requires_recovery: None,
+            is_holiday_indicator: None,
+            is_rest_day: None,
+            display_order: None,
+        }
+    }
+
+    #[test]
+    fn test_create_normalizes_and_defaults_flags() {
+        let shift_type = create("x_nuit", ShiftCategory::Night, 8.0)
+            .into_shift_type(Uuid::nil(), &ShiftCodeGrammar::default())
+            .unwrap();
+        assert_eq!(shift_type.code, "X_NUIT");
+        assert_eq!(shift_type.color_hex, "D9E6FF");
+        assert!(shift_type.is_countable);
+        assert!(!shift_type.is_rest_day);
+
+        let rest = create("RX", ShiftCategory::Rest, 0.0)
+            .into_shift_type(Uuid::nil(), &ShiftCodeGrammar::default())
+            .unwrap();
+        assert!(rest.is_rest_day);
+        assert!(!rest.is_countable);
+    }
+
+    #[test]
+    fn test_create_defaults_from_grammar() {
+        let grammar = ShiftCodeGrammar::default();
+
+        let holiday_night = CreateShiftType {
+            night_hours: None,
+            ..create("7121", ShiftCategory::Night, 8.0)
+        }
+        .into_shift_type(Uuid::nil(), &grammar)
+        .unwrap();
+        assert_eq!(holiday_night.night_hours, 8.0);
+        assert!(holiday_night.is_holiday_indicator);
+        assert!(holiday_night.requires_recovery);
+        assert!(holiday_night.is_holiday_work(&grammar));
+
+        let sunday = CreateShiftType {
+            night_hours: None,
+            ..create("6101", ShiftCategory::Standard, 8.0)
+        }
+        .into_shift_type(Uuid::nil(), &grammar)
+        .unwrap();
+        assert_eq!(sunday.night_hours, 2.0);
+        assert!(!sunday.is_holiday_work(&grammar));
+
+        let closed = ShiftCodeGrammar {
+            allow_custom: false,
+            ..grammar
+        };
+        assert!(matches!(
+            create("X_NUIT", ShiftCategory::Night, 8.0).into_shift_type(Uuid::nil(), &closed),
+            Err(ShiftTypeError::Code(ShiftCodeError::Unknown(_)))
+        ));
+    }
+
+    #[test]
+    fn test_create_rejects_invalid_values() {
+        let grammar = ShiftCodeGrammar::default();
+        let invalid =
+            |request: CreateShiftType| request.into_shift_type(Uuid::nil(), &grammar).unwrap_err();
+
+        assert!(matches!(
+            invalid(create("R H", ShiftCategory::Rest, 0.0)),
+            ShiftTypeError::Code(_)
+        ));
+        assert!(matches!(
+            invalid(CreateShiftType {
+                color_hex: "FFF".to_string(),
+                ..create("RX", ShiftCategory::Rest, 0.0)
+            }),
+            ShiftTypeError::Color(_)
+        ));
+        assert!(matches!(
+            invalid(CreateShiftType {
+                night_hours: Some(9.0),
+                ..create("X_9", ShiftCategory::Special, 8.0)
+            }),
+            ShiftTypeError::Hours(_)
+        ));
+        assert!(matches!(
+            invalid(create("RX", ShiftCategory::Rest, 8.0)),
+            ShiftTypeError::Flags(_)
+        ));
+        assert!(matches!(
+            invalid(CreateShiftType {
+                is_rest_day: Some(true),
+                ..create("101B", ShiftCategory::Standard, 8.0)
+            }),
+            ShiftTypeError::Flags(_)
+        ));
+    }
+
+    #[test]
+    fn test_update_revalidates() {
+        let shift_type = create("X_8", ShiftCategory::Special, 8.0)
+            .into_shift_type(Uuid::nil(), &ShiftCodeGrammar::default())
+            .unwrap();
+
+        let updated = UpdateShiftType {
+            color_hex: Some("ff4444".to_string()),
+            display_order: Some(3),
+            ..Default::default()
+        }
+        .apply(&shift_type)
+        .unwrap();
+        assert_eq!(updated.color_hex, "FF4444");
+        assert_eq!(updated.display_order, 3);
+        assert_eq!(updated.code, "X_8");
+
+        let to_rest = UpdateShiftType {
+            category: Some(ShiftCategory::Rest),
+            ..Default::default()
+        };
+        assert!(matches!(
+            to_rest.apply(&shift_type),
+            Err(ShiftTypeError::Flags(_))
+        ));
+    }
 }
@@ -15,6 +15,57 @@ pub enum UserRole {
     Agent,
 }
 
+impl UserRole {
+    /// Permissions of the system role (seeded in `roles.permissions`)
+    ///
+    /// Users without a role get the agent permissions.
+    pub fn default_permissions(&self) -> &'static [&'static str] {
+        match self {
+            UserRole::Admin => &[
+                "users:read",
+                "users:manage",
+                "roles:read",
+                "roles:manage",
+                "schedules:read",
+                "schedules:write",
+                "shift_types:read",
+                "shift_types:manage",
+                "periods:read",
+                "periods:write",
+                "periods:lock",
+                "balances:read",
+                "holidays:read",
+                "holidays:manage",
+                "statistics:read",
+                "calendar:manage",
+                "payroll:manage",
+            ],
+            UserRole::Planner => &[
+                "users:read",
+                "roles:read",
+                "schedules:read",
+                "schedules:write",
+                "shift_types:read",
+                "periods:read",
+                "periods:lock",
+                "balances:read",
+                "holidays:read",
+                "statistics:read",
+                "calendar:manage",
+            ],
+            UserRole::Agent => &[
+                "schedules:read:own",
+                "shift_types:read",
+                "periods:read",
+                "balances:read:own",
+                "holidays:read",
+                "statistics:read:own",
+                "calendar:manage:own",
+            ],
+        }
+    }
+}
+
 impl std::fmt::Display for UserRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -26,7 +77,7 @@ impl std::fmt::Display for UserRole {
 }
 
 /// User entity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct User {
     pub id: Uuid,
     pub organization_id: Uuid,
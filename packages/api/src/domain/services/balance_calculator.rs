@@ -0,0 +1,294 @@
+//! Balance Calculator Service
+//!
+//! Builds a period balance from schedule entries:
+//! - Total hours = sum of countable shift durations
+//! - Night hours = sum of shift night credits
+//! - Per-code counts (CH, RH, CV, RR, CN, JC)
+//! - Holidays worked = worked shifts on a public holiday
+//!
+//! Quota validation is delegated to the QuotaValidator so the rules
+//! are defined in a single place. Shift types are taken as defined on each
+//! schedule date when a history is given.
+
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::{Period, Schedule, ShiftType};
+use crate::domain::services::quota_validator::QuotaValidator;
+use crate::domain::services::shift_type_history::ShiftTypeHistory;
+
+/// Balance calculator service
+pub struct BalanceCalculator {
+    validator: QuotaValidator,
+    history: ShiftTypeHistory,
+}
+
+impl Default for BalanceCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BalanceCalculator {
+    /// Create with default quotas
+    pub fn new() -> Self {
+        Self {
+            validator: QuotaValidator::new(),
+            history: ShiftTypeHistory::default(),
+        }
+    }
+
+    /// Use the shift type definitions in force on each schedule date
+    pub fn with_history(self, history: ShiftTypeHistory) -> Self {
+        Self { history, ..self }
+    }
+
+    /// Calculate the balance of an agent for a period
+    ///
+    /// Only `schedules` of `user_id` dated within the period are counted.
+    /// A day is a holiday if it is listed in `holidays` or flagged on the
+    /// schedule entry. The hour limit is the period's own `hour_quota`.
+    pub fn calculate(
+        &self,
+        period: &Period,
+        user_id: Uuid,
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        holidays: &[NaiveDate],
+    ) -> PeriodBalance {
+        let mut balance = PeriodBalance {
+            id: Uuid::new_v4(),
+            period_id: period.id,
+            user_id,
+            total_hours: 0.0,
+            night_hours: 0.0,
+            ch_count: 0,
+            rh_count: 0,
+            cv_count: 0,
+            rr_count: 0,
+            cn_count: 0,
+            jc_count: 0,
+            holidays_worked: 0,
+            is_valid: false,
+            validation_errors: Vec::new(),
+            calculated_at: Utc::now(),
+        };
+
+        let entries = schedules
+            .iter()
+            .filter(|s| s.user_id == user_id && period.contains_date(s.date));
+
+        for schedule in entries {
+            let Some(shift_type) = schedule
+                .shift_type_id
+                .and_then(|id| shift_types.iter().find(|st| st.id == id))
+            else {
+                continue;
+            };
+            let shift_type = self.history.definition_on(shift_type, schedule.date);
+
+            if shift_type.is_countable {
+                balance.total_hours += shift_type.duration_hours;
+            }
+            balance.night_hours += shift_type.night_hours;
+
+            match shift_type.code.as_str() {
+                "CH" => balance.ch_count += 1,
+                "RH" => balance.rh_count += 1,
+                "CV" => balance.cv_count += 1,
+                "RR" => balance.rr_count += 1,
+                "CN" => balance.cn_count += 1,
+                "JC" => balance.jc_count += 1,
+                _ => {}
+            }
+
+            let is_holiday = schedule.is_holiday || holidays.contains(&schedule.date);
+            if is_holiday && shift_type.is_worked() {
+                balance.holidays_worked += 1;
+            }
+        }
+
+        let validator = QuotaValidator {
+            max_hours: period.hour_quota as f64,
+            ..self.validator
+        };
+        let result = validator.validate(&balance);
+
+        // A balance is valid only when every quota is met (errors and warnings)
+        balance.is_valid = !result.has_errors() && !result.has_warnings();
+        balance.validation_errors = result.error_messages();
+        balance.validation_errors.extend(result.warning_messages());
+
+        balance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, period, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::ShiftTypeVersion;
+    use chrono::Duration;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("121", ShiftCategory::Night, 8.0, 8.0),
+            shift_type("CH", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("RR", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("CV", ShiftCategory::Leave, 0.0, 0.0),
+            shift_type("CN", ShiftCategory::Leave, 8.0, 0.0),
+        ]
+    }
+
+    /// Build schedules from a pattern starting on the first day of the period:
+    /// W = 101, N = 121, C = CH, H = RH, R = RR, V = CV, L = CN, . = empty
+    fn schedules(pattern: &str, user_id: Uuid, types: &[ShiftType]) -> Vec<Schedule> {
+        let period = period();
+        pattern
+            .chars()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                let code = match c {
+                    'W' => "101",
+                    'N' => "121",
+                    'C' => "CH",
+                    'H' => "RH",
+                    'R' => "RR",
+                    'V' => "CV",
+                    'L' => "CN",
+                    _ => return None,
+                };
+                let st = types.iter().find(|t| t.code == code).unwrap();
+                let date = period.start_date + Duration::days(i as i64);
+                Some(Schedule {
+                    period_id: Some(period.id),
+                    ..fixtures::schedule(user_id, date, st)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compliant_period() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWCHV", user, &types);
+
+        let balance = BalanceCalculator::new().calculate(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(balance.total_hours, 152.0);
+        assert_eq!(balance.night_hours, 38.0);
+        assert_eq!(balance.ch_count, 4);
+        assert_eq!(balance.rh_count, 4);
+        assert_eq!(balance.cv_count, 1);
+        assert!(balance.is_valid);
+        assert!(balance.validation_errors.is_empty());
+    }
+
+    #[test]
+    fn test_counts_leave_and_night_hours() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules("NNLL", user, &types);
+
+        let balance = BalanceCalculator::new().calculate(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(balance.total_hours, 32.0);
+        assert_eq!(balance.night_hours, 16.0);
+        assert_eq!(balance.cn_count, 2);
+        assert!(!balance.is_valid);
+    }
+
+    #[test]
+    fn test_ignores_other_users_and_dates() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let mut rows = schedules("WW", user, &types);
+        rows.extend(schedules("WWWW", Uuid::new_v4(), &types));
+        let mut outside = schedules("W", user, &types);
+        outside[0].date = period().end_date + Duration::days(1);
+        rows.extend(outside);
+
+        let balance = BalanceCalculator::new().calculate(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(balance.total_hours, 16.0);
+    }
+
+    #[test]
+    fn test_holiday_worked_requires_rr() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let holiday = period().start_date;
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWCHV", user, &types);
+
+        let balance =
+            BalanceCalculator::new().calculate(&period(), user, &rows, &types, &[holiday]);
+
+        assert_eq!(balance.holidays_worked, 1);
+        assert!(!balance.is_valid);
+        assert!(balance.validation_errors[0].starts_with("RR manquant"));
+    }
+
+    #[test]
+    fn test_rest_on_holiday_is_not_worked() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let holiday = period().start_date + Duration::days(5);
+        let mut rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWCHV", user, &types);
+        rows[5].is_holiday = true;
+
+        let balance =
+            BalanceCalculator::new().calculate(&period(), user, &rows, &types, &[holiday]);
+
+        assert_eq!(balance.holidays_worked, 0);
+        assert!(balance.is_valid);
+    }
+
+    #[test]
+    fn test_uses_period_hour_quota() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let mut period = period();
+        period.hour_quota = 144;
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWCHV", user, &types);
+
+        let balance = BalanceCalculator::new().calculate(&period, user, &rows, &types, &[]);
+
+        assert!(!balance.is_valid);
+        assert_eq!(
+            balance.validation_errors,
+            vec!["Heures: 152.0/144 (dépassement)"]
+        );
+    }
+
+    #[test]
+    fn test_uses_definition_in_force_on_each_date() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules("WWWW", user, &types);
+        let change = period().start_date + Duration::days(2);
+        let history = ShiftTypeHistory::new(vec![
+            ShiftTypeVersion {
+                duration_hours: 7.0,
+                night_hours: 1.0,
+                ..ShiftTypeVersion::of(&types[0], period().start_date)
+            },
+            ShiftTypeVersion::of(&types[0], change),
+        ]);
+
+        let balance = BalanceCalculator::new().with_history(history).calculate(
+            &period(),
+            user,
+            &rows,
+            &types,
+            &[],
+        );
+
+        assert_eq!(balance.total_hours, 30.0);
+        assert_eq!(balance.night_hours, 6.0);
+    }
+}
@@ -0,0 +1,360 @@
+//! Dashboard Builder Service
+//!
+//! Builds the yearly dashboard ("TABLEAU DE BORD" sheet of generate.py):
+//! - One row per shift code with its count, hours and night hours
+//! - Count of each code per calendar month and per period
+//! - TOTAL row
+//! - Carry-over block ("REPORT POUR {year+1}") for CN and JC
+
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+use crate::domain::entities::{Period, Schedule, ShiftType};
+use crate::domain::services::shift_type_history::ShiftTypeHistory;
+
+/// Leave entitlement used in the carry-over block
+#[derive(Debug, Clone, Serialize)]
+pub struct LeaveEntitlement {
+    pub code: String,
+    /// Annual entitlement ("Droit")
+    pub entitlement: i32,
+    /// Carried over from the previous year ("Report")
+    pub carryover: i32,
+}
+
+/// Dashboard row for one shift code
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardRow {
+    pub code: String,
+    pub description: Option<String>,
+    /// Count over the calendar year
+    pub count: i32,
+    pub hours: f64,
+    pub night_hours: f64,
+    /// Count per calendar month (January = index 0)
+    pub by_month: Vec<i32>,
+    /// Count per period of the year (P1 = index 0)
+    pub by_period: Vec<i32>,
+}
+
+/// TOTAL row
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DashboardTotal {
+    pub count: i32,
+    pub hours: f64,
+    pub night_hours: f64,
+}
+
+/// Carry-over row (Type, Droit, Report, Pris, Solde)
+#[derive(Debug, Clone, Serialize)]
+pub struct CarryOverRow {
+    pub code: String,
+    pub entitlement: i32,
+    pub carryover: i32,
+    pub taken: i32,
+    pub balance: i32,
+}
+
+/// Yearly dashboard
+#[derive(Debug, Clone, Serialize)]
+pub struct YearlyDashboard {
+    pub year: i32,
+    /// Period labels matching `DashboardRow::by_period`
+    pub periods: Vec<String>,
+    pub rows: Vec<DashboardRow>,
+    pub total: DashboardTotal,
+    /// Carry-over to the next year
+    pub carry_over: Vec<CarryOverRow>,
+}
+
+/// Dashboard builder service
+pub struct DashboardBuilder;
+
+impl DashboardBuilder {
+    /// Build the dashboard of a year
+    ///
+    /// Code totals cover the calendar year, as the monthly sheets do.
+    /// Period counts cover each period entirely, even the days of P1 or
+    /// P13 falling in a neighbouring year. Hours come from the shift type
+    /// configuration in force on each day (countable codes only).
+    pub fn build(
+        year: i32,
+        periods: &[Period],
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+        leave: &[LeaveEntitlement],
+    ) -> YearlyDashboard {
+        let mut periods: Vec<&Period> = periods.iter().filter(|p| p.year == year).collect();
+        periods.sort_by_key(|p| p.number);
+
+        // Retired codes keep their row for the years they were used in
+        let mut rows: Vec<DashboardRow> = shift_types
+            .iter()
+            .filter(|st| st.is_active || schedules.iter().any(|s| s.shift_type_id == Some(st.id)))
+            .map(|st| DashboardRow {
+                code: st.code.clone(),
+                description: st.description.clone(),
+                count: 0,
+                hours: 0.0,
+                night_hours: 0.0,
+                by_month: vec![0; 12],
+                by_period: vec![0; periods.len()],
+            })
+            .collect();
+
+        for schedule in schedules {
+            let Some(shift_type) = schedule
+                .shift_type_id
+                .and_then(|id| shift_types.iter().find(|st| st.id == id))
+            else {
+                continue;
+            };
+            let Some(row) = rows.iter_mut().find(|r| r.code == shift_type.code) else {
+                continue;
+            };
+
+            if let Some(index) = periods.iter().position(|p| p.contains_date(schedule.date)) {
+                row.by_period[index] += 1;
+            }
+
+            if schedule.date.year() == year {
+                row.by_month[schedule.date.month0() as usize] += 1;
+                row.count += 1;
+                let shift_type = history.definition_on(shift_type, schedule.date);
+                if shift_type.is_countable {
+                    row.hours += shift_type.duration_hours;
+                }
+                row.night_hours += shift_type.night_hours;
+            }
+        }
+
+        let total = rows.iter().fold(DashboardTotal::default(), |mut t, r| {
+            t.count += r.count;
+            t.hours += r.hours;
+            t.night_hours += r.night_hours;
+            t
+        });
+
+        let carry_over = leave
+            .iter()
+            .map(|l| {
+                let taken = rows
+                    .iter()
+                    .find(|r| r.code == l.code)
+                    .map(|r| r.count)
+                    .unwrap_or(0);
+                CarryOverRow {
+                    code: l.code.clone(),
+                    entitlement: l.entitlement,
+                    carryover: l.carryover,
+                    taken,
+                    balance: l.entitlement + l.carryover - taken,
+                }
+            })
+            .collect();
+
+        YearlyDashboard {
+            year,
+            periods: periods.iter().map(|p| p.label()).collect(),
+            rows,
+            total,
+            carry_over,
+        }
+    }
+
+    /// Calendar range covered by a dashboard (year plus overlapping periods)
+    pub fn date_range(year: i32, periods: &[Period]) -> (NaiveDate, NaiveDate) {
+        let year_start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let year_end = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+
+        periods
+            .iter()
+            .filter(|p| p.year == year)
+            .fold((year_start, year_end), |(start, end), p| {
+                (start.min(p.start_date), end.max(p.end_date))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, periods, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use uuid::Uuid;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("121", ShiftCategory::Night, 8.0, 8.0),
+            shift_type("CN", ShiftCategory::Leave, 8.0, 0.0),
+            shift_type("JC", ShiftCategory::Leave, 8.0, 0.0),
+            shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+        ]
+    }
+
+    fn schedule(date: NaiveDate, code: &str, types: &[ShiftType]) -> Schedule {
+        let shift_type = types.iter().find(|t| t.code == code).unwrap();
+        fixtures::schedule(Uuid::nil(), date, shift_type)
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn row<'a>(dashboard: &'a YearlyDashboard, code: &str) -> &'a DashboardRow {
+        dashboard.rows.iter().find(|r| r.code == code).unwrap()
+    }
+
+    #[test]
+    fn test_counts_per_month_and_period() {
+        let types = shift_types();
+        let rows = vec![
+            schedule(date(1, 5), "101", &types),
+            schedule(date(1, 12), "101", &types),
+            schedule(date(2, 9), "101", &types),
+            schedule(date(2, 10), "121", &types),
+        ];
+
+        let dashboard = DashboardBuilder::build(
+            2026,
+            &periods(2026),
+            &rows,
+            &types,
+            &ShiftTypeHistory::default(),
+            &[],
+        );
+        let standard = row(&dashboard, "101");
+
+        assert_eq!(standard.count, 3);
+        assert_eq!(standard.hours, 24.0);
+        assert_eq!(standard.night_hours, 6.0);
+        assert_eq!(standard.by_month[0], 2);
+        assert_eq!(standard.by_month[1], 1);
+        // 5 January falls before P1
+        assert_eq!(standard.by_period[0], 1);
+        assert_eq!(standard.by_period[1], 1);
+        assert_eq!(dashboard.periods.len(), 13);
+        assert_eq!(dashboard.periods[0], "P1");
+    }
+
+    #[test]
+    fn test_total_row() {
+        let types = shift_types();
+        let rows = vec![
+            schedule(date(3, 2), "101", &types),
+            schedule(date(3, 3), "121", &types),
+            schedule(date(3, 4), "RH", &types),
+        ];
+
+        let dashboard = DashboardBuilder::build(
+            2026,
+            &periods(2026),
+            &rows,
+            &types,
+            &ShiftTypeHistory::default(),
+            &[],
+        );
+
+        assert_eq!(dashboard.total.count, 3);
+        assert_eq!(dashboard.total.hours, 16.0);
+        assert_eq!(dashboard.total.night_hours, 10.0);
+        assert_eq!(dashboard.rows.len(), 5);
+    }
+
+    #[test]
+    fn test_carry_over_block() {
+        let types = shift_types();
+        let rows = vec![
+            schedule(date(4, 1), "CN", &types),
+            schedule(date(4, 2), "CN", &types),
+            schedule(date(4, 3), "JC", &types),
+        ];
+        let leave = vec![
+            LeaveEntitlement {
+                code: "CN".to_string(),
+                entitlement: 20,
+                carryover: 3,
+            },
+            LeaveEntitlement {
+                code: "JC".to_string(),
+                entitlement: 10,
+                carryover: 0,
+            },
+        ];
+
+        let dashboard = DashboardBuilder::build(
+            2026,
+            &periods(2026),
+            &rows,
+            &types,
+            &ShiftTypeHistory::default(),
+            &leave,
+        );
+
+        assert_eq!(dashboard.carry_over[0].taken, 2);
+        assert_eq!(dashboard.carry_over[0].balance, 21);
+        assert_eq!(dashboard.carry_over[1].taken, 1);
+        assert_eq!(dashboard.carry_over[1].balance, 9);
+    }
+
+    #[test]
+    fn test_ignores_other_years_in_totals() {
+        let types = shift_types();
+        // P13 2026 ends in January 2027
+        let rows = vec![schedule(
+            NaiveDate::from_ymd_opt(2027, 1, 5).unwrap(),
+            "101",
+            &types,
+        )];
+
+        let dashboard = DashboardBuilder::build(
+            2026,
+            &periods(2026),
+            &rows,
+            &types,
+            &ShiftTypeHistory::default(),
+            &[],
+        );
+        let standard = row(&dashboard, "101");
+
+        assert_eq!(standard.count, 0);
+        assert_eq!(standard.by_period[12], 1);
+    }
+
+    #[test]
+    fn test_keeps_rows_of_retired_codes_in_use() {
+        let mut types = shift_types();
+        types.push(ShiftType {
+            is_active: false,
+            ..shift_type("X_10", ShiftCategory::Special, 10.0, 0.0)
+        });
+        types.push(ShiftType {
+            is_active: false,
+            ..shift_type("X_12", ShiftCategory::Special, 12.0, 0.0)
+        });
+        let rows = vec![schedule(date(3, 2), "X_10", &types)];
+
+        let dashboard = DashboardBuilder::build(
+            2026,
+            &periods(2026),
+            &rows,
+            &types,
+            &ShiftTypeHistory::default(),
+            &[],
+        );
+
+        assert_eq!(row(&dashboard, "X_10").count, 1);
+        assert_eq!(dashboard.total.hours, 10.0);
+        assert!(dashboard.rows.iter().all(|r| r.code != "X_12"));
+    }
+
+    #[test]
+    fn test_date_range_includes_periods() {
+        let (start, end) = DashboardBuilder::date_range(2026, &periods(2026));
+
+        assert_eq!(start, date(1, 1));
+        assert_eq!(end, NaiveDate::from_ymd_opt(2027, 1, 10).unwrap());
+    }
+}
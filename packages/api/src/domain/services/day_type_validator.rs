@@ -0,0 +1,190 @@
+//! Day Type Validator Service
+//!
+//! Checks that the code variant of each cell matches its day, following
+//! the organization's code grammar:
+//! - Public holidays take the holiday variant (7101)
+//! - Other Sundays take the Sunday variant (6101)
+//! - Other days take the base code (101)
+//!
+//! A day type without a variant in the grammar falls back to the next
+//! rule (a holiday is then treated as a Sunday or a weekday). Standalone
+//! and custom codes fit any day.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::shift_code_grammar::CodeVariant;
+use crate::domain::entities::{Schedule, ShiftCodeGrammar, ShiftType};
+
+/// Type of a day for code variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayType {
+    Weekday,
+    Sunday,
+    Holiday,
+}
+
+impl DayType {
+    /// Type of a date (holidays first, Saturdays are weekdays)
+    pub fn of(date: NaiveDate, holidays: &[NaiveDate]) -> Self {
+        if holidays.contains(&date) {
+            DayType::Holiday
+        } else if date.weekday() == Weekday::Sun {
+            DayType::Sunday
+        } else {
+            DayType::Weekday
+        }
+    }
+}
+
+/// Cell holding a variant that does not fit its day
+#[derive(Debug, Clone, Serialize)]
+pub struct DayTypeMismatch {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub day_type: DayType,
+    pub code: String,
+    pub shift_type_id: Uuid,
+    /// Variant of the same family fitting the day
+    pub expected_code: String,
+    /// Active shift type using the expected code (None when not configured)
+    pub expected_shift_type_id: Option<Uuid>,
+}
+
+/// Day type validator service
+#[derive(Debug, Clone, Default)]
+pub struct DayTypeValidator {
+    grammar: ShiftCodeGrammar,
+}
+
+impl DayTypeValidator {
+    /// Create for the code grammar of an organization
+    pub fn new(grammar: ShiftCodeGrammar) -> Self {
+        Self { grammar }
+    }
+
+    /// Variant expected on a date (None for the base code)
+    pub fn expected_variant(&self, date: NaiveDate, holidays: &[NaiveDate]) -> Option<CodeVariant> {
+        if holidays.contains(&date) && self.grammar.has_variant(CodeVariant::Holiday) {
+            Some(CodeVariant::Holiday)
+        } else if date.weekday() == Weekday::Sun && self.grammar.has_variant(CodeVariant::Sunday) {
+            Some(CodeVariant::Sunday)
+        } else {
+            None
+        }
+    }
+
+    /// Cells of `schedules` whose code variant does not fit their day
+    pub fn validate(
+        &self,
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        holidays: &[NaiveDate],
+    ) -> Vec<DayTypeMismatch> {
+        schedules
+            .iter()
+            .filter_map(|schedule| {
+                let shift_type = schedule
+                    .shift_type_id
+                    .and_then(|id| shift_types.iter().find(|st| st.id == id))?;
+                let parsed = self.grammar.classify(&shift_type.code)?;
+                let family = parsed.family?;
+
+                let expected = self.expected_variant(schedule.date, holidays);
+                if parsed.variant == expected {
+                    return None;
+                }
+                let expected_code = self.grammar.variant_code(&family, expected)?;
+
+                Some(DayTypeMismatch {
+                    user_id: schedule.user_id,
+                    date: schedule.date,
+                    day_type: DayType::of(schedule.date, holidays),
+                    code: shift_type.code.clone(),
+                    shift_type_id: shift_type.id,
+                    expected_shift_type_id: shift_types
+                        .iter()
+                        .find(|st| st.is_active && st.code == expected_code)
+                        .map(|st| st.id),
+                    expected_code,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, shift_type};
+    use crate::domain::entities::shift_code_grammar::CodePrefix;
+    use crate::domain::entities::shift_type::ShiftCategory;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 12, day).unwrap()
+    }
+
+    fn schedule(day: u32, shift_type: &ShiftType) -> Schedule {
+        fixtures::schedule(Uuid::nil(), date(day), shift_type)
+    }
+
+    #[test]
+    fn test_day_types() {
+        let christmas = [date(25)];
+        assert_eq!(DayType::of(date(22), &christmas), DayType::Weekday);
+        assert_eq!(DayType::of(date(26), &christmas), DayType::Weekday);
+        assert_eq!(DayType::of(date(27), &christmas), DayType::Sunday);
+        assert_eq!(DayType::of(date(25), &christmas), DayType::Holiday);
+    }
+
+    #[test]
+    fn test_reports_mismatched_variants() {
+        let types: Vec<_> = ["101", "6101", "7101", "RH"]
+            .into_iter()
+            .map(|code| shift_type(code, ShiftCategory::Standard, 8.0, 2.0))
+            .collect();
+        let christmas = [date(25)];
+        let rows = vec![
+            // Tuesday 22 December
+            schedule(22, &types[2]),
+            schedule(25, &types[0]),
+            // Sunday 27 December
+            schedule(27, &types[1]),
+            schedule(27, &types[3]),
+            schedule(28, &types[0]),
+        ];
+
+        let mismatches = DayTypeValidator::default().validate(&rows, &types, &christmas);
+
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(mismatches[0].day_type, DayType::Weekday);
+        assert_eq!(mismatches[0].expected_code, "101");
+        assert_eq!(mismatches[0].expected_shift_type_id, Some(types[0].id));
+        assert_eq!(mismatches[1].day_type, DayType::Holiday);
+        assert_eq!(mismatches[1].expected_code, "7101");
+    }
+
+    #[test]
+    fn test_missing_variants_fall_back() {
+        let types = vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("F101", ShiftCategory::Standard, 8.0, 2.0),
+        ];
+        let grammar = ShiftCodeGrammar {
+            prefixes: vec![CodePrefix {
+                prefix: "F".to_string(),
+                variant: CodeVariant::Holiday,
+            }],
+            ..ShiftCodeGrammar::default()
+        };
+        // No Sunday variant: the base code fits Sundays
+        let rows = vec![schedule(27, &types[0]), schedule(25, &types[0])];
+
+        let mismatches = DayTypeValidator::new(grammar).validate(&rows, &types, &[date(25)]);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].expected_code, "F101");
+    }
+}
@@ -0,0 +1,436 @@
+//! Fairness Analyzer Service
+//!
+//! Measures how unpleasant shifts are spread across a team:
+//! - Full nights (121, 6121, 7121)
+//! - Night hours
+//! - Weekend days worked
+//! - Holidays worked (holiday codes, e.g. 7xxx, or worked public holidays)
+//! - AG days (strike)
+//!
+//! Shift types count with the definition in force on each schedule date.
+//!
+//! For each metric the team distribution (mean, standard deviation) is
+//! computed and agents too far from the mean are reported as outliers.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::{Schedule, ShiftCodeGrammar, ShiftType};
+use crate::domain::services::shift_type_history::ShiftTypeHistory;
+
+/// Metric tracked for fairness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FairnessMetric {
+    FullNights,
+    NightHours,
+    WeekendDays,
+    HolidaysWorked,
+    AgDays,
+}
+
+impl FairnessMetric {
+    /// All metrics, in report order
+    pub const ALL: [FairnessMetric; 5] = [
+        FairnessMetric::FullNights,
+        FairnessMetric::NightHours,
+        FairnessMetric::WeekendDays,
+        FairnessMetric::HolidaysWorked,
+        FairnessMetric::AgDays,
+    ];
+}
+
+/// Per-agent counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FairnessMetrics {
+    pub full_nights: i32,
+    pub night_hours: f64,
+    pub weekend_days: i32,
+    pub holidays_worked: i32,
+    pub ag_days: i32,
+}
+
+impl FairnessMetrics {
+    /// Get the value of a metric
+    pub fn value(&self, metric: FairnessMetric) -> f64 {
+        match metric {
+            FairnessMetric::FullNights => self.full_nights as f64,
+            FairnessMetric::NightHours => self.night_hours,
+            FairnessMetric::WeekendDays => self.weekend_days as f64,
+            FairnessMetric::HolidaysWorked => self.holidays_worked as f64,
+            FairnessMetric::AgDays => self.ag_days as f64,
+        }
+    }
+}
+
+/// Counters of one agent
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentFairness {
+    pub user_id: Uuid,
+    pub metrics: FairnessMetrics,
+}
+
+/// Team distribution of a metric
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricDistribution {
+    pub metric: FairnessMetric,
+    pub total: f64,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Agent whose value is far from the team mean
+#[derive(Debug, Clone, Serialize)]
+pub struct FairnessOutlier {
+    pub user_id: Uuid,
+    pub metric: FairnessMetric,
+    pub value: f64,
+    pub mean: f64,
+    /// Distance from the mean in standard deviations (signed)
+    pub z_score: f64,
+}
+
+/// Fairness report over a date range
+#[derive(Debug, Clone, Serialize)]
+pub struct FairnessReport {
+    pub agents: Vec<AgentFairness>,
+    pub distribution: Vec<MetricDistribution>,
+    pub outliers: Vec<FairnessOutlier>,
+}
+
+/// Fairness analyzer service
+pub struct FairnessAnalyzer {
+    /// Minimum |z-score| for an agent to be reported as an outlier
+    pub outlier_threshold: f64,
+    /// Code grammar telling holiday codes apart
+    pub grammar: ShiftCodeGrammar,
+    /// Shift type definitions in force on each schedule date
+    pub history: ShiftTypeHistory,
+}
+
+impl Default for FairnessAnalyzer {
+    fn default() -> Self {
+        Self {
+            outlier_threshold: 1.5,
+            grammar: ShiftCodeGrammar::default(),
+            history: ShiftTypeHistory::default(),
+        }
+    }
+}
+
+impl FairnessAnalyzer {
+    /// Create with the default outlier threshold (1.5σ)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create with a custom outlier threshold
+    pub fn with_threshold(outlier_threshold: f64) -> Self {
+        Self {
+            outlier_threshold,
+            ..Self::default()
+        }
+    }
+
+    /// Use the code grammar of an organization
+    pub fn with_grammar(self, grammar: ShiftCodeGrammar) -> Self {
+        Self { grammar, ..self }
+    }
+
+    /// Use the shift type definitions in force on each schedule date
+    pub fn with_history(self, history: ShiftTypeHistory) -> Self {
+        Self { history, ..self }
+    }
+
+    /// Analyze the schedules of `agents`
+    ///
+    /// Every agent is part of the distribution, even without schedules,
+    /// so that an agent never assigned a night still weighs on the mean.
+    pub fn analyze(
+        &self,
+        agents: &[Uuid],
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        holidays: &[NaiveDate],
+    ) -> FairnessReport {
+        let agents: Vec<AgentFairness> = agents
+            .iter()
+            .map(|user_id| AgentFairness {
+                user_id: *user_id,
+                metrics: self.count(*user_id, schedules, shift_types, holidays),
+            })
+            .collect();
+
+        let mut distribution = Vec::new();
+        let mut outliers = Vec::new();
+
+        for metric in FairnessMetric::ALL {
+            let values: Vec<f64> = agents.iter().map(|a| a.metrics.value(metric)).collect();
+            let Some(dist) = Self::distribution(metric, &values) else {
+                continue;
+            };
+
+            if dist.std_dev > 0.0 {
+                for agent in &agents {
+                    let value = agent.metrics.value(metric);
+                    let z_score = (value - dist.mean) / dist.std_dev;
+                    if z_score.abs() >= self.outlier_threshold {
+                        outliers.push(FairnessOutlier {
+                            user_id: agent.user_id,
+                            metric,
+                            value,
+                            mean: dist.mean,
+                            z_score,
+                        });
+                    }
+                }
+            }
+
+            distribution.push(dist);
+        }
+
+        outliers.sort_by(|a, b| b.z_score.abs().total_cmp(&a.z_score.abs()));
+
+        FairnessReport {
+            agents,
+            distribution,
+            outliers,
+        }
+    }
+
+    /// Count the metrics of one agent
+    fn count(
+        &self,
+        user_id: Uuid,
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        holidays: &[NaiveDate],
+    ) -> FairnessMetrics {
+        let mut metrics = FairnessMetrics::default();
+
+        for schedule in schedules.iter().filter(|s| s.user_id == user_id) {
+            let Some(shift_type) = schedule
+                .shift_type_id
+                .and_then(|id| shift_types.iter().find(|st| st.id == id))
+            else {
+                continue;
+            };
+            let shift_type = self.history.definition_on(shift_type, schedule.date);
+
+            // Strike days are not worked, whatever their category
+            if shift_type.code == "AG" {
+                metrics.ag_days += 1;
+                continue;
+            }
+            if !shift_type.is_worked() {
+                continue;
+            }
+
+            if shift_type.is_full_night() {
+                metrics.full_nights += 1;
+            }
+            metrics.night_hours += shift_type.night_hours;
+
+            if matches!(schedule.date.weekday(), Weekday::Sat | Weekday::Sun) {
+                metrics.weekend_days += 1;
+            }
+
+            let is_holiday = schedule.is_holiday || holidays.contains(&schedule.date);
+            if is_holiday || shift_type.is_holiday_work(&self.grammar) {
+                metrics.holidays_worked += 1;
+            }
+        }
+
+        metrics
+    }
+
+    /// Compute the distribution of a metric (None for an empty team)
+    fn distribution(metric: FairnessMetric, values: &[f64]) -> Option<MetricDistribution> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let total: f64 = values.iter().sum();
+        let mean = total / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        Some(MetricDistribution {
+            metric,
+            total,
+            mean,
+            std_dev: variance.sqrt(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::ShiftTypeVersion;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("121", ShiftCategory::Night, 8.0, 8.0),
+            shift_type("7101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("AG", ShiftCategory::Special, 8.0, 0.0),
+        ]
+    }
+
+    fn schedule(user_id: Uuid, date: NaiveDate, code: &str, types: &[ShiftType]) -> Schedule {
+        let shift_type = types.iter().find(|t| t.code == code).unwrap();
+        fixtures::schedule(user_id, date, shift_type)
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        // January 2026: the 17th is a Saturday
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    #[test]
+    fn test_counts_metrics() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = vec![
+            schedule(user, date(12), "121", &types),
+            schedule(user, date(17), "101", &types),
+            schedule(user, date(18), "7101", &types),
+            schedule(user, date(19), "AG", &types),
+        ];
+
+        let report = FairnessAnalyzer::new().analyze(&[user], &rows, &types, &[]);
+        let metrics = &report.agents[0].metrics;
+
+        assert_eq!(metrics.full_nights, 1);
+        assert_eq!(metrics.night_hours, 12.0);
+        assert_eq!(metrics.weekend_days, 2);
+        assert_eq!(metrics.holidays_worked, 1);
+        assert_eq!(metrics.ag_days, 1);
+    }
+
+    #[test]
+    fn test_worked_public_holiday_counts() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = vec![schedule(user, date(13), "101", &types)];
+
+        let report = FairnessAnalyzer::new().analyze(&[user], &rows, &types, &[date(13)]);
+
+        assert_eq!(report.agents[0].metrics.holidays_worked, 1);
+    }
+
+    #[test]
+    fn test_strike_days_are_not_worked() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        // A Saturday and a public holiday
+        let rows = vec![
+            schedule(user, date(17), "AG", &types),
+            schedule(user, date(13), "AG", &types),
+        ];
+
+        let report = FairnessAnalyzer::new().analyze(&[user], &rows, &types, &[date(13)]);
+        let metrics = &report.agents[0].metrics;
+
+        assert_eq!(metrics.ag_days, 2);
+        assert_eq!(metrics.weekend_days, 0);
+        assert_eq!(metrics.holidays_worked, 0);
+        assert_eq!(metrics.night_hours, 0.0);
+    }
+
+    #[test]
+    fn test_night_hours_follow_versions() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = vec![
+            schedule(user, date(12), "101", &types),
+            schedule(user, date(14), "101", &types),
+            schedule(user, date(19), "101", &types),
+        ];
+        // 101 had 4 night hours until the 15th
+        let history = ShiftTypeHistory::new(vec![
+            ShiftTypeVersion {
+                night_hours: 4.0,
+                ..ShiftTypeVersion::of(&types[0], date(1))
+            },
+            ShiftTypeVersion::of(&types[0], date(15)),
+        ]);
+
+        let report =
+            FairnessAnalyzer::new()
+                .with_history(history)
+                .analyze(&[user], &rows, &types, &[]);
+
+        assert_eq!(report.agents[0].metrics.night_hours, 4.0 + 4.0 + 2.0);
+    }
+
+    #[test]
+    fn test_distribution() {
+        let types = shift_types();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            schedule(a, date(12), "121", &types),
+            schedule(a, date(13), "121", &types),
+            schedule(a, date(14), "121", &types),
+            schedule(b, date(12), "121", &types),
+        ];
+
+        let report = FairnessAnalyzer::new().analyze(&[a, b], &rows, &types, &[]);
+        let nights = report
+            .distribution
+            .iter()
+            .find(|d| d.metric == FairnessMetric::FullNights)
+            .unwrap();
+
+        assert_eq!(nights.total, 4.0);
+        assert_eq!(nights.mean, 2.0);
+        assert_eq!(nights.std_dev, 1.0);
+        assert_eq!(nights.min, 1.0);
+        assert_eq!(nights.max, 3.0);
+    }
+
+    #[test]
+    fn test_detects_outlier() {
+        let types = shift_types();
+        let agents: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        let mut rows: Vec<Schedule> = agents
+            .iter()
+            .map(|a| schedule(*a, date(12), "121", &types))
+            .collect();
+        for day in 13..=16 {
+            rows.push(schedule(agents[0], date(day), "121", &types));
+        }
+
+        let report = FairnessAnalyzer::new().analyze(&agents, &rows, &types, &[]);
+        let nights: Vec<_> = report
+            .outliers
+            .iter()
+            .filter(|o| o.metric == FairnessMetric::FullNights)
+            .collect();
+
+        assert_eq!(nights.len(), 1);
+        assert_eq!(nights[0].user_id, agents[0]);
+        assert!(nights[0].z_score > 1.5);
+    }
+
+    #[test]
+    fn test_agent_without_schedules_is_included() {
+        let types = shift_types();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![schedule(a, date(12), "121", &types)];
+
+        let report = FairnessAnalyzer::new().analyze(&[a, b], &rows, &types, &[]);
+
+        assert_eq!(report.agents.len(), 2);
+        assert_eq!(report.agents[1].metrics, FairnessMetrics::default());
+    }
+}
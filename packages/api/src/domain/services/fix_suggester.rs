@@ -0,0 +1,537 @@
+//! Fix Suggester Service
+//!
+//! Proposes minimal schedule edits that resolve the quota violations
+//! reported by the QuotaValidator:
+//! - CH/RH/CV counts different from the expected quota
+//! - RR missing for a worked holiday
+//! - Hours above the period maximum
+//!
+//! Each suggestion changes a single day and never moves another rule
+//! further away from compliance.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::{Period, Schedule, ShiftType};
+use crate::domain::services::quota_validator::QuotaValidator;
+
+/// Rule a suggestion brings closer to compliance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaIssue {
+    ChCount,
+    RhCount,
+    CvCount,
+    MissingRecovery,
+    HoursExceeded,
+}
+
+impl QuotaIssue {
+    /// Check if this issue is a hard constraint (validation error)
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            QuotaIssue::MissingRecovery | QuotaIssue::HoursExceeded
+        )
+    }
+}
+
+/// A single-day edit proposed to fix quota violations
+#[derive(Debug, Clone, Serialize)]
+pub struct FixSuggestion {
+    /// Position in the ranking (1 = apply first)
+    pub rank: usize,
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    pub current_code: Option<String>,
+    pub current_shift_type_id: Option<Uuid>,
+    /// Suggested code (None = clear the day)
+    pub suggested_code: Option<String>,
+    pub suggested_shift_type_id: Option<Uuid>,
+    /// Rules this edit brings closer to compliance
+    pub resolves: Vec<QuotaIssue>,
+    pub reason: String,
+}
+
+impl FixSuggestion {
+    /// Convert into a schedule entry ready to be written
+    pub fn to_create_schedule(&self) -> CreateSchedule {
+        CreateSchedule {
+            user_id: self.user_id,
+            shift_type_id: self.suggested_shift_type_id,
+            date: self.date,
+            notes: None,
+        }
+    }
+}
+
+/// Codes the suggester may place on a day
+const TARGET_CODES: &[&str] = &["RR", "CH", "RH", "CV"];
+
+/// Fix suggester service
+pub struct FixSuggester {
+    validator: QuotaValidator,
+}
+
+impl Default for FixSuggester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One day of the agent's period being simulated
+#[derive(Clone, Copy)]
+struct Day<'a> {
+    date: NaiveDate,
+    shift: Option<&'a ShiftType>,
+    is_holiday: bool,
+    edited: bool,
+}
+
+impl Day<'_> {
+    fn code(&self) -> Option<&str> {
+        self.shift.map(|s| s.code.as_str())
+    }
+
+    fn is_worked(&self) -> bool {
+        self.shift.is_some_and(ShiftType::is_worked)
+    }
+
+    fn is_worked_holiday(&self) -> bool {
+        self.is_holiday && self.is_worked()
+    }
+}
+
+/// Distance of each rule from compliance (0 = satisfied)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Distances {
+    ch: i32,
+    rh: i32,
+    cv: i32,
+    rr: i32,
+    hours: f64,
+}
+
+impl Distances {
+    fn is_compliant(&self) -> bool {
+        self.ch == 0 && self.rh == 0 && self.cv == 0 && self.rr == 0 && self.hours <= 0.0
+    }
+
+    /// Issues improved when moving from `self` to `after`,
+    /// or None if any rule gets worse
+    fn improvements(&self, after: &Distances) -> Option<Vec<QuotaIssue>> {
+        if after.ch > self.ch
+            || after.rh > self.rh
+            || after.cv > self.cv
+            || after.rr > self.rr
+            || after.hours > self.hours
+        {
+            return None;
+        }
+
+        let mut improved = Vec::new();
+        if after.rr < self.rr {
+            improved.push(QuotaIssue::MissingRecovery);
+        }
+        if after.hours < self.hours {
+            improved.push(QuotaIssue::HoursExceeded);
+        }
+        if after.ch < self.ch {
+            improved.push(QuotaIssue::ChCount);
+        }
+        if after.rh < self.rh {
+            improved.push(QuotaIssue::RhCount);
+        }
+        if after.cv < self.cv {
+            improved.push(QuotaIssue::CvCount);
+        }
+        Some(improved)
+    }
+}
+
+/// Candidate edit with its ranking key
+struct Candidate<'a> {
+    index: usize,
+    target: Option<&'a ShiftType>,
+    resolves: Vec<QuotaIssue>,
+    /// Lower is better: (errors fixed desc, issues fixed desc, disruption, proximity, date)
+    key: (i32, i32, i32, i64, NaiveDate),
+}
+
+impl FixSuggester {
+    /// Create with default quotas
+    pub fn new() -> Self {
+        Self {
+            validator: QuotaValidator::new(),
+        }
+    }
+
+    /// Suggest ranked edits for one agent within a period
+    ///
+    /// `schedules` are the agent's entries in the period, `holidays`
+    /// the holiday dates of the organization.
+    pub fn suggest(
+        &self,
+        period: &Period,
+        user_id: Uuid,
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        holidays: &[NaiveDate],
+    ) -> Vec<FixSuggestion> {
+        let mut days = Self::build_days(period, user_id, schedules, shift_types, holidays);
+        let targets: Vec<&ShiftType> = TARGET_CODES
+            .iter()
+            .filter_map(|code| {
+                shift_types
+                    .iter()
+                    .find(|st| st.is_active && st.code == *code)
+            })
+            .collect();
+
+        let mut suggestions = Vec::new();
+
+        loop {
+            let before = self.distances(&days);
+            if before.is_compliant() {
+                break;
+            }
+
+            let Some(best) = self.best_candidate(&days, &targets, &before) else {
+                break;
+            };
+
+            let day = days[best.index];
+            suggestions.push(FixSuggestion {
+                rank: suggestions.len() + 1,
+                user_id,
+                date: day.date,
+                current_code: day.code().map(str::to_string),
+                current_shift_type_id: day.shift.map(|s| s.id),
+                suggested_code: best.target.map(|t| t.code.clone()),
+                suggested_shift_type_id: best.target.map(|t| t.id),
+                reason: Self::reason(&day, best.target, &best.resolves),
+                resolves: best.resolves,
+            });
+
+            days[best.index].shift = best.target;
+            days[best.index].edited = true;
+        }
+
+        suggestions
+    }
+
+    /// Build the simulated day list covering the whole period
+    fn build_days<'a>(
+        period: &Period,
+        user_id: Uuid,
+        schedules: &[Schedule],
+        shift_types: &'a [ShiftType],
+        holidays: &[NaiveDate],
+    ) -> Vec<Day<'a>> {
+        let mut days = Vec::new();
+        let mut date = period.start_date;
+
+        while date <= period.end_date {
+            let schedule = schedules
+                .iter()
+                .find(|s| s.user_id == user_id && s.date == date);
+            let shift = schedule
+                .and_then(|s| s.shift_type_id)
+                .and_then(|id| shift_types.iter().find(|st| st.id == id));
+
+            days.push(Day {
+                date,
+                shift,
+                is_holiday: holidays.contains(&date) || schedule.is_some_and(|s| s.is_holiday),
+                edited: false,
+            });
+            date += Duration::days(1);
+        }
+
+        days
+    }
+
+    /// Compute the distance of each rule from compliance
+    fn distances(&self, days: &[Day]) -> Distances {
+        let count = |code: &str| days.iter().filter(|d| d.code() == Some(code)).count() as i32;
+        let hours: f64 = days
+            .iter()
+            .filter_map(|d| d.shift)
+            .filter(|s| s.is_countable)
+            .map(|s| s.duration_hours)
+            .sum();
+        let holidays_worked = days.iter().filter(|d| d.is_worked_holiday()).count() as i32;
+
+        Distances {
+            ch: (count("CH") - self.validator.expected_ch).abs(),
+            rh: (count("RH") - self.validator.expected_rh).abs(),
+            cv: (count("CV") - self.validator.expected_cv).abs(),
+            rr: (holidays_worked - count("RR")).max(0),
+            hours: (hours - self.validator.max_hours).max(0.0),
+        }
+    }
+
+    /// Find the best single-day edit, if any improves compliance
+    fn best_candidate<'a>(
+        &self,
+        days: &[Day<'a>],
+        targets: &[&'a ShiftType],
+        before: &Distances,
+    ) -> Option<Candidate<'a>> {
+        let unmatched_holidays = Self::unmatched_worked_holidays(days);
+        let mut best: Option<Candidate<'a>> = None;
+
+        for (index, day) in days.iter().enumerate() {
+            if !Self::is_editable(day) {
+                continue;
+            }
+
+            let options = targets
+                .iter()
+                .map(|t| Some(*t))
+                .chain(std::iter::once(None));
+            for target in options {
+                if target.map(|t| t.id) == day.shift.map(|s| s.id) {
+                    continue;
+                }
+                // Clearing is only useful to remove hours or a surplus rest day
+                if target.is_none() && day.shift.is_none() {
+                    continue;
+                }
+
+                // RR goes on the nearest day after a worked holiday
+                let proximity = if target.is_some_and(|t| t.code == "RR") {
+                    match unmatched_holidays.iter().filter(|h| **h < day.date).max() {
+                        Some(holiday) => (day.date - *holiday).num_days(),
+                        None => continue,
+                    }
+                } else {
+                    0
+                };
+
+                let mut simulated = days.to_vec();
+                simulated[index].shift = target;
+                let after = self.distances(&simulated);
+
+                let Some(resolves) = before.improvements(&after) else {
+                    continue;
+                };
+                if resolves.is_empty() {
+                    continue;
+                }
+
+                let errors = resolves.iter().filter(|i| i.is_error()).count() as i32;
+                let key = (
+                    -errors,
+                    -(resolves.len() as i32),
+                    Self::disruption(day, target),
+                    proximity,
+                    day.date,
+                );
+
+                if best.as_ref().is_none_or(|b| key < b.key) {
+                    best = Some(Candidate {
+                        index,
+                        target,
+                        resolves,
+                        key,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Days that may be changed by a suggestion
+    ///
+    /// Leave (CN, JC), ZM and worked holidays are never touched,
+    /// and each day is edited at most once.
+    fn is_editable(day: &Day) -> bool {
+        if day.edited || day.is_worked_holiday() {
+            return false;
+        }
+        match day.shift {
+            None => true,
+            Some(st) => st.is_worked() || TARGET_CODES.contains(&st.code.as_str()),
+        }
+    }
+
+    /// Cost of changing a day (lower = less disruptive)
+    fn disruption(day: &Day, target: Option<&ShiftType>) -> i32 {
+        let weekend_bonus = match day.date.weekday() {
+            chrono::Weekday::Sat | chrono::Weekday::Sun if target.is_some() => 0,
+            _ => 1,
+        };
+        let base = match day.shift {
+            None => 0,
+            Some(st) if !st.is_worked() => 2,
+            Some(_) => 4,
+        };
+        base + weekend_bonus
+    }
+
+    /// Worked holidays not followed by an RR, oldest first
+    fn unmatched_worked_holidays(days: &[Day]) -> Vec<NaiveDate> {
+        let mut rr_days: Vec<NaiveDate> = days
+            .iter()
+            .filter(|d| d.code() == Some("RR"))
+            .map(|d| d.date)
+            .collect();
+        let mut unmatched = Vec::new();
+
+        for holiday in days.iter().filter(|d| d.is_worked_holiday()) {
+            match rr_days.iter().position(|rr| *rr > holiday.date) {
+                Some(pos) => {
+                    rr_days.remove(pos);
+                }
+                None => unmatched.push(holiday.date),
+            }
+        }
+
+        unmatched
+    }
+
+    /// Human readable explanation (French, like validation messages)
+    fn reason(day: &Day, target: Option<&ShiftType>, resolves: &[QuotaIssue]) -> String {
+        let date = day.date.format("%d/%m");
+        let current = day.code().unwrap_or("vide");
+        let action = match target {
+            Some(t) => format!("Passer le {} de {} à {}", date, current, t.code),
+            None => format!("Libérer le {} ({})", date, current),
+        };
+        let labels: Vec<&str> = resolves
+            .iter()
+            .map(|issue| match issue {
+                QuotaIssue::ChCount => "CH",
+                QuotaIssue::RhCount => "RH",
+                QuotaIssue::CvCount => "CV",
+                QuotaIssue::MissingRecovery => "RR manquant",
+                QuotaIssue::HoursExceeded => "dépassement d'heures",
+            })
+            .collect();
+
+        format!("{} ({})", action, labels.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, period, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 0.0),
+            shift_type("7101", ShiftCategory::Standard, 8.0, 0.0),
+            shift_type("CH", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("RR", ShiftCategory::Rest, 0.0, 0.0),
+            shift_type("CV", ShiftCategory::Leave, 0.0, 0.0),
+        ]
+    }
+
+    /// Build a period from a 28-character pattern:
+    /// W = 101, F = 7101, C = CH, H = RH, R = RR, V = CV, . = empty
+    fn schedules(pattern: &str, user_id: Uuid, types: &[ShiftType]) -> Vec<Schedule> {
+        let period = period();
+        pattern
+            .chars()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                let code = match c {
+                    'W' => "101",
+                    'F' => "7101",
+                    'C' => "CH",
+                    'H' => "RH",
+                    'R' => "RR",
+                    'V' => "CV",
+                    _ => return None,
+                };
+                let st = types.iter().find(|t| t.code == code).unwrap();
+                let date = period.start_date + Duration::days(i as i64);
+                Some(Schedule {
+                    period_id: Some(period.id),
+                    ..fixtures::schedule(user_id, date, st)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compliant_period_has_no_suggestions() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        // 19 worked days (152h), 4 CH, 4 RH, 1 CV
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWCHV", user, &types);
+        let suggestions = FixSuggester::new().suggest(&period(), user, &rows, &types, &[]);
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_missing_ch_uses_empty_day_first() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        // 3 CH, one empty day
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWW.HV", user, &types);
+        let suggestions = FixSuggester::new().suggest(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].suggested_code.as_deref(), Some("CH"));
+        assert_eq!(suggestions[0].current_code, None);
+        assert_eq!(suggestions[0].resolves, vec![QuotaIssue::ChCount]);
+    }
+
+    #[test]
+    fn test_missing_ch_over_hours_converts_working_day() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        // 3 CH, no CV and 21 worked days (168h)
+        let rows = schedules("WWWWWCHWWWWWCHWWWWWCHWWWWWWH", user, &types);
+        let suggestions = FixSuggester::new().suggest(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(suggestions[0].current_code.as_deref(), Some("101"));
+        assert_eq!(suggestions[0].suggested_code.as_deref(), Some("CH"));
+        assert_eq!(
+            suggestions[0].resolves,
+            vec![QuotaIssue::HoursExceeded, QuotaIssue::ChCount]
+        );
+    }
+
+    #[test]
+    fn test_rr_placed_on_nearest_day_after_worked_holiday() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let holiday = period().start_date + Duration::days(2);
+        // Holiday worked on day 3, empty days on day 5 and day 10
+        let rows = schedules("WWFW.CHWW.WCHWWWWWCHWWWWCHWV", user, &types);
+        let suggestions = FixSuggester::new().suggest(&period(), user, &rows, &types, &[holiday]);
+
+        assert_eq!(suggestions[0].suggested_code.as_deref(), Some("RR"));
+        assert_eq!(suggestions[0].date, holiday + Duration::days(2));
+        assert_eq!(suggestions[0].resolves, vec![QuotaIssue::MissingRecovery]);
+    }
+
+    #[test]
+    fn test_suggestions_never_break_other_rules() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        // 6 CH, 2 RH: surplus CH should become RH
+        let rows = schedules("WWWWWCCWWWWWCCWWWWWCHWWWWCHV", user, &types);
+        let suggestions = FixSuggester::new().suggest(&period(), user, &rows, &types, &[]);
+
+        assert_eq!(suggestions.len(), 2);
+        for suggestion in &suggestions {
+            assert_eq!(suggestion.current_code.as_deref(), Some("CH"));
+            assert_eq!(suggestion.suggested_code.as_deref(), Some("RH"));
+            assert_eq!(
+                suggestion.resolves,
+                vec![QuotaIssue::ChCount, QuotaIssue::RhCount]
+            );
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Hour Bank Calculator Service
+//!
+//! Computes the hour bank entry recorded when a period is closed:
+//! - Delta = period hours - period quota
+//! - New balance = previous balance + delta
+//! - Credit above the cap is paid out or forfeited (policy)
+//! - Debt below the floor is written off
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::hour_bank::{HourBankEntry, HourBankExcess, HourBankPolicy};
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::Period;
+
+/// Hour bank calculator service
+pub struct HourBankCalculator {
+    policy: HourBankPolicy,
+}
+
+impl Default for HourBankCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HourBankCalculator {
+    /// Create with the default policy
+    pub fn new() -> Self {
+        Self {
+            policy: HourBankPolicy::default(),
+        }
+    }
+
+    /// Create with an organization policy
+    pub fn with_policy(policy: HourBankPolicy) -> Self {
+        Self { policy }
+    }
+
+    /// Compute the ledger entry closing `period` for the balance's agent
+    ///
+    /// `previous_balance` is the running balance after the last closed period.
+    pub fn close(
+        &self,
+        period: &Period,
+        balance: &PeriodBalance,
+        previous_balance: f64,
+    ) -> HourBankEntry {
+        let delta = balance.total_hours - period.hour_quota as f64;
+        let running = previous_balance + delta;
+
+        let mut paid_out = 0.0;
+        let mut forfeited = 0.0;
+        let mut carried = running;
+
+        if running > self.policy.max_balance {
+            let excess = running - self.policy.max_balance;
+            match self.policy.excess {
+                HourBankExcess::Payout => paid_out = excess,
+                HourBankExcess::Forfeit => forfeited = excess,
+            }
+            carried = self.policy.max_balance;
+        } else if running < self.policy.min_balance {
+            forfeited = running - self.policy.min_balance;
+            carried = self.policy.min_balance;
+        }
+
+        HourBankEntry {
+            id: Uuid::new_v4(),
+            organization_id: period.organization_id,
+            user_id: balance.user_id,
+            period_id: period.id,
+            total_hours: balance.total_hours,
+            hour_quota: period.hour_quota,
+            delta,
+            paid_out,
+            forfeited,
+            balance: carried,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::period;
+
+    fn balance(hours: f64) -> PeriodBalance {
+        PeriodBalance {
+            id: Uuid::new_v4(),
+            period_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            total_hours: hours,
+            night_hours: 0.0,
+            ch_count: 4,
+            rh_count: 4,
+            cv_count: 1,
+            rr_count: 0,
+            cn_count: 0,
+            jc_count: 0,
+            holidays_worked: 0,
+            is_valid: true,
+            validation_errors: vec![],
+            calculated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_carries_over_within_caps() {
+        let entry = HourBankCalculator::new().close(&period(), &balance(168.0), 4.0);
+
+        assert_eq!(entry.delta, 8.0);
+        assert_eq!(entry.balance, 12.0);
+        assert_eq!(entry.paid_out, 0.0);
+        assert_eq!(entry.forfeited, 0.0);
+    }
+
+    #[test]
+    fn test_pays_out_above_cap() {
+        let entry = HourBankCalculator::new().close(&period(), &balance(176.0), 8.0);
+
+        assert_eq!(entry.balance, 16.0);
+        assert_eq!(entry.paid_out, 8.0);
+        assert_eq!(entry.forfeited, 0.0);
+    }
+
+    #[test]
+    fn test_forfeits_above_cap() {
+        let policy = HourBankPolicy {
+            excess: HourBankExcess::Forfeit,
+            ..HourBankPolicy::default()
+        };
+        let entry = HourBankCalculator::with_policy(policy).close(&period(), &balance(176.0), 8.0);
+
+        assert_eq!(entry.balance, 16.0);
+        assert_eq!(entry.paid_out, 0.0);
+        assert_eq!(entry.forfeited, 8.0);
+    }
+
+    #[test]
+    fn test_writes_off_debt_below_floor() {
+        let entry = HourBankCalculator::new().close(&period(), &balance(144.0), -8.0);
+
+        assert_eq!(entry.delta, -16.0);
+        assert_eq!(entry.balance, -16.0);
+        assert_eq!(entry.forfeited, -8.0);
+    }
+
+    #[test]
+    fn test_uses_period_quota() {
+        let mut period = period();
+        period.hour_quota = 152;
+        let entry = HourBankCalculator::new().close(&period, &balance(152.0), 0.0);
+
+        assert_eq!(entry.delta, 0.0);
+        assert_eq!(entry.balance, 0.0);
+    }
+}
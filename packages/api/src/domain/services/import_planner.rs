@@ -0,0 +1,341 @@
+//! Import Planner Service
+//!
+//! Turns the codes read from a planning workbook into schedule entries:
+//! - Codes are mapped to the organization's active shift types by code;
+//!   unmapped codes are reported as invalid unless the organization's code
+//!   grammar knows them
+//! - Days already holding the same code are left unchanged
+//! - Days holding another code are conflicts (written only on overwrite)
+//! - Days in closed periods are never written
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::{Period, Schedule, ShiftCodeGrammar, ShiftType};
+
+/// Code read from a calendar cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedDay {
+    pub date: NaiveDate,
+    pub code: String,
+    /// Cell reference, e.g. "Mars!C6"
+    pub cell: String,
+}
+
+/// Why a code cannot be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownCodeReason {
+    /// Not a code of the organization's grammar
+    Invalid,
+    /// Known to the grammar or deactivated, but no active shift type of the
+    /// organization uses it
+    NotConfigured,
+}
+
+/// Code that cannot be mapped to a shift type
+#[derive(Debug, Clone, Serialize)]
+pub struct UnknownCode {
+    pub code: String,
+    pub reason: UnknownCodeReason,
+    pub cells: Vec<String>,
+}
+
+/// Kind of conflict with the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The day already holds another code
+    Different,
+    /// The day belongs to a closed period
+    ClosedPeriod,
+}
+
+/// Imported day conflicting with an existing row
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportConflict {
+    pub date: NaiveDate,
+    pub cell: String,
+    pub existing_code: Option<String>,
+    pub imported_code: String,
+    pub kind: ConflictKind,
+}
+
+/// Result of planning an import
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    /// Entries to write
+    pub entries: Vec<CreateSchedule>,
+    /// Days already holding the imported code
+    pub unchanged: usize,
+    pub unknown_codes: Vec<UnknownCode>,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+/// Import planner service
+pub struct ImportPlanner;
+
+impl ImportPlanner {
+    /// Plan the import of an agent's days
+    ///
+    /// With `overwrite`, days holding another code are written too (they
+    /// are still reported as conflicts). Closed periods are always skipped.
+    pub fn plan(
+        user_id: Uuid,
+        days: &[ImportedDay],
+        shift_types: &[ShiftType],
+        grammar: &ShiftCodeGrammar,
+        existing: &[Schedule],
+        periods: &[Period],
+        overwrite: bool,
+    ) -> ImportPlan {
+        let mut plan = ImportPlan {
+            entries: Vec::new(),
+            unchanged: 0,
+            unknown_codes: Vec::new(),
+            conflicts: Vec::new(),
+        };
+
+        for day in days {
+            let Some(shift_type) = Self::shift_type(shift_types, grammar, &mut plan, day) else {
+                continue;
+            };
+
+            let current = existing
+                .iter()
+                .find(|s| s.user_id == user_id && s.date == day.date)
+                .and_then(|s| s.shift_type_id)
+                .and_then(|id| shift_types.iter().find(|st| st.id == id));
+
+            if current.is_some_and(|c| c.id == shift_type.id) {
+                plan.unchanged += 1;
+                continue;
+            }
+
+            let conflict = |kind| ImportConflict {
+                date: day.date,
+                cell: day.cell.clone(),
+                existing_code: current.map(|c| c.code.clone()),
+                imported_code: shift_type.code.clone(),
+                kind,
+            };
+
+            if periods
+                .iter()
+                .any(|p| p.is_closed() && p.contains_date(day.date))
+            {
+                plan.conflicts.push(conflict(ConflictKind::ClosedPeriod));
+                continue;
+            }
+
+            if current.is_some() {
+                plan.conflicts.push(conflict(ConflictKind::Different));
+                if !overwrite {
+                    continue;
+                }
+            }
+
+            plan.entries.push(CreateSchedule {
+                user_id,
+                shift_type_id: Some(shift_type.id),
+                date: day.date,
+                notes: None,
+            });
+        }
+
+        plan
+    }
+
+    /// Map a code to an active shift type, recording unknown codes
+    fn shift_type<'a>(
+        shift_types: &'a [ShiftType],
+        grammar: &ShiftCodeGrammar,
+        plan: &mut ImportPlan,
+        day: &ImportedDay,
+    ) -> Option<&'a ShiftType> {
+        let code = day.code.trim().to_uppercase();
+        let shift_type = shift_types
+            .iter()
+            .find(|st| st.is_active && st.code == code);
+
+        if shift_type.is_none() {
+            let deactivated = shift_types.iter().any(|st| st.code == code);
+            let reason = if deactivated || grammar.is_known(&code) {
+                UnknownCodeReason::NotConfigured
+            } else {
+                UnknownCodeReason::Invalid
+            };
+            match plan.unknown_codes.iter_mut().find(|u| u.code == code) {
+                Some(unknown) => unknown.cells.push(day.cell.clone()),
+                None => plan.unknown_codes.push(UnknownCode {
+                    code,
+                    reason,
+                    cells: vec![day.cell.clone()],
+                }),
+            }
+        }
+
+        shift_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use chrono::Utc;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 0.0),
+            shift_type("RH", ShiftCategory::Rest, 8.0, 0.0),
+        ]
+    }
+
+    fn day(day: u32, code: &str) -> ImportedDay {
+        ImportedDay {
+            date: date(day),
+            code: code.to_string(),
+            cell: format!("Mars!{}", day),
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn schedule(user_id: Uuid, day: u32, shift_type: &ShiftType) -> Schedule {
+        fixtures::schedule(user_id, date(day), shift_type)
+    }
+
+    fn closed_period() -> Period {
+        Period {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            year: 2026,
+            number: 3,
+            start_date: date(1),
+            end_date: date(10),
+            hour_quota: 160,
+            closed_at: Some(Utc::now()),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn grammar() -> ShiftCodeGrammar {
+        ShiftCodeGrammar::default()
+    }
+
+    #[test]
+    fn test_maps_codes_to_shift_types() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+
+        let plan = ImportPlanner::plan(
+            user,
+            &[day(2, "101"), day(3, "rh")],
+            &types,
+            &grammar(),
+            &[],
+            &[],
+            false,
+        );
+
+        assert_eq!(plan.entries.len(), 2);
+        assert_eq!(plan.entries[0].shift_type_id, Some(types[0].id));
+        assert_eq!(plan.entries[1].shift_type_id, Some(types[1].id));
+        assert!(plan.unknown_codes.is_empty());
+    }
+
+    #[test]
+    fn test_reports_unknown_codes() {
+        let plan = ImportPlanner::plan(
+            Uuid::new_v4(),
+            &[day(2, "XYZ"), day(3, "XYZ"), day(4, "CV")],
+            &shift_types(),
+            &grammar(),
+            &[],
+            &[],
+            false,
+        );
+
+        assert!(plan.entries.is_empty());
+        assert_eq!(plan.unknown_codes.len(), 2);
+        assert_eq!(plan.unknown_codes[0].reason, UnknownCodeReason::Invalid);
+        assert_eq!(plan.unknown_codes[0].cells.len(), 2);
+        assert_eq!(
+            plan.unknown_codes[1].reason,
+            UnknownCodeReason::NotConfigured
+        );
+    }
+
+    #[test]
+    fn test_codes_follow_the_grammar() {
+        let mut types = shift_types();
+        types.push(shift_type("N1", ShiftCategory::Night, 8.0, 0.0));
+        let grammar = ShiftCodeGrammar {
+            standalone: Vec::new(),
+            ..grammar()
+        };
+
+        let plan = ImportPlanner::plan(
+            Uuid::new_v4(),
+            &[day(2, "n1"), day(3, "CV"), day(4, "7101")],
+            &types,
+            &grammar,
+            &[],
+            &[],
+            false,
+        );
+
+        // Custom codes of the organization map even outside the grammar
+        assert_eq!(plan.entries.len(), 1);
+        assert_eq!(plan.entries[0].shift_type_id, Some(types[2].id));
+        assert_eq!(plan.unknown_codes[0].reason, UnknownCodeReason::Invalid);
+        assert_eq!(
+            plan.unknown_codes[1].reason,
+            UnknownCodeReason::NotConfigured
+        );
+    }
+
+    #[test]
+    fn test_conflicts_with_existing_rows() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let existing = vec![schedule(user, 2, &types[0]), schedule(user, 3, &types[0])];
+        let days = [day(2, "101"), day(3, "RH")];
+
+        let plan = ImportPlanner::plan(user, &days, &types, &grammar(), &existing, &[], false);
+
+        assert_eq!(plan.unchanged, 1);
+        assert!(plan.entries.is_empty());
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].kind, ConflictKind::Different);
+        assert_eq!(plan.conflicts[0].existing_code.as_deref(), Some("101"));
+
+        let plan = ImportPlanner::plan(user, &days, &types, &grammar(), &existing, &[], true);
+
+        assert_eq!(plan.entries.len(), 1);
+        assert_eq!(plan.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_skips_closed_periods() {
+        let plan = ImportPlanner::plan(
+            Uuid::new_v4(),
+            &[day(2, "101"), day(12, "101")],
+            &shift_types(),
+            &grammar(),
+            &[],
+            &[closed_period()],
+            true,
+        );
+
+        assert_eq!(plan.entries.len(), 1);
+        assert_eq!(plan.entries[0].date, date(12));
+        assert_eq!(plan.conflicts[0].kind, ConflictKind::ClosedPeriod);
+    }
+}
@@ -2,10 +2,30 @@
 //!
 //! Pure business logic services without external dependencies.
 
+pub mod balance_calculator;
+pub mod dashboard_builder;
+pub mod day_type_validator;
+pub mod fairness_analyzer;
+pub mod fix_suggester;
 pub mod holiday_calculator;
+pub mod hour_bank_calculator;
+pub mod import_planner;
+pub mod monthly_stats;
+pub mod payroll_calculator;
 pub mod period_calculator;
 pub mod quota_validator;
+pub mod shift_type_history;
 
+pub use balance_calculator::BalanceCalculator;
+pub use dashboard_builder::DashboardBuilder;
+pub use day_type_validator::DayTypeValidator;
+pub use fairness_analyzer::FairnessAnalyzer;
+pub use fix_suggester::FixSuggester;
 pub use holiday_calculator::HolidayCalculator;
+pub use hour_bank_calculator::HourBankCalculator;
+pub use import_planner::ImportPlanner;
+pub use monthly_stats::MonthlyStats;
+pub use payroll_calculator::PayrollCalculator;
 pub use period_calculator::PeriodCalculator;
 pub use quota_validator::QuotaValidator;
+pub use shift_type_history::ShiftTypeHistory;
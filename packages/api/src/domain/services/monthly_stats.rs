@@ -0,0 +1,309 @@
+//! Monthly Statistics Service
+//!
+//! Per calendar month counts ("Stats Mensuelles" sheets of generate.py):
+//! - Count, hours and night hours per shift code for each agent
+//! - Split of a period across the months it covers, so that the sum of
+//!   the monthly slices always equals the period figures
+//! - Hours of each day taken from the shift type definition in force
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::{Period, Schedule, ShiftType};
+use crate::domain::services::shift_type_history::ShiftTypeHistory;
+
+/// Count of one shift code
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodeStats {
+    pub code: String,
+    pub count: i32,
+    pub hours: f64,
+    pub night_hours: f64,
+}
+
+/// Counts of one agent over a date range
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStats {
+    pub user_id: Uuid,
+    /// Codes with at least one day, in shift type order
+    pub codes: Vec<CodeStats>,
+    pub total_hours: f64,
+    pub night_hours: f64,
+}
+
+/// Statistics of a calendar month (or the part of it inside a period)
+#[derive(Debug, Clone, Serialize)]
+pub struct MonthStats {
+    pub year: i32,
+    pub month: u32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub agents: Vec<AgentStats>,
+}
+
+/// Period figures split by calendar month
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodMonthSplit {
+    pub period_id: Uuid,
+    pub period_number: i32,
+    pub is_cross_month: bool,
+    /// One slice per month covered by the period
+    pub months: Vec<MonthStats>,
+    /// Whole period (equals the sum of the slices)
+    pub total: Vec<AgentStats>,
+}
+
+/// Monthly statistics service
+pub struct MonthlyStats;
+
+impl MonthlyStats {
+    /// Statistics of a calendar month
+    pub fn month(
+        year: i32,
+        month: u32,
+        agents: &[Uuid],
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+    ) -> MonthStats {
+        let start = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+        let end = Self::month_end(start);
+
+        MonthStats {
+            year,
+            month,
+            start_date: start,
+            end_date: end,
+            agents: Self::aggregate(start, end, agents, schedules, shift_types, history),
+        }
+    }
+
+    /// Split a period across the calendar months it covers
+    pub fn split_period(
+        period: &Period,
+        agents: &[Uuid],
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+    ) -> PeriodMonthSplit {
+        let mut months = Vec::new();
+        let mut start = period.start_date;
+
+        while start <= period.end_date {
+            let end = Self::month_end(start).min(period.end_date);
+            months.push(MonthStats {
+                year: start.year(),
+                month: start.month(),
+                start_date: start,
+                end_date: end,
+                agents: Self::aggregate(start, end, agents, schedules, shift_types, history),
+            });
+            start = end + Duration::days(1);
+        }
+
+        PeriodMonthSplit {
+            period_id: period.id,
+            period_number: period.number,
+            is_cross_month: period.is_cross_month(),
+            months,
+            total: Self::aggregate(
+                period.start_date,
+                period.end_date,
+                agents,
+                schedules,
+                shift_types,
+                history,
+            ),
+        }
+    }
+
+    /// Aggregate the schedules of each agent between two dates (inclusive)
+    pub fn aggregate(
+        start: NaiveDate,
+        end: NaiveDate,
+        agents: &[Uuid],
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+    ) -> Vec<AgentStats> {
+        agents
+            .iter()
+            .map(|user_id| {
+                let mut stats = AgentStats {
+                    user_id: *user_id,
+                    codes: Vec::new(),
+                    total_hours: 0.0,
+                    night_hours: 0.0,
+                };
+
+                for shift_type in shift_types {
+                    let (mut count, mut hours, mut night_hours) = (0, 0.0, 0.0);
+                    let days = schedules
+                        .iter()
+                        .filter(|s| s.user_id == *user_id && s.date >= start && s.date <= end)
+                        .filter(|s| s.shift_type_id == Some(shift_type.id));
+                    for schedule in days {
+                        let definition = history.definition_on(shift_type, schedule.date);
+                        count += 1;
+                        if definition.is_countable {
+                            hours += definition.duration_hours;
+                        }
+                        night_hours += definition.night_hours;
+                    }
+                    if count == 0 {
+                        continue;
+                    }
+
+                    stats.total_hours += hours;
+                    stats.night_hours += night_hours;
+                    stats.codes.push(CodeStats {
+                        code: shift_type.code.clone(),
+                        count,
+                        hours,
+                        night_hours,
+                    });
+                }
+
+                stats
+            })
+            .collect()
+    }
+
+    /// Last day of the month of a date
+    pub fn month_end(date: NaiveDate) -> NaiveDate {
+        let (year, month) = if date.month() == 12 {
+            (date.year() + 1, 1)
+        } else {
+            (date.year(), date.month() + 1)
+        };
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap() - Duration::days(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{self, period, shift_type};
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::ShiftTypeVersion;
+
+    fn shift_types() -> Vec<ShiftType> {
+        vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            shift_type("121", ShiftCategory::Night, 8.0, 8.0),
+            shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+        ]
+    }
+
+    /// One schedule per day from the first day of the period, cycling through `codes`
+    fn schedules(user_id: Uuid, days: i64, codes: &[&str], types: &[ShiftType]) -> Vec<Schedule> {
+        (0..days)
+            .map(|i| {
+                let code = codes[i as usize % codes.len()];
+                let shift_type = types.iter().find(|t| t.code == code).unwrap();
+                fixtures::schedule(user_id, period().start_date + Duration::days(i), shift_type)
+            })
+            .collect()
+    }
+
+    fn history() -> ShiftTypeHistory {
+        ShiftTypeHistory::default()
+    }
+
+    fn count(stats: &AgentStats, code: &str) -> i32 {
+        stats
+            .codes
+            .iter()
+            .find(|c| c.code == code)
+            .map(|c| c.count)
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_month() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules(user, 28, &["101", "121", "RH"], &types);
+
+        let stats = MonthlyStats::month(2026, 1, &[user], &rows, &types, &history());
+
+        // 12-31 January = 20 days
+        assert_eq!(
+            stats.end_date,
+            NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()
+        );
+        assert_eq!(count(&stats.agents[0], "101"), 7);
+        assert_eq!(count(&stats.agents[0], "121"), 7);
+        assert_eq!(count(&stats.agents[0], "RH"), 6);
+        assert_eq!(stats.agents[0].total_hours, 112.0);
+        assert_eq!(stats.agents[0].night_hours, 70.0);
+    }
+
+    #[test]
+    fn test_split_cross_month_period() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules(user, 28, &["101", "121", "RH"], &types);
+
+        let split = MonthlyStats::split_period(&period(), &[user], &rows, &types, &history());
+
+        assert!(split.is_cross_month);
+        assert_eq!(split.months.len(), 2);
+        assert_eq!((split.months[0].year, split.months[0].month), (2026, 1));
+        assert_eq!(
+            split.months[1].start_date,
+            NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()
+        );
+        assert_eq!(split.months[1].end_date, period().end_date);
+    }
+
+    #[test]
+    fn test_split_reconciles_with_period() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules(user, 28, &["101", "121", "RH"], &types);
+
+        let split = MonthlyStats::split_period(&period(), &[user], &rows, &types, &history());
+        let hours: f64 = split.months.iter().map(|m| m.agents[0].total_hours).sum();
+        let nights: i32 = split
+            .months
+            .iter()
+            .map(|m| count(&m.agents[0], "121"))
+            .sum();
+
+        assert_eq!(hours, split.total[0].total_hours);
+        assert_eq!(nights, count(&split.total[0], "121"));
+    }
+
+    #[test]
+    fn test_december_month_end() {
+        let stats = MonthlyStats::month(2026, 12, &[], &[], &[], &history());
+
+        assert_eq!(
+            stats.end_date,
+            NaiveDate::from_ymd_opt(2026, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_month_uses_definition_in_force() {
+        let types = shift_types();
+        let user = Uuid::new_v4();
+        let rows = schedules(user, 28, &["101"], &types);
+        let history = ShiftTypeHistory::new(vec![
+            ShiftTypeVersion {
+                duration_hours: 7.0,
+                ..ShiftTypeVersion::of(&types[0], period().start_date)
+            },
+            ShiftTypeVersion::of(&types[0], NaiveDate::from_ymd_opt(2026, 2, 1).unwrap()),
+        ]);
+
+        let split = MonthlyStats::split_period(&period(), &[user], &rows, &types, &history);
+
+        assert_eq!(split.months[0].agents[0].total_hours, 140.0);
+        assert_eq!(split.months[1].agents[0].total_hours, 64.0);
+        assert_eq!(split.total[0].total_hours, 204.0);
+        assert_eq!(count(&split.total[0], "101"), 28);
+    }
+}
@@ -0,0 +1,238 @@
+//! Payroll Calculator Service
+//!
+//! Per-agent payroll totals of a period and their export lines:
+//! - Worked and night hours (same rules as the period balance)
+//! - Holiday work days (flagged codes and holiday variants of the
+//!   organization's code grammar) and Sunday work days
+//! - AG strike days and leave days taken
+//! - Days of the shift codes mapped to their own wage code
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Weekday};
+use uuid::Uuid;
+
+use crate::domain::entities::payroll::{PayrollItem, PayrollLine, PayrollMapping, PayrollTotals};
+use crate::domain::entities::shift_type::ShiftCategory;
+use crate::domain::entities::{Period, Schedule, ShiftCodeGrammar, ShiftType};
+use crate::domain::services::shift_type_history::ShiftTypeHistory;
+
+/// Strike shift code
+const STRIKE_CODE: &str = "AG";
+
+/// Payroll calculator service
+pub struct PayrollCalculator;
+
+impl PayrollCalculator {
+    /// Totals of an agent for a period
+    ///
+    /// Only `schedules` of `user_id` dated within the period are counted,
+    /// with the shift type definition in force on their date.
+    pub fn totals(
+        period: &Period,
+        user_id: Uuid,
+        schedules: &[Schedule],
+        shift_types: &[ShiftType],
+        history: &ShiftTypeHistory,
+        grammar: &ShiftCodeGrammar,
+    ) -> PayrollTotals {
+        let mut totals = PayrollTotals {
+            period_id: period.id,
+            user_id,
+            worked_hours: 0.0,
+            night_hours: 0.0,
+            holiday_work_days: 0,
+            sunday_work_days: 0,
+            strike_days: 0,
+            leave_days: 0,
+            codes: BTreeMap::new(),
+        };
+
+        let entries = schedules
+            .iter()
+            .filter(|s| s.user_id == user_id && period.contains_date(s.date));
+
+        for schedule in entries {
+            let Some(shift_type) = schedule
+                .shift_type_id
+                .and_then(|id| shift_types.iter().find(|st| st.id == id))
+            else {
+                continue;
+            };
+            let shift_type = history.definition_on(shift_type, schedule.date);
+
+            if shift_type.is_countable {
+                totals.worked_hours += shift_type.duration_hours;
+            }
+            totals.night_hours += shift_type.night_hours;
+            *totals.codes.entry(shift_type.code.clone()).or_insert(0) += 1;
+
+            if shift_type.code == STRIKE_CODE {
+                totals.strike_days += 1;
+            } else if shift_type.category == ShiftCategory::Leave {
+                totals.leave_days += 1;
+            } else if shift_type.is_worked() {
+                if shift_type.is_holiday_work(grammar) {
+                    totals.holiday_work_days += 1;
+                }
+                if schedule.date.weekday() == Weekday::Sun {
+                    totals.sunday_work_days += 1;
+                }
+            }
+        }
+
+        totals
+    }
+
+    /// Export lines of an agent's totals
+    ///
+    /// Items without a wage code and zero quantities are left out.
+    pub fn lines(
+        period: &Period,
+        totals: &PayrollTotals,
+        matricule: Option<&str>,
+        agent_name: &str,
+        mapping: &PayrollMapping,
+    ) -> Vec<PayrollLine> {
+        let line = |wage_code: &str, label: &str, quantity: f64| PayrollLine {
+            user_id: totals.user_id,
+            matricule: matricule.map(str::to_string),
+            agent_name: agent_name.to_string(),
+            year: period.year,
+            period_number: period.number,
+            start_date: period.start_date,
+            end_date: period.end_date,
+            wage_code: wage_code.to_string(),
+            label: label.to_string(),
+            quantity,
+        };
+
+        let items = PayrollItem::ALL.iter().map(|item| {
+            (
+                mapping.wage_code(*item),
+                item.label().to_string(),
+                totals.quantity(*item),
+            )
+        });
+        let codes = mapping.codes.iter().map(|(code, wage_code)| {
+            let days = totals.codes.get(code).copied().unwrap_or(0);
+            (wage_code.as_str(), format!("Jours {}", code), days as f64)
+        });
+
+        items
+            .chain(codes)
+            .filter(|(wage_code, _, quantity)| !wage_code.is_empty() && *quantity != 0.0)
+            .map(|(wage_code, label, quantity)| line(wage_code, &label, quantity))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::{period, schedule, shift_type};
+    use chrono::{NaiveDate, Utc};
+
+    fn fixture() -> (Period, Uuid, Vec<ShiftType>, Vec<Schedule>) {
+        let types = vec![
+            shift_type("101", ShiftCategory::Standard, 8.0, 2.0),
+            // A holiday by its code prefix only
+            ShiftType {
+                is_holiday_indicator: false,
+                ..shift_type("7121", ShiftCategory::Night, 8.0, 8.0)
+            },
+            shift_type("AG", ShiftCategory::Special, 0.0, 0.0),
+            shift_type("CN", ShiftCategory::Leave, 0.0, 0.0),
+            shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+        ];
+        let user = Uuid::new_v4();
+        let date = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        let schedules = vec![
+            // Monday
+            schedule(user, date(12), &types[0]),
+            // Sunday
+            schedule(user, date(18), &types[0]),
+            // Sunday, holiday code
+            schedule(user, date(25), &types[1]),
+            schedule(user, date(13), &types[2]),
+            schedule(user, date(14), &types[3]),
+            schedule(user, date(15), &types[3]),
+            schedule(user, date(16), &types[4]),
+            // Outside the period
+            schedule(user, date(5), &types[0]),
+        ];
+        let period = Period {
+            closed_at: Some(Utc::now()),
+            ..period()
+        };
+        (period, user, types, schedules)
+    }
+
+    #[test]
+    fn test_totals() {
+        let (period, user, types, schedules) = fixture();
+
+        let totals = PayrollCalculator::totals(
+            &period,
+            user,
+            &schedules,
+            &types,
+            &ShiftTypeHistory::default(),
+            &ShiftCodeGrammar::default(),
+        );
+
+        assert_eq!(totals.worked_hours, 24.0);
+        assert_eq!(totals.night_hours, 12.0);
+        assert_eq!(totals.holiday_work_days, 1);
+        assert_eq!(totals.sunday_work_days, 2);
+        assert_eq!(totals.strike_days, 1);
+        assert_eq!(totals.leave_days, 2);
+        assert_eq!(totals.codes.get("101"), Some(&2));
+
+        // 7 is not a holiday prefix in this grammar
+        let grammar = ShiftCodeGrammar {
+            prefixes: Vec::new(),
+            ..ShiftCodeGrammar::default()
+        };
+        let totals = PayrollCalculator::totals(
+            &period,
+            user,
+            &schedules,
+            &types,
+            &ShiftTypeHistory::default(),
+            &grammar,
+        );
+        assert_eq!(totals.holiday_work_days, 0);
+    }
+
+    #[test]
+    fn test_lines_follow_mapping() {
+        let (period, user, types, schedules) = fixture();
+        let totals = PayrollCalculator::totals(
+            &period,
+            user,
+            &schedules,
+            &types,
+            &ShiftTypeHistory::default(),
+            &ShiftCodeGrammar::default(),
+        );
+        let mut mapping = PayrollMapping {
+            night_hours: String::new(),
+            ..PayrollMapping::default()
+        };
+        mapping.codes.insert("CN".to_string(), "3000".to_string());
+        mapping.codes.insert("CV".to_string(), "3200".to_string());
+
+        let lines =
+            PayrollCalculator::lines(&period, &totals, Some("M001"), "Dupont Marie", &mapping);
+        let codes: Vec<&str> = lines.iter().map(|l| l.wage_code.as_str()).collect();
+
+        // Night hours disabled, CV never taken
+        assert_eq!(
+            codes,
+            vec!["HTRAV", "JFER", "JDIM", "GREVE", "CONGE", "3000"]
+        );
+        assert_eq!(lines[5].quantity, 2.0);
+        assert_eq!(lines[0].matricule.as_deref(), Some("M001"));
+    }
+}
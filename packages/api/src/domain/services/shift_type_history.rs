@@ -0,0 +1,123 @@
+//! Shift Type History Service
+//!
+//! Resolves the definition of a shift type in force on a date from its
+//! effective-dated versions:
+//! - The latest version starting on or before the date applies
+//! - Dates before the first version use the first version
+//! - Shift types without versions use their current attributes
+
+use std::borrow::Cow;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::domain::entities::{ShiftType, ShiftTypeVersion};
+
+/// Versions of the shift types of an organization
+#[derive(Debug, Clone, Default)]
+pub struct ShiftTypeHistory {
+    /// Sorted by shift type, then effective date
+    versions: Vec<ShiftTypeVersion>,
+}
+
+impl ShiftTypeHistory {
+    /// Build from versions in any order
+    pub fn new(mut versions: Vec<ShiftTypeVersion>) -> Self {
+        versions.sort_by_key(|v| (v.shift_type_id, v.effective_from));
+        Self { versions }
+    }
+
+    /// Versions of a shift type, oldest first
+    pub fn versions_of(&self, shift_type_id: Uuid) -> &[ShiftTypeVersion] {
+        let start = self
+            .versions
+            .partition_point(|v| v.shift_type_id < shift_type_id);
+        let end = self
+            .versions
+            .partition_point(|v| v.shift_type_id <= shift_type_id);
+        &self.versions[start..end]
+    }
+
+    /// Version of a shift type in force on a date
+    pub fn version_on(&self, shift_type_id: Uuid, date: NaiveDate) -> Option<&ShiftTypeVersion> {
+        let versions = self.versions_of(shift_type_id);
+        versions
+            .iter()
+            .rev()
+            .find(|v| v.effective_from <= date)
+            .or_else(|| versions.first())
+    }
+
+    /// Shift type with the attributes in force on a date
+    pub fn definition_on<'a>(
+        &self,
+        shift_type: &'a ShiftType,
+        date: NaiveDate,
+    ) -> Cow<'a, ShiftType> {
+        match self.version_on(shift_type.id, date) {
+            Some(version) if !version.matches(shift_type) => Cow::Owned(version.apply(shift_type)),
+            _ => Cow::Borrowed(shift_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures::shift_type;
+    use crate::domain::entities::shift_type::ShiftCategory;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn version(shift_type: &ShiftType, hours: f64, from: NaiveDate) -> ShiftTypeVersion {
+        ShiftTypeVersion {
+            duration_hours: hours,
+            ..ShiftTypeVersion::of(shift_type, from)
+        }
+    }
+
+    #[test]
+    fn test_version_on_date() {
+        let current = shift_type("111", ShiftCategory::Intermediate, 9.0, 2.0);
+        let history = ShiftTypeHistory::new(vec![
+            version(&current, 9.0, date(6, 1)),
+            version(&current, 8.5, date(1, 1)),
+            version(
+                &shift_type("111", ShiftCategory::Intermediate, 7.0, 2.0),
+                7.0,
+                date(3, 1),
+            ),
+        ]);
+
+        assert_eq!(history.versions_of(current.id).len(), 2);
+        assert_eq!(
+            history.definition_on(&current, date(5, 31)).duration_hours,
+            8.5
+        );
+        assert_eq!(
+            history.definition_on(&current, date(6, 1)).duration_hours,
+            9.0
+        );
+        // Before the first version
+        assert_eq!(
+            history
+                .definition_on(&current, date(1, 1) - chrono::Duration::days(1))
+                .duration_hours,
+            8.5
+        );
+    }
+
+    #[test]
+    fn test_unversioned_type_keeps_its_attributes() {
+        let current = shift_type("111", ShiftCategory::Intermediate, 9.0, 2.0);
+        let history = ShiftTypeHistory::default();
+
+        assert!(history.version_on(current.id, date(1, 1)).is_none());
+        assert!(matches!(
+            history.definition_on(&current, date(1, 1)),
+            Cow::Borrowed(_)
+        ));
+    }
+}
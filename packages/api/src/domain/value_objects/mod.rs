@@ -4,8 +4,10 @@
 
 pub mod color;
 pub mod night_hours;
+pub mod permission;
 pub mod shift_code;
 
 pub use color::Color;
 pub use night_hours::NightHoursCategory;
+pub use permission::{Permission, PermissionScope, Permissions};
 pub use shift_code::ShiftCode;
@@ -4,25 +4,29 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::entities::ShiftCodeGrammar;
+
 /// Night hours category based on shift code
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NightHoursCategory {
-    /// Full night shift: 8 hours (121, 6121, 7121)
+    /// Full night shift: 8 hours or more (121, 6121, 7121)
     Full,
-    /// Partial night: 2 hours (standard/intermediate codes)
+    /// Partial night: less than 8 hours (standard/intermediate codes)
     Partial,
     /// No night hours (rest, special codes)
     None,
 }
 
 impl NightHoursCategory {
-    /// Create from shift code
-    pub fn from_code(code: &str) -> Self {
-        match code {
-            "121" | "6121" | "7121" => Self::Full,
-            "101" | "102" | "6101" | "6102" | "7101" | "7102" | "111" | "112" | "6111" | "6112"
-            | "7111" | "7112" | "X_AM" | "X_PM" => Self::Partial,
-            _ => Self::None,
+    /// Create from the night credit of a code in an organization's grammar
+    pub fn from_code(code: &str, grammar: &ShiftCodeGrammar) -> Self {
+        let hours = grammar.night_hours(code);
+        if hours >= Self::Full.hours() {
+            Self::Full
+        } else if hours > 0.0 {
+            Self::Partial
+        } else {
+            Self::None
         }
     }
 
@@ -53,13 +57,13 @@ mod tests {
 
     #[test]
     fn test_categories() {
-        assert_eq!(NightHoursCategory::from_code("121"), NightHoursCategory::Full);
-        assert_eq!(
-            NightHoursCategory::from_code("101"),
-            NightHoursCategory::Partial
-        );
-        assert_eq!(NightHoursCategory::from_code("AG"), NightHoursCategory::None);
-        assert_eq!(NightHoursCategory::from_code("RH"), NightHoursCategory::None);
+        let grammar = ShiftCodeGrammar::default();
+        let category = |code| NightHoursCategory::from_code(code, &grammar);
+
+        assert_eq!(category("121"), NightHoursCategory::Full);
+        assert_eq!(category("101"), NightHoursCategory::Partial);
+        assert_eq!(category("AG"), NightHoursCategory::None);
+        assert_eq!(category("RH"), NightHoursCategory::None);
     }
 
     #[test]
@@ -0,0 +1,317 @@
+//! Permission Value Object
+//!
+//! Catalogue of the permissions a role can grant, stored in
+//! `roles.permissions` as `resource:action` strings. Some read permissions
+//! can be granted on the holder's own rows only, with an `:own` suffix
+//! (`schedules:read:own`).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// Permission of the catalogue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    UsersRead,
+    UsersManage,
+    RolesRead,
+    RolesManage,
+    SchedulesRead,
+    SchedulesWrite,
+    ShiftTypesRead,
+    ShiftTypesManage,
+    PeriodsRead,
+    PeriodsWrite,
+    PeriodsLock,
+    BalancesRead,
+    HolidaysRead,
+    HolidaysManage,
+    StatisticsRead,
+    CalendarManage,
+    PayrollManage,
+}
+
+impl Permission {
+    /// Every permission, in catalogue order
+    pub const ALL: [Permission; 17] = [
+        Permission::UsersRead,
+        Permission::UsersManage,
+        Permission::RolesRead,
+        Permission::RolesManage,
+        Permission::SchedulesRead,
+        Permission::SchedulesWrite,
+        Permission::ShiftTypesRead,
+        Permission::ShiftTypesManage,
+        Permission::PeriodsRead,
+        Permission::PeriodsWrite,
+        Permission::PeriodsLock,
+        Permission::BalancesRead,
+        Permission::HolidaysRead,
+        Permission::HolidaysManage,
+        Permission::StatisticsRead,
+        Permission::CalendarManage,
+        Permission::PayrollManage,
+    ];
+
+    /// Stored name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::UsersRead => "users:read",
+            Permission::UsersManage => "users:manage",
+            Permission::RolesRead => "roles:read",
+            Permission::RolesManage => "roles:manage",
+            Permission::SchedulesRead => "schedules:read",
+            Permission::SchedulesWrite => "schedules:write",
+            Permission::ShiftTypesRead => "shift_types:read",
+            Permission::ShiftTypesManage => "shift_types:manage",
+            Permission::PeriodsRead => "periods:read",
+            Permission::PeriodsWrite => "periods:write",
+            Permission::PeriodsLock => "periods:lock",
+            Permission::BalancesRead => "balances:read",
+            Permission::HolidaysRead => "holidays:read",
+            Permission::HolidaysManage => "holidays:manage",
+            Permission::StatisticsRead => "statistics:read",
+            Permission::CalendarManage => "calendar:manage",
+            Permission::PayrollManage => "payroll:manage",
+        }
+    }
+
+    /// What the permission allows
+    pub fn description(&self) -> &'static str {
+        match self {
+            Permission::UsersRead => "List and view users",
+            Permission::UsersManage => "Create, update and delete users",
+            Permission::RolesRead => "List roles and the permission catalogue",
+            Permission::RolesManage => "Create, update and delete custom roles",
+            Permission::SchedulesRead => "View schedules and live planning changes",
+            Permission::SchedulesWrite => "Edit schedules, import plannings and take edit locks",
+            Permission::ShiftTypesRead => "View shift codes and the code grammar",
+            Permission::ShiftTypesManage => "Edit, remap and delete shift codes",
+            Permission::PeriodsRead => "View periods and their editors",
+            Permission::PeriodsWrite => "Generate periods",
+            Permission::PeriodsLock => "Close periods",
+            Permission::BalancesRead => "View hour and leave balances",
+            Permission::HolidaysRead => "View public holidays",
+            Permission::HolidaysManage => "Add, generate and delete holidays",
+            Permission::StatisticsRead => "View statistics",
+            Permission::CalendarManage => "Manage calendar feed tokens",
+            Permission::PayrollManage => "Configure and export payroll",
+        }
+    }
+
+    /// Check if the permission can be granted on the holder's own rows only
+    pub fn has_own_scope(&self) -> bool {
+        matches!(
+            self,
+            Permission::SchedulesRead
+                | Permission::BalancesRead
+                | Permission::StatisticsRead
+                | Permission::CalendarManage
+        )
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = PermissionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|p| p.as_str() == s)
+            .ok_or_else(|| PermissionError::Unknown(s.to_string()))
+    }
+}
+
+/// Rows a granted permission covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionScope {
+    /// The holder's own rows
+    Own,
+    /// Every row of the organization
+    All,
+}
+
+/// Permission granted by a role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grant {
+    pub permission: Permission,
+    pub scope: PermissionScope,
+}
+
+impl Grant {
+    /// Suffix of the grants limited to the holder's own rows
+    pub const OWN_SUFFIX: &'static str = ":own";
+
+    /// Parse `resource:action` or `resource:action:own`
+    pub fn parse(grant: &str) -> Result<Self, PermissionError> {
+        let grant = grant.trim();
+        match grant.strip_suffix(Self::OWN_SUFFIX) {
+            Some(name) => {
+                let permission: Permission = name.parse()?;
+                if !permission.has_own_scope() {
+                    return Err(PermissionError::NoOwnScope(permission));
+                }
+                Ok(Self {
+                    permission,
+                    scope: PermissionScope::Own,
+                })
+            }
+            None => Ok(Self {
+                permission: grant.parse()?,
+                scope: PermissionScope::All,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Grant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scope {
+            PermissionScope::All => write!(f, "{}", self.permission),
+            PermissionScope::Own => write!(f, "{}{}", self.permission, Self::OWN_SUFFIX),
+        }
+    }
+}
+
+/// Invalid permission name
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermissionError {
+    /// Not in the catalogue
+    Unknown(String),
+    /// `:own` suffix on a permission without own scope
+    NoOwnScope(Permission),
+}
+
+impl fmt::Display for PermissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionError::Unknown(name) => write!(f, "Unknown permission: {}", name),
+            PermissionError::NoOwnScope(permission) => {
+                write!(f, "{} cannot be limited to own rows", permission)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PermissionError {}
+
+/// Permissions granted to a user, with their widest scope
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Permissions(HashMap<Permission, PermissionScope>);
+
+impl Permissions {
+    /// Collect grants, keeping the widest scope of each permission
+    pub fn new(grants: impl IntoIterator<Item = Grant>) -> Self {
+        let mut permissions = HashMap::new();
+        for grant in grants {
+            let scope = permissions.entry(grant.permission).or_insert(grant.scope);
+            *scope = (*scope).max(grant.scope);
+        }
+        Self(permissions)
+    }
+
+    /// Permissions from stored grants
+    ///
+    /// Names outside the catalogue are ignored; migration
+    /// 008_role_permissions maps those of earlier releases.
+    pub fn from_stored<S: AsRef<str>>(grants: &[S]) -> Self {
+        Self::new(grants.iter().filter_map(|g| Grant::parse(g.as_ref()).ok()))
+    }
+
+    /// Validate grants submitted for a role, normalized and deduplicated
+    /// in catalogue order
+    pub fn normalize(grants: &[String]) -> Result<Vec<String>, PermissionError> {
+        let grants = grants
+            .iter()
+            .map(|g| Grant::parse(g))
+            .collect::<Result<Vec<_>, _>>()?;
+        let permissions = Self::new(grants);
+
+        Ok(Permission::ALL
+            .into_iter()
+            .filter_map(|permission| {
+                let scope = permissions.scope(permission)?;
+                Some(Grant { permission, scope }.to_string())
+            })
+            .collect())
+    }
+
+    /// Scope of a permission (None when not granted)
+    pub fn scope(&self, permission: Permission) -> Option<PermissionScope> {
+        self.0.get(&permission).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(grants: &[&str]) -> Vec<String> {
+        grants.iter().map(|g| g.to_string()).collect()
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for permission in Permission::ALL {
+            assert_eq!(permission.as_str().parse::<Permission>(), Ok(permission));
+        }
+        assert_eq!(
+            Grant::parse("schedules:read:own").unwrap().to_string(),
+            "schedules:read:own"
+        );
+        assert_eq!(
+            Grant::parse("users:manage:own"),
+            Err(PermissionError::NoOwnScope(Permission::UsersManage))
+        );
+    }
+
+    #[test]
+    fn test_widest_scope_wins() {
+        let permissions = Permissions::from_stored(&strings(&[
+            "schedules:read:own",
+            "schedules:read",
+            "balances:read:own",
+            "leave_requests:read",
+        ]));
+
+        assert_eq!(
+            permissions.scope(Permission::SchedulesRead),
+            Some(PermissionScope::All)
+        );
+        assert_eq!(
+            permissions.scope(Permission::BalancesRead),
+            Some(PermissionScope::Own)
+        );
+        assert_eq!(permissions.scope(Permission::SchedulesWrite), None);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            Permissions::normalize(&strings(&[
+                "schedules:write",
+                " users:read",
+                "schedules:read:own",
+                "users:read",
+            ])),
+            Ok(strings(&[
+                "users:read",
+                "schedules:read:own",
+                "schedules:write"
+            ]))
+        );
+        assert_eq!(
+            Permissions::normalize(&strings(&["audit:read"])),
+            Err(PermissionError::Unknown("audit:read".to_string()))
+        );
+    }
+}
@@ -1,46 +1,32 @@
 //! ShiftCode Value Object
 //!
-//! Validated shift/prestation code.
-//! Based on LISTE_PRESTATIONS and LISTE_REPOS from generate.py
+//! Well-formed shift/prestation code. Which codes exist and what they
+//! mean is defined by the organization's `ShiftCodeGrammar`.
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Known prestation codes (from generate.py LISTE_PRESTATIONS)
-pub const PRESTATIONS: &[&str] = &[
-    "101", "102", "111", "112", "121", "6101", "6102", "6111", "6112", "6121", "7101", "7102",
-    "7111", "7112", "7121", "X_AM", "X_PM", "X_10", "AG",
-];
-
-/// Known repos codes (from generate.py LISTE_REPOS)
-pub const REPOS: &[&str] = &["CN", "JC", "RH", "CH", "RR", "CV", "ZM"];
-
-/// Codes with 8h night credit
-pub const CODES_NUIT_8H: &[&str] = &["121", "6121", "7121"];
-
-/// Codes with 2h night credit
-pub const CODES_NUIT_2H: &[&str] = &[
-    "101", "102", "6101", "6102", "7101", "7102", "111", "112", "6111", "6112", "7111", "7112",
-    "X_AM", "X_PM",
-];
-
-/// Holiday work indicator codes (7xxx prefix)
-pub const CODES_FERIE: &[&str] = &["7101", "7102", "7111", "7112", "7121"];
-
 /// Validated shift code
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ShiftCode(String);
 
 impl ShiftCode {
-    /// Create a new shift code (validates against known codes)
-    pub fn new(code: impl Into<String>) -> Result<Self, ShiftCodeError> {
-        let code = code.into().to_uppercase();
-
-        // Validate against known codes
-        if Self::is_valid_code(&code) {
+    /// Maximum code length (shift_types.code)
+    pub const MAX_LENGTH: usize = 20;
+
+    /// Create an organization code, known or not
+    ///
+    /// Codes are 1 to 20 uppercase letters, digits or underscores.
+    pub fn parse(code: impl Into<String>) -> Result<Self, ShiftCodeError> {
+        let code = code.into().trim().to_uppercase();
+
+        let well_formed = !code.is_empty()
+            && code.len() <= Self::MAX_LENGTH
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if well_formed {
             Ok(Self(code))
         } else {
-            Err(ShiftCodeError::Unknown(code))
+            Err(ShiftCodeError::InvalidFormat(code))
         }
     }
 
@@ -49,52 +35,11 @@ impl ShiftCode {
         Self(code.into().to_uppercase())
     }
 
-    /// Check if a code is in the known lists
-    pub fn is_valid_code(code: &str) -> bool {
-        PRESTATIONS.contains(&code) || REPOS.contains(&code)
-    }
-
-    /// Check if this is a prestation code
-    pub fn is_prestation(&self) -> bool {
-        PRESTATIONS.contains(&self.0.as_str())
-    }
-
-    /// Check if this is a repos code
-    pub fn is_repos(&self) -> bool {
-        REPOS.contains(&self.0.as_str())
-    }
-
-    /// Check if this is a full night code (8h)
-    pub fn is_full_night(&self) -> bool {
-        CODES_NUIT_8H.contains(&self.0.as_str())
-    }
-
-    /// Check if this is a partial night code (2h)
-    pub fn is_partial_night(&self) -> bool {
-        CODES_NUIT_2H.contains(&self.0.as_str())
-    }
-
-    /// Check if this is a holiday work code
-    pub fn is_holiday_code(&self) -> bool {
-        CODES_FERIE.contains(&self.0.as_str())
-    }
-
     /// Check if this is a strike code
     pub fn is_strike(&self) -> bool {
         self.0 == "AG"
     }
 
-    /// Get night hours for this code
-    pub fn night_hours(&self) -> f64 {
-        if self.is_full_night() {
-            8.0
-        } else if self.is_partial_night() {
-            2.0
-        } else {
-            0.0
-        }
-    }
-
     /// Get the inner string value
     pub fn as_str(&self) -> &str {
         &self.0
@@ -121,14 +66,22 @@ impl From<ShiftCode> for String {
 /// Shift code validation error
 #[derive(Debug, Clone, PartialEq)]
 pub enum ShiftCodeError {
-    /// Code is not in the known list
+    /// Code is outside the organization's grammar
     Unknown(String),
+    /// Code is empty, too long or has characters other than A-Z, 0-9 and _
+    InvalidFormat(String),
 }
 
 impl fmt::Display for ShiftCodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ShiftCodeError::Unknown(code) => write!(f, "Unknown shift code: {}", code),
+            ShiftCodeError::InvalidFormat(code) => write!(
+                f,
+                "Invalid shift code: '{}' (1-{} letters, digits or _)",
+                code,
+                ShiftCode::MAX_LENGTH
+            ),
         }
     }
 }
@@ -140,28 +93,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_valid_codes() {
-        assert!(ShiftCode::new("101").is_ok());
-        assert!(ShiftCode::new("CN").is_ok());
-        assert!(ShiftCode::new("121").is_ok());
-    }
-
-    #[test]
-    fn test_invalid_code() {
-        assert!(ShiftCode::new("INVALID").is_err());
-    }
-
-    #[test]
-    fn test_night_hours() {
-        assert_eq!(ShiftCode::new("121").unwrap().night_hours(), 8.0);
-        assert_eq!(ShiftCode::new("101").unwrap().night_hours(), 2.0);
-        assert_eq!(ShiftCode::new("AG").unwrap().night_hours(), 0.0);
-        assert_eq!(ShiftCode::new("RH").unwrap().night_hours(), 0.0);
-    }
-
-    #[test]
-    fn test_holiday_codes() {
-        assert!(ShiftCode::new("7101").unwrap().is_holiday_code());
-        assert!(!ShiftCode::new("101").unwrap().is_holiday_code());
+    fn test_parse_custom_codes() {
+        assert_eq!(ShiftCode::parse(" x_nuit ").unwrap().as_str(), "X_NUIT");
+        assert_eq!(ShiftCode::parse("101").unwrap().as_str(), "101");
+        assert!(ShiftCode::parse("").is_err());
+        assert!(ShiftCode::parse("RH-2").is_err());
+        assert!(ShiftCode::parse("A".repeat(21)).is_err());
     }
 }
@@ -2,7 +2,7 @@
 //!
 //! Create and validate JWT tokens for authentication.
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -22,6 +22,12 @@ pub struct Claims {
     pub iat: i64,
     /// Token type (access or refresh)
     pub typ: TokenType,
+    /// Session (refresh token family) the token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    /// Token ID, unique per issued token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<Uuid>,
 }
 
 /// Token type
@@ -38,6 +44,8 @@ pub struct TokenPair {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_in: i64,
+    /// Expiry of the refresh token
+    pub refresh_expires_at: DateTime<Utc>,
 }
 
 /// JWT service
@@ -73,14 +81,16 @@ impl JwtService {
         }
     }
 
-    /// Create a token pair for a user
+    /// Create a token pair for a user session
     pub fn create_tokens(
         &self,
         user_id: Uuid,
         org_id: Uuid,
         role: &str,
+        session_id: Uuid,
     ) -> Result<TokenPair, JwtError> {
         let now = Utc::now();
+        let refresh_expires_at = now + Duration::seconds(self.refresh_expiry);
 
         // Access token
         let access_claims = Claims {
@@ -90,6 +100,8 @@ impl JwtService {
             exp: (now + Duration::seconds(self.access_expiry)).timestamp(),
             iat: now.timestamp(),
             typ: TokenType::Access,
+            sid: Some(session_id),
+            jti: Some(Uuid::new_v4()),
         };
 
         let access_token = encode(
@@ -103,9 +115,11 @@ impl JwtService {
             sub: user_id,
             org: org_id,
             role: role.to_string(),
-            exp: (now + Duration::seconds(self.refresh_expiry)).timestamp(),
+            exp: refresh_expires_at.timestamp(),
             iat: now.timestamp(),
             typ: TokenType::Refresh,
+            sid: Some(session_id),
+            jti: Some(Uuid::new_v4()),
         };
 
         let refresh_token = encode(
@@ -118,6 +132,7 @@ impl JwtService {
             access_token,
             refresh_token,
             expires_in: self.access_expiry,
+            refresh_expires_at,
         })
     }
 
@@ -165,7 +180,9 @@ mod tests {
         let user_id = Uuid::new_v4();
         let org_id = Uuid::new_v4();
 
-        let tokens = service.create_tokens(user_id, org_id, "admin").unwrap();
+        let tokens = service
+            .create_tokens(user_id, org_id, "admin", Uuid::new_v4())
+            .unwrap();
 
         assert!(!tokens.access_token.is_empty());
         assert!(!tokens.refresh_token.is_empty());
@@ -178,11 +195,35 @@ mod tests {
         let user_id = Uuid::new_v4();
         let org_id = Uuid::new_v4();
 
-        let tokens = service.create_tokens(user_id, org_id, "admin").unwrap();
+        let session_id = Uuid::new_v4();
+
+        let tokens = service
+            .create_tokens(user_id, org_id, "admin", session_id)
+            .unwrap();
         let claims = service.validate_access_token(&tokens.access_token).unwrap();
 
         assert_eq!(claims.sub, user_id);
         assert_eq!(claims.org, org_id);
         assert_eq!(claims.role, "admin");
+        assert_eq!(claims.sid, Some(session_id));
+    }
+
+    #[test]
+    fn test_refresh_tokens_are_unique() {
+        let service = JwtService::new("test-secret", 900, 604800);
+        let (user_id, org_id, session_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        // Issued in the same second, for the same session
+        let first = service
+            .create_tokens(user_id, org_id, "agent", session_id)
+            .unwrap();
+        let second = service
+            .create_tokens(user_id, org_id, "agent", session_id)
+            .unwrap();
+
+        assert_ne!(first.refresh_token, second.refresh_token);
+        let claims = service.validate_refresh_token(&second.refresh_token).unwrap();
+        assert_eq!(claims.sid, Some(session_id));
+        assert_eq!(claims.exp, second.refresh_expires_at.timestamp());
     }
 }
@@ -0,0 +1,281 @@
+//! Planning Events
+//!
+//! In-process broadcast of planning changes to live subscribers (SSE):
+//! - `cells_changed`: schedule cells written, with their new code
+//! - `balances_changed`: recomputed period balances and validation status
+//! - `presence_changed`: editors and edit locks of a period
+//!
+//! Subscribers filter events by organization and date range. Events are
+//! not persisted: a subscriber that falls behind receives `resync` and
+//! reloads the matrix.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::entities::edit_lock::{EditLock, Presence};
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::infrastructure::persistence::{balances, planning_data};
+use crate::infrastructure::AppState;
+
+/// Events kept for slow subscribers before they lag
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Schedule cell after a write
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CellChange {
+    pub user_id: Uuid,
+    pub date: NaiveDate,
+    /// None when the cell was cleared
+    pub shift_type_id: Option<Uuid>,
+    pub code: Option<String>,
+}
+
+/// Recomputed balance of an agent for a period
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceChange {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub balance: PeriodBalance,
+}
+
+/// Planning change pushed to subscribers
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+pub enum PlanningEvent {
+    CellsChanged {
+        organization_id: Uuid,
+        cells: Vec<CellChange>,
+    },
+    BalancesChanged {
+        organization_id: Uuid,
+        balances: Vec<BalanceChange>,
+    },
+    PresenceChanged {
+        organization_id: Uuid,
+        period_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        editors: Vec<Presence>,
+        locks: Vec<EditLock>,
+    },
+}
+
+impl PlanningEvent {
+    /// SSE event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlanningEvent::CellsChanged { .. } => "cells_changed",
+            PlanningEvent::BalancesChanged { .. } => "balances_changed",
+            PlanningEvent::PresenceChanged { .. } => "presence_changed",
+        }
+    }
+
+    /// Part of the event visible to a subscription, if any
+    ///
+    /// Cells are kept when their date is in the range, balances and presence
+    /// when their period overlaps it. Open bounds match every date.
+    pub fn within(
+        &self,
+        org_id: Uuid,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Option<PlanningEvent> {
+        let after = |date: NaiveDate| from.is_none_or(|from| date >= from);
+        let before = |date: NaiveDate| to.is_none_or(|to| date <= to);
+
+        match self {
+            PlanningEvent::CellsChanged {
+                organization_id,
+                cells,
+            } if *organization_id == org_id => {
+                let cells: Vec<_> = cells
+                    .iter()
+                    .filter(|c| after(c.date) && before(c.date))
+                    .cloned()
+                    .collect();
+                (!cells.is_empty()).then_some(PlanningEvent::CellsChanged {
+                    organization_id: org_id,
+                    cells,
+                })
+            }
+            PlanningEvent::BalancesChanged {
+                organization_id,
+                balances,
+            } if *organization_id == org_id => {
+                let balances: Vec<_> = balances
+                    .iter()
+                    .filter(|b| after(b.end_date) && before(b.start_date))
+                    .cloned()
+                    .collect();
+                (!balances.is_empty()).then_some(PlanningEvent::BalancesChanged {
+                    organization_id: org_id,
+                    balances,
+                })
+            }
+            PlanningEvent::PresenceChanged {
+                organization_id,
+                start_date,
+                end_date,
+                ..
+            } if *organization_id == org_id && after(*end_date) && before(*start_date) => {
+                Some(self.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Broadcast hub of planning events
+#[derive(Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<PlanningEvent>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventHub {
+    /// Create a hub without subscribers
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Subscribe to every event published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<PlanningEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish an event (dropped when nobody listens)
+    pub fn publish(&self, event: PlanningEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Recalculate balances after a schedule write and notify subscribers
+///
+/// Failures are logged: the write itself already succeeded.
+pub async fn schedules_written(state: &AppState, org_id: Uuid, entries: &[CreateSchedule]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let shift_types = match planning_data::find_shift_types(&state.db, org_id).await {
+        Ok(shift_types) => shift_types,
+        Err(e) => {
+            tracing::error!("Failed to load shift types for planning events: {:?}", e);
+            Vec::new()
+        }
+    };
+    state.events.publish(PlanningEvent::CellsChanged {
+        organization_id: org_id,
+        cells: entries
+            .iter()
+            .map(|e| CellChange {
+                user_id: e.user_id,
+                date: e.date,
+                shift_type_id: e.shift_type_id,
+                code: e
+                    .shift_type_id
+                    .and_then(|id| shift_types.iter().find(|st| st.id == id))
+                    .map(|st| st.code.clone()),
+            })
+            .collect(),
+    });
+
+    let changes: Vec<_> = entries.iter().map(|e| (e.user_id, e.date)).collect();
+    recalculate_balances(state, org_id, &changes).await;
+}
+
+/// Recalculate the balances of the (user_id, date) pairs and notify subscribers
+///
+/// Failures are logged: the change itself already succeeded.
+pub async fn recalculate_balances(state: &AppState, org_id: Uuid, changes: &[(Uuid, NaiveDate)]) {
+    let recalculated = match balances::recalculate(&state.db, org_id, changes).await {
+        Ok(recalculated) => recalculated,
+        Err(e) => {
+            tracing::error!("Failed to recalculate period balances: {:?}", e);
+            return;
+        }
+    };
+
+    let mut changed = Vec::with_capacity(recalculated.len());
+    for balance in recalculated {
+        match planning_data::find_period(&state.db, org_id, balance.period_id).await {
+            Ok(Some(period)) => changed.push(BalanceChange {
+                start_date: period.start_date,
+                end_date: period.end_date,
+                balance,
+            }),
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to load period for planning events: {:?}", e),
+        }
+    }
+
+    if !changed.is_empty() {
+        state.events.publish(PlanningEvent::BalancesChanged {
+            organization_id: org_id,
+            balances: changed,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(org_id: Uuid, days: &[u32]) -> PlanningEvent {
+        PlanningEvent::CellsChanged {
+            organization_id: org_id,
+            cells: days
+                .iter()
+                .map(|d| CellChange {
+                    user_id: Uuid::nil(),
+                    date: NaiveDate::from_ymd_opt(2026, 3, *d).unwrap(),
+                    shift_type_id: None,
+                    code: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_within_filters_organization_and_range() {
+        let org = Uuid::new_v4();
+        let event = cells(org, &[2, 15, 28]);
+        let date = |d| Some(NaiveDate::from_ymd_opt(2026, 3, d).unwrap());
+
+        assert!(event.within(Uuid::new_v4(), None, None).is_none());
+        assert!(event.within(org, date(16), date(20)).is_none());
+
+        let Some(PlanningEvent::CellsChanged { cells, .. }) = event.within(org, date(10), None)
+        else {
+            panic!("expected cells");
+        };
+        assert_eq!(cells.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hub_broadcasts_to_subscribers() {
+        let hub = EventHub::new();
+        let org = Uuid::new_v4();
+
+        // Publishing without subscribers is a no-op
+        hub.publish(cells(org, &[1]));
+
+        let mut first = hub.subscribe();
+        let mut second = hub.subscribe();
+        hub.publish(cells(org, &[2]));
+
+        assert_eq!(first.recv().await.unwrap().name(), "cells_changed");
+        assert_eq!(second.recv().await.unwrap().name(), "cells_changed");
+        assert!(first.try_recv().is_err());
+    }
+}
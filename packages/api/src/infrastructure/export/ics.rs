@@ -0,0 +1,273 @@
+//! iCalendar Export
+//!
+//! RFC 5545 feeds of schedules:
+//! - One VEVENT per schedule entry, with the shift code and description
+//! - Timed events when the shift type has times, all-day events for rest
+//!   and leave codes (or codes without times)
+//! - Public holidays marked in the summary, description and categories
+//!
+//! Times are floating local times: calendar apps show them as written.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+use crate::domain::entities::{Schedule, ShiftType};
+
+/// Maximum line length in octets (excluding CRLF)
+const MAX_LINE: usize = 75;
+
+/// Calendar event
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub stamp: DateTime<Utc>,
+    pub date: NaiveDate,
+    /// Local start and end times (all-day event when None)
+    pub times: Option<(NaiveTime, NaiveTime)>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub categories: Vec<String>,
+}
+
+impl IcsEvent {
+    /// Event of a schedule entry
+    ///
+    /// `agent` prefixes the summary (team feed). `holiday` is the name of
+    /// the public holiday of the day, if any.
+    pub fn from_schedule(
+        schedule: &Schedule,
+        shift_type: &ShiftType,
+        agent: Option<&str>,
+        holiday: Option<&str>,
+    ) -> Self {
+        let mut summary = match agent {
+            Some(agent) => format!("{} — {}", agent, shift_type.code),
+            None => shift_type.code.clone(),
+        };
+        if let Some(description) = &shift_type.description {
+            summary.push_str(&format!(" - {}", description));
+        }
+
+        let is_holiday = holiday.is_some() || schedule.is_holiday;
+        let mut description = Vec::new();
+        let mut categories = vec![format!("{:?}", shift_type.category)];
+        if is_holiday {
+            summary.push_str(" (férié)");
+            description.push(format!("Jour férié : {}", holiday.unwrap_or("oui")));
+            categories.push("Férié".to_string());
+        }
+        if let Some(notes) = &schedule.notes {
+            description.push(notes.clone());
+        }
+
+        let times = match (shift_type.start_time, shift_type.end_time) {
+            (Some(start), Some(end)) if shift_type.is_worked() => Some((start, end)),
+            _ => None,
+        };
+
+        Self {
+            uid: format!("{}@planningos", schedule.id),
+            stamp: schedule.updated_at,
+            date: schedule.date,
+            times,
+            summary,
+            description: (!description.is_empty()).then(|| description.join("\n")),
+            categories,
+        }
+    }
+}
+
+/// iCalendar feed
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    pub name: String,
+    pub events: Vec<IcsEvent>,
+}
+
+impl IcsCalendar {
+    /// Create an empty calendar
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Render the feed (CRLF line endings, folded lines)
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut line = |content: String| out.push_str(&fold(&content));
+
+        line("BEGIN:VCALENDAR".to_string());
+        line("VERSION:2.0".to_string());
+        line("PRODID:-//PlanningOS//Planning//FR".to_string());
+        line("CALSCALE:GREGORIAN".to_string());
+        line("METHOD:PUBLISH".to_string());
+        line(format!("X-WR-CALNAME:{}", escape(&self.name)));
+
+        for event in &self.events {
+            line("BEGIN:VEVENT".to_string());
+            line(format!("UID:{}", event.uid));
+            line(format!("DTSTAMP:{}", event.stamp.format("%Y%m%dT%H%M%SZ")));
+            match event.times {
+                Some((start, end)) => {
+                    // Shifts ending at or before their start end the next day
+                    let end_date = if end <= start {
+                        event.date + Duration::days(1)
+                    } else {
+                        event.date
+                    };
+                    line(format!(
+                        "DTSTART:{}",
+                        event.date.and_time(start).format("%Y%m%dT%H%M%S")
+                    ));
+                    line(format!(
+                        "DTEND:{}",
+                        end_date.and_time(end).format("%Y%m%dT%H%M%S")
+                    ));
+                    line("TRANSP:OPAQUE".to_string());
+                }
+                None => {
+                    line(format!(
+                        "DTSTART;VALUE=DATE:{}",
+                        event.date.format("%Y%m%d")
+                    ));
+                    line(format!(
+                        "DTEND;VALUE=DATE:{}",
+                        (event.date + Duration::days(1)).format("%Y%m%d")
+                    ));
+                    line("TRANSP:TRANSPARENT".to_string());
+                }
+            }
+            line(format!("SUMMARY:{}", escape(&event.summary)));
+            if let Some(description) = &event.description {
+                line(format!("DESCRIPTION:{}", escape(description)));
+            }
+            if !event.categories.is_empty() {
+                let categories: Vec<String> = event.categories.iter().map(|c| escape(c)).collect();
+                line(format!("CATEGORIES:{}", categories.join(",")));
+            }
+            line("END:VEVENT".to_string());
+        }
+
+        line("END:VCALENDAR".to_string());
+        out
+    }
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, without splitting a character
+fn fold(content: &str) -> String {
+    let mut out = String::with_capacity(content.len() + 4);
+    let mut length = 0;
+
+    for c in content.chars() {
+        // Continuation lines start with a space, which counts in the limit
+        if length + c.len_utf8() > MAX_LINE {
+            out.push_str("\r\n ");
+            length = 1;
+        }
+        out.push(c);
+        length += c.len_utf8();
+    }
+
+    out.push_str("\r\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use uuid::Uuid;
+
+    fn timed(code: &str, category: ShiftCategory, times: Option<(u32, u32)>) -> ShiftType {
+        ShiftType {
+            description: Some(format!("Code {}", code)),
+            start_time: times.map(|t| NaiveTime::from_hms_opt(t.0, 0, 0).unwrap()),
+            end_time: times.map(|t| NaiveTime::from_hms_opt(t.1, 0, 0).unwrap()),
+            ..fixtures::shift_type(code, category, 8.0, 0.0)
+        }
+    }
+
+    fn schedule(shift_type: &ShiftType) -> Schedule {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        Schedule {
+            id: Uuid::nil(),
+            ..fixtures::schedule(Uuid::nil(), date, shift_type)
+        }
+    }
+
+    fn render(event: IcsEvent) -> String {
+        IcsCalendar {
+            name: "Planning".to_string(),
+            events: vec![event],
+        }
+        .render()
+    }
+
+    #[test]
+    fn test_all_day_rest_code() {
+        let rest = timed("RH", ShiftCategory::Rest, Some((6, 14)));
+        let ics = render(IcsEvent::from_schedule(&schedule(&rest), &rest, None, None));
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260302\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20260303\r\n"));
+        assert!(ics.contains("SUMMARY:RH - Code RH\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_night_shift_ends_next_day() {
+        let night = timed("121", ShiftCategory::Night, Some((22, 6)));
+        let ics = render(IcsEvent::from_schedule(
+            &schedule(&night),
+            &night,
+            None,
+            None,
+        ));
+
+        assert!(ics.contains("DTSTART:20260302T220000\r\n"));
+        assert!(ics.contains("DTEND:20260303T060000\r\n"));
+        assert!(ics.contains("CATEGORIES:Night\r\n"));
+    }
+
+    #[test]
+    fn test_holiday_marker_and_agent() {
+        let standard = timed("7101", ShiftCategory::Standard, Some((6, 14)));
+        let event = IcsEvent::from_schedule(
+            &schedule(&standard),
+            &standard,
+            Some("Dupont Marie"),
+            Some("Lundi de Pâques"),
+        );
+
+        assert_eq!(event.summary, "Dupont Marie — 7101 - Code 7101 (férié)");
+        assert_eq!(
+            event.description.as_deref(),
+            Some("Jour férié : Lundi de Pâques")
+        );
+        assert!(event.categories.contains(&"Férié".to_string()));
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+
+        let folded = fold(&format!("SUMMARY:{}", "é".repeat(60)));
+        let lines: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE));
+        assert!(lines[1..].iter().all(|l| l.starts_with(' ')));
+    }
+}
@@ -0,0 +1,13 @@
+//! Export Infrastructure
+//!
+//! File formats generated from planning data.
+
+pub mod ics;
+pub mod payroll;
+pub mod pdf;
+pub mod xlsx;
+
+pub use ics::{IcsCalendar, IcsEvent};
+pub use payroll::PayrollExporter;
+pub use pdf::{PaperSize, PdfExporter, PlanningMatrix};
+pub use xlsx::{PlanningWorkbook, XlsxExporter};
@@ -0,0 +1,155 @@
+//! Payroll Export
+//!
+//! Files sent to payroll, one record per agent, period and wage code.
+//!
+//! CSV: semicolon separated with a header row, quantities with two
+//! decimals.
+//!
+//! Fixed width (37 characters per record, CRLF):
+//!
+//! | Columns | Width | Field                                  |
+//! |---------|-------|----------------------------------------|
+//! | 1-10    | 10    | Matricule, left aligned                |
+//! | 11-14   | 4     | Year                                   |
+//! | 15-16   | 2     | Period number, zero padded             |
+//! | 17-26   | 10    | Wage code, left aligned                |
+//! | 27-37   | 11    | Quantity in hundredths, zero padded    |
+
+use uuid::Uuid;
+
+use crate::domain::entities::payroll::{PayrollFormat, PayrollLine};
+
+/// CSV header row
+const CSV_HEADER: &str = "matricule;agent;annee;periode;debut;fin;rubrique;libelle;quantite";
+
+/// Payroll file exporter
+pub struct PayrollExporter;
+
+impl PayrollExporter {
+    /// Render the lines in a format
+    pub fn export(lines: &[PayrollLine], format: PayrollFormat) -> String {
+        match format {
+            PayrollFormat::Csv => Self::csv(lines),
+            PayrollFormat::Fixed => Self::fixed_width(lines),
+        }
+    }
+
+    /// Semicolon separated values
+    pub fn csv(lines: &[PayrollLine]) -> String {
+        let mut out = String::from(CSV_HEADER);
+        out.push_str("\r\n");
+
+        for line in lines {
+            let fields = [
+                csv_field(line.matricule.as_deref().unwrap_or("")),
+                csv_field(&line.agent_name),
+                line.year.to_string(),
+                format!("P{:02}", line.period_number),
+                line.start_date.format("%Y-%m-%d").to_string(),
+                line.end_date.format("%Y-%m-%d").to_string(),
+                csv_field(&line.wage_code),
+                csv_field(&line.label),
+                format!("{:.2}", line.quantity),
+            ];
+            out.push_str(&fields.join(";"));
+            out.push_str("\r\n");
+        }
+
+        out
+    }
+
+    /// Fixed-width records
+    pub fn fixed_width(lines: &[PayrollLine]) -> String {
+        let mut out = String::new();
+
+        for line in lines {
+            out.push_str(&format!(
+                "{:<10}{:04}{:02}{:<10}{:011}\r\n",
+                truncate(line.matricule.as_deref().unwrap_or(""), 10),
+                line.year,
+                line.period_number,
+                truncate(&line.wage_code, 10),
+                (line.quantity * 100.0).round() as i64,
+            ));
+        }
+
+        out
+    }
+
+    /// File name of a batch
+    pub fn file_name(batch_id: Uuid, format: PayrollFormat) -> String {
+        let extension = match format {
+            PayrollFormat::Csv => "csv",
+            PayrollFormat::Fixed => "txt",
+        };
+        format!("paie_{}.{}", batch_id.simple(), extension)
+    }
+
+    /// MIME type of a format
+    pub fn content_type(format: PayrollFormat) -> &'static str {
+        match format {
+            PayrollFormat::Csv => "text/csv; charset=utf-8",
+            PayrollFormat::Fixed => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// Quote a CSV field when it holds a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// First `width` characters of a value
+fn truncate(value: &str, width: usize) -> String {
+    value.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn line(matricule: Option<&str>, wage_code: &str, quantity: f64) -> PayrollLine {
+        PayrollLine {
+            user_id: Uuid::nil(),
+            matricule: matricule.map(str::to_string),
+            agent_name: "Dupont; Marie".to_string(),
+            year: 2026,
+            period_number: 3,
+            start_date: NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 4, 5).unwrap(),
+            wage_code: wage_code.to_string(),
+            label: "Heures travaillées".to_string(),
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = PayrollExporter::csv(&[line(Some("M001"), "HTRAV", 152.0)]);
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(rows[0], CSV_HEADER);
+        assert_eq!(
+            rows[1],
+            "M001;\"Dupont; Marie\";2026;P03;2026-03-09;2026-04-05;HTRAV;Heures travaillées;152.00"
+        );
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let fixed = PayrollExporter::fixed_width(&[
+            line(Some("M001"), "HTRAV", 152.5),
+            line(None, "3000", 2.0),
+        ]);
+        let records: Vec<&str> = fixed.trim_end().split("\r\n").collect();
+
+        assert_eq!(records[0], "M001      202603HTRAV     00000015250");
+        assert_eq!(records[1], "          2026033000      00000000200");
+        assert!(records.iter().all(|r| r.len() == 37));
+    }
+}
@@ -0,0 +1,587 @@
+//! PDF Planning Export
+//!
+//! Printable planning matrix of a period or month, to post on the wall:
+//! - Agents as rows, days as columns, cells in the shift type colors
+//! - Weekends and public holidays shaded in the header and empty cells
+//! - Per-agent totals (hours and night hours over the whole range)
+//! - Legend of the codes used, and page numbers
+//!
+//! A4 or A3 landscape. Ranges wider than a page are split into balanced
+//! column blocks, and long agent lists into row blocks.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, Color as PdfColor, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect, Rgb,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::entities::{Schedule, ShiftType};
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::domain::services::{MonthlyStats, ShiftTypeHistory};
+use crate::domain::value_objects::Color;
+use crate::infrastructure::persistence::planning_data::AgentSummary;
+
+/// Weekday letters, Monday first
+const WEEKDAYS: [&str; 7] = ["L", "M", "M", "J", "V", "S", "D"];
+
+// Layout (mm)
+const MARGIN: f32 = 10.0;
+const TITLE_HEIGHT: f32 = 10.0;
+const HEADER_HEIGHT: f32 = 8.0;
+const ROW_HEIGHT: f32 = 6.0;
+const NAME_WIDTH: f32 = 40.0;
+const TOTAL_WIDTH: f32 = 12.0;
+const MIN_DAY_WIDTH: f32 = 6.5;
+const MAX_DAY_WIDTH: f32 = 12.0;
+const LEGEND_ITEM_WIDTH: f32 = 45.0;
+const LEGEND_LINE_HEIGHT: f32 = 5.0;
+const FOOTER_HEIGHT: f32 = 6.0;
+
+/// Total columns (hours, night hours)
+const TOTAL_COLUMNS: [&str; 2] = ["Heures", "Nuit"];
+
+// generate.py palette
+const HEADER_FILL: (u8, u8, u8) = (0x44, 0x72, 0xC4);
+const TOTAL_FILL: (u8, u8, u8) = (0xD9, 0xE1, 0xF2);
+const WEEKEND_FILL: (u8, u8, u8) = (0xE7, 0xE6, 0xE6);
+const HOLIDAY_FILL: (u8, u8, u8) = (0xFF, 0xC7, 0xCE);
+const GRID: (u8, u8, u8) = (0xA6, 0xA6, 0xA6);
+const WHITE: (u8, u8, u8) = (0xFF, 0xFF, 0xFF);
+const BLACK: (u8, u8, u8) = (0x00, 0x00, 0x00);
+
+/// Paper size (always landscape)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaperSize {
+    #[default]
+    A4,
+    A3,
+}
+
+impl PaperSize {
+    /// Width and height in mm
+    pub fn dimensions(&self) -> (f32, f32) {
+        match self {
+            PaperSize::A4 => (297.0, 210.0),
+            PaperSize::A3 => (420.0, 297.0),
+        }
+    }
+}
+
+/// Data of a printable planning matrix
+#[derive(Debug, Clone)]
+pub struct PlanningMatrix {
+    /// Title printed on every page, e.g. "Planning P3 2026"
+    pub title: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Agents in row order
+    pub agents: Vec<AgentSummary>,
+    pub shift_types: Vec<ShiftType>,
+    /// Past definitions of the shift types
+    pub history: ShiftTypeHistory,
+    /// Schedules of the agents within the range
+    pub schedules: Vec<Schedule>,
+    pub holidays: Vec<Holiday>,
+}
+
+impl PlanningMatrix {
+    /// Days of the range
+    pub fn days(&self) -> Vec<NaiveDate> {
+        let mut days = Vec::new();
+        let mut date = self.start_date;
+        while date <= self.end_date {
+            days.push(date);
+            date += Duration::days(1);
+        }
+        days
+    }
+
+    /// Shift types used in the range, in display order
+    pub fn used_shift_types(&self) -> Vec<&ShiftType> {
+        self.shift_types
+            .iter()
+            .filter(|st| {
+                self.schedules
+                    .iter()
+                    .any(|s| s.shift_type_id == Some(st.id))
+            })
+            .collect()
+    }
+}
+
+/// Days and agents printed on one page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSlice {
+    pub days: Range<usize>,
+    pub agents: Range<usize>,
+}
+
+/// Page layout of a matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageLayout {
+    pub paper: PaperSize,
+    pub day_width: f32,
+    pub legend_lines: usize,
+    pub pages: Vec<PageSlice>,
+}
+
+impl PageLayout {
+    /// Split `days` x `agents` into pages
+    ///
+    /// Column blocks are balanced (a 40-day range on A4 gives two blocks
+    /// of 20 days rather than 32 + 8). Every page repeats the names, the
+    /// totals and the legend.
+    pub fn new(paper: PaperSize, days: usize, agents: usize, legend_items: usize) -> Self {
+        let (width, height) = paper.dimensions();
+        let grid_width = width - 2.0 * MARGIN - NAME_WIDTH - Self::totals_width();
+
+        let max_days = ((grid_width / MIN_DAY_WIDTH) as usize).max(1);
+        let day_blocks = days.div_ceil(max_days).max(1);
+        let days_per_page = days.div_ceil(day_blocks).max(1);
+        let day_width = (grid_width / days_per_page as f32).min(MAX_DAY_WIDTH);
+
+        let per_line = (((width - 2.0 * MARGIN) / LEGEND_ITEM_WIDTH) as usize).max(1);
+        // Weekend and holiday swatches come first
+        let legend_lines = (legend_items + 2).div_ceil(per_line);
+        let grid_height = height
+            - 2.0 * MARGIN
+            - TITLE_HEIGHT
+            - HEADER_HEIGHT
+            - Self::legend_height(legend_lines)
+            - FOOTER_HEIGHT;
+        let rows_per_page = ((grid_height / ROW_HEIGHT) as usize).max(1);
+
+        let mut pages = Vec::new();
+        for agent_start in (0..agents.max(1)).step_by(rows_per_page) {
+            for day_start in (0..days.max(1)).step_by(days_per_page) {
+                pages.push(PageSlice {
+                    days: day_start..(day_start + days_per_page).min(days),
+                    agents: agent_start..(agent_start + rows_per_page).min(agents),
+                });
+            }
+        }
+
+        Self {
+            paper,
+            day_width,
+            legend_lines,
+            pages,
+        }
+    }
+
+    fn totals_width() -> f32 {
+        TOTAL_WIDTH * TOTAL_COLUMNS.len() as f32
+    }
+
+    fn legend_height(lines: usize) -> f32 {
+        LEGEND_LINE_HEIGHT * (lines as f32 + 1.0)
+    }
+}
+
+/// PDF planning exporter
+pub struct PdfExporter;
+
+impl PdfExporter {
+    /// Render the matrix as a PDF document
+    pub fn export(matrix: &PlanningMatrix, paper: PaperSize) -> Result<Vec<u8>, printpdf::Error> {
+        let days = matrix.days();
+        let legend = matrix.used_shift_types();
+        let layout = PageLayout::new(paper, days.len(), matrix.agents.len(), legend.len());
+        let (width, height) = paper.dimensions();
+
+        let cells: HashMap<(Uuid, NaiveDate), &ShiftType> = matrix
+            .schedules
+            .iter()
+            .filter_map(|s| {
+                let shift_type = matrix
+                    .shift_types
+                    .iter()
+                    .find(|st| Some(st.id) == s.shift_type_id)?;
+                Some(((s.user_id, s.date), shift_type))
+            })
+            .collect();
+        let agent_ids: Vec<Uuid> = matrix.agents.iter().map(|a| a.id).collect();
+        let totals = MonthlyStats::aggregate(
+            matrix.start_date,
+            matrix.end_date,
+            &agent_ids,
+            &matrix.schedules,
+            &matrix.shift_types,
+            &matrix.history,
+        );
+
+        let (doc, first_page, first_layer) =
+            PdfDocument::new(&matrix.title, Mm(width), Mm(height), "Planning");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+        for (index, slice) in layout.pages.iter().enumerate() {
+            let layer = if index == 0 {
+                doc.get_page(first_page).get_layer(first_layer)
+            } else {
+                let (page, layer) = doc.add_page(Mm(width), Mm(height), "Planning");
+                doc.get_page(page).get_layer(layer)
+            };
+            let canvas = Canvas {
+                layer,
+                height,
+                regular: &regular,
+                bold: &bold,
+            };
+
+            let mut y = MARGIN;
+            canvas.text(&matrix.title, 14.0, MARGIN, y + 6.0, true, BLACK);
+            canvas.text(
+                &format!(
+                    "Du {} au {}",
+                    matrix.start_date.format("%d/%m/%Y"),
+                    matrix.end_date.format("%d/%m/%Y")
+                ),
+                9.0,
+                MARGIN,
+                y + 9.5,
+                false,
+                BLACK,
+            );
+            y += TITLE_HEIGHT;
+
+            // Header
+            let mut x = MARGIN;
+            canvas.cell(x, y, NAME_WIDTH, HEADER_HEIGHT, HEADER_FILL);
+            canvas.centered("Agent", 8.0, x, y, NAME_WIDTH, HEADER_HEIGHT, true, WHITE);
+            x += NAME_WIDTH;
+            for date in &days[slice.days.clone()] {
+                let fill = Self::day_fill(*date, &matrix.holidays).unwrap_or(HEADER_FILL);
+                let text = if fill == HEADER_FILL { WHITE } else { BLACK };
+                canvas.cell(x, y, layout.day_width, HEADER_HEIGHT, fill);
+                canvas.centered(
+                    &date.day().to_string(),
+                    7.0,
+                    x,
+                    y,
+                    layout.day_width,
+                    HEADER_HEIGHT / 2.0,
+                    true,
+                    text,
+                );
+                canvas.centered(
+                    WEEKDAYS[date.weekday().num_days_from_monday() as usize],
+                    6.0,
+                    x,
+                    y + HEADER_HEIGHT / 2.0,
+                    layout.day_width,
+                    HEADER_HEIGHT / 2.0,
+                    false,
+                    text,
+                );
+                x += layout.day_width;
+            }
+            for label in TOTAL_COLUMNS {
+                canvas.cell(x, y, TOTAL_WIDTH, HEADER_HEIGHT, HEADER_FILL);
+                canvas.centered(label, 7.0, x, y, TOTAL_WIDTH, HEADER_HEIGHT, true, WHITE);
+                x += TOTAL_WIDTH;
+            }
+            y += HEADER_HEIGHT;
+
+            // Rows
+            for (agent, stats) in matrix.agents[slice.agents.clone()]
+                .iter()
+                .zip(&totals[slice.agents.clone()])
+            {
+                let mut x = MARGIN;
+                canvas.cell(x, y, NAME_WIDTH, ROW_HEIGHT, WHITE);
+                canvas.text(
+                    &format!("{} {}", agent.last_name, agent.first_name),
+                    7.0,
+                    x + 1.0,
+                    y + ROW_HEIGHT - 1.8,
+                    false,
+                    BLACK,
+                );
+                x += NAME_WIDTH;
+
+                for date in &days[slice.days.clone()] {
+                    match cells.get(&(agent.id, *date)) {
+                        Some(shift_type) => {
+                            let color = Color::new_unchecked(shift_type.color_hex.as_str());
+                            let text =
+                                Color::new_unchecked(color.text_color().trim_start_matches('#'));
+                            canvas.cell(x, y, layout.day_width, ROW_HEIGHT, color.rgb());
+                            canvas.centered(
+                                &shift_type.code,
+                                6.0,
+                                x,
+                                y,
+                                layout.day_width,
+                                ROW_HEIGHT,
+                                true,
+                                text.rgb(),
+                            );
+                        }
+                        None => {
+                            let fill = Self::day_fill(*date, &matrix.holidays).unwrap_or(WHITE);
+                            canvas.cell(x, y, layout.day_width, ROW_HEIGHT, fill);
+                        }
+                    }
+                    x += layout.day_width;
+                }
+
+                for value in [stats.total_hours, stats.night_hours] {
+                    canvas.cell(x, y, TOTAL_WIDTH, ROW_HEIGHT, TOTAL_FILL);
+                    canvas.centered(
+                        &format!("{:.1}", value),
+                        7.0,
+                        x,
+                        y,
+                        TOTAL_WIDTH,
+                        ROW_HEIGHT,
+                        true,
+                        BLACK,
+                    );
+                    x += TOTAL_WIDTH;
+                }
+                y += ROW_HEIGHT;
+            }
+
+            // Legend
+            y += LEGEND_LINE_HEIGHT;
+            let per_line = (((width - 2.0 * MARGIN) / LEGEND_ITEM_WIDTH) as usize).max(1);
+            let mut items = vec![
+                ("Week-end".to_string(), WEEKEND_FILL, BLACK),
+                ("Férié".to_string(), HOLIDAY_FILL, BLACK),
+            ];
+            for shift_type in &legend {
+                let color = Color::new_unchecked(shift_type.color_hex.as_str());
+                let label = match &shift_type.description {
+                    Some(description) => format!("{} - {}", shift_type.code, description),
+                    None => shift_type.code.clone(),
+                };
+                items.push((label, color.rgb(), BLACK));
+            }
+            for (i, (label, fill, text)) in items.iter().enumerate() {
+                let x = MARGIN + (i % per_line) as f32 * LEGEND_ITEM_WIDTH;
+                let y = y + (i / per_line) as f32 * LEGEND_LINE_HEIGHT;
+                canvas.cell(x, y, 4.0, 3.5, *fill);
+                canvas.text(
+                    &Self::truncate(label, 30),
+                    6.5,
+                    x + 5.5,
+                    y + 3.0,
+                    false,
+                    *text,
+                );
+            }
+
+            // Footer
+            canvas.text(
+                &format!("Page {} / {}", index + 1, layout.pages.len()),
+                7.0,
+                width - MARGIN - 20.0,
+                height - MARGIN,
+                false,
+                BLACK,
+            );
+        }
+
+        doc.save_to_bytes()
+    }
+
+    /// File name of a matrix export, e.g. planning_2026_P03.pdf
+    pub fn file_name(year: i32, scope: &str) -> String {
+        format!("planning_{}_{}.pdf", year, scope)
+    }
+
+    /// Shading of a day without a code (holidays take precedence)
+    fn day_fill(date: NaiveDate, holidays: &[Holiday]) -> Option<(u8, u8, u8)> {
+        if holidays.iter().any(|h| h.date == date) {
+            Some(HOLIDAY_FILL)
+        } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            Some(WEEKEND_FILL)
+        } else {
+            None
+        }
+    }
+
+    fn truncate(text: &str, max: usize) -> String {
+        if text.chars().count() <= max {
+            text.to_string()
+        } else {
+            format!("{}…", text.chars().take(max - 1).collect::<String>())
+        }
+    }
+}
+
+/// Drawing helpers with a top-left origin
+struct Canvas<'a> {
+    layer: PdfLayerReference,
+    height: f32,
+    regular: &'a IndirectFontRef,
+    bold: &'a IndirectFontRef,
+}
+
+impl Canvas<'_> {
+    /// Filled cell with a grid border
+    fn cell(&self, x: f32, y: f32, width: f32, height: f32, fill: (u8, u8, u8)) {
+        self.layer.set_fill_color(rgb(fill));
+        self.layer.set_outline_color(rgb(GRID));
+        self.layer.set_outline_thickness(0.3);
+        self.layer.add_rect(
+            Rect::new(
+                Mm(x),
+                Mm(self.height - y - height),
+                Mm(x + width),
+                Mm(self.height - y),
+            )
+            .with_mode(PaintMode::FillStroke),
+        );
+    }
+
+    /// Text with its baseline at `y`
+    fn text(&self, text: &str, size: f32, x: f32, y: f32, bold: bool, color: (u8, u8, u8)) {
+        let font = if bold { self.bold } else { self.regular };
+        self.layer.set_fill_color(rgb(color));
+        self.layer
+            .use_text(text, size, Mm(x), Mm(self.height - y), font);
+    }
+
+    /// Text centered in a box
+    #[allow(clippy::too_many_arguments)]
+    fn centered(
+        &self,
+        text: &str,
+        size: f32,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        bold: bool,
+        color: (u8, u8, u8),
+    ) {
+        let x = x + (width - text_width(text, size)) / 2.0;
+        // Cap height of Helvetica is about 0.7 em
+        let y = y + (height + size * PT_TO_MM * 0.7) / 2.0;
+        self.text(text, size, x, y, bold, color);
+    }
+}
+
+const PT_TO_MM: f32 = 0.3528;
+
+/// Approximate width of a Helvetica string (mm)
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.55 * PT_TO_MM
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> PdfColor {
+    PdfColor::Rgb(Rgb::new(
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use chrono::Utc;
+
+    #[test]
+    fn test_period_and_month_fit_a4() {
+        let period = PageLayout::new(PaperSize::A4, 28, 10, 5);
+        assert_eq!(
+            period.pages,
+            vec![PageSlice {
+                days: 0..28,
+                agents: 0..10
+            }]
+        );
+
+        let month = PageLayout::new(PaperSize::A4, 31, 10, 5);
+        assert_eq!(month.pages.len(), 1);
+        assert!(month.day_width >= MIN_DAY_WIDTH);
+    }
+
+    #[test]
+    fn test_wide_range_split_in_balanced_blocks() {
+        let layout = PageLayout::new(PaperSize::A4, 40, 10, 5);
+
+        assert_eq!(layout.pages.len(), 2);
+        assert_eq!(layout.pages[0].days, 0..20);
+        assert_eq!(layout.pages[1].days, 20..40);
+
+        // A3 takes the whole range on one page
+        assert_eq!(PageLayout::new(PaperSize::A3, 40, 10, 5).pages.len(), 1);
+    }
+
+    #[test]
+    fn test_long_agent_list_split_in_row_blocks() {
+        let layout = PageLayout::new(PaperSize::A4, 40, 60, 5);
+        let blocks: Vec<_> = layout.pages.iter().map(|p| p.agents.clone()).collect();
+
+        assert!(layout.pages.len() > 2);
+        assert_eq!(blocks[0], blocks[1]);
+        assert_eq!(blocks.last().unwrap().end, 60);
+    }
+
+    #[test]
+    fn test_export_renders_pdf() {
+        let shift_type = ShiftType {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::nil(),
+            code: "101".to_string(),
+            description: Some("Matin".to_string()),
+            category: ShiftCategory::Standard,
+            color_hex: "4472C4".to_string(),
+            icon: None,
+            duration_hours: 8.0,
+            night_hours: 0.0,
+            start_time: None,
+            end_time: None,
+            is_countable: true,
+            requires_recovery: false,
+            is_holiday_indicator: false,
+            is_rest_day: false,
+            display_order: 0,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let agent = AgentSummary {
+            id: Uuid::new_v4(),
+            first_name: "Marie".to_string(),
+            last_name: "Dupont".to_string(),
+            matricule: None,
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        let matrix = PlanningMatrix {
+            title: "Planning Mai 2026".to_string(),
+            start_date: date,
+            end_date: NaiveDate::from_ymd_opt(2026, 5, 31).unwrap(),
+            schedules: vec![Schedule {
+                is_holiday: true,
+                ..fixtures::schedule(agent.id, date, &shift_type)
+            }],
+            agents: vec![agent],
+            shift_types: vec![shift_type],
+            history: ShiftTypeHistory::default(),
+            holidays: vec![Holiday {
+                date,
+                name: "Fête du Travail".to_string(),
+                is_moveable: false,
+            }],
+        };
+
+        assert_eq!(matrix.used_shift_types().len(), 1);
+
+        let bytes = PdfExporter::export(&matrix, PaperSize::A3).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+}
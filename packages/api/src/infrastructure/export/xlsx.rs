@@ -0,0 +1,762 @@
+//! XLSX Planning Export
+//!
+//! Native replacement for generate.py. Writes the planning workbook of an
+//! agent with the same layout:
+//! - CONFIGURATION: year, leave entitlements, periods and shift codes
+//! - TABLEAU DE BORD: yearly count per code and the carry-over block
+//! - One sheet per month: calendar, prestations and repos tables, and the
+//!   period control block
+//!
+//! Values are computed here rather than written as formulas, so the file
+//! reflects the database at export time.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rust_xlsxwriter::{
+    Color as XlsxColor, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError,
+};
+use uuid::Uuid;
+
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::{Period, Schedule, ShiftType};
+use crate::domain::services::dashboard_builder::LeaveEntitlement;
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::domain::services::monthly_stats::AgentStats;
+use crate::domain::services::{
+    BalanceCalculator, DashboardBuilder, MonthlyStats, ShiftTypeHistory,
+};
+use crate::domain::value_objects::Color;
+
+/// Month sheet names
+pub const MONTHS: [&str; 12] = [
+    "Janvier",
+    "Février",
+    "Mars",
+    "Avril",
+    "Mai",
+    "Juin",
+    "Juillet",
+    "Août",
+    "Septembre",
+    "Octobre",
+    "Novembre",
+    "Décembre",
+];
+
+/// Weekday letters, Monday first
+const WEEKDAYS: [&str; 7] = ["L", "M", "M", "J", "V", "S", "D"];
+
+// generate.py palette
+const HEADER_FILL: u32 = 0x4472C4;
+const TOTAL_FILL: u32 = 0xD9E1F2;
+const WEEKEND_FILL: u32 = 0xE7E6E6;
+const HOLIDAY_FILL: u32 = 0xFFC7CE;
+const PERIOD_BOUND_FILL: u32 = 0xB4C7E7;
+const PERIOD_BORDER: u32 = 0x0070C0;
+const ALERT_FILL: u32 = 0xFF6B6B;
+const WARNING_FILL: u32 = 0xFFE699;
+const OK_FILL: u32 = 0xC6EFCE;
+
+/// Status of a period meeting every control
+pub const STATUS_OK: &str = "✓ OK";
+
+/// Data of an agent's planning workbook
+#[derive(Debug, Clone)]
+pub struct PlanningWorkbook {
+    pub year: i32,
+    pub user_id: Uuid,
+    /// Agent name shown on the CONFIGURATION sheet
+    pub agent_name: String,
+    /// Periods of the year (P1-P13)
+    pub periods: Vec<Period>,
+    pub shift_types: Vec<ShiftType>,
+    /// Past definitions of the shift types
+    pub history: ShiftTypeHistory,
+    /// Schedules of the agent, covering the year and its periods
+    pub schedules: Vec<Schedule>,
+    pub holidays: Vec<Holiday>,
+    /// CN and JC entitlements for the carry-over block
+    pub leave: Vec<LeaveEntitlement>,
+}
+
+/// Cell formats shared by every sheet
+struct Styles {
+    title: Format,
+    label: Format,
+    header: Format,
+    cell: Format,
+    total: Format,
+    date: Format,
+}
+
+impl Styles {
+    fn new() -> Self {
+        let cell = Format::new()
+            .set_font_name("Aptos")
+            .set_font_size(10)
+            .set_align(FormatAlign::Center)
+            .set_align(FormatAlign::VerticalCenter)
+            .set_border(FormatBorder::Thin);
+
+        Self {
+            title: Format::new()
+                .set_font_name("Aptos")
+                .set_font_size(14)
+                .set_bold(),
+            label: Format::new()
+                .set_font_name("Aptos")
+                .set_font_size(12)
+                .set_bold(),
+            header: cell
+                .clone()
+                .set_bold()
+                .set_font_color(XlsxColor::White)
+                .set_background_color(HEADER_FILL),
+            total: cell.clone().set_bold().set_background_color(TOTAL_FILL),
+            date: cell.clone().set_num_format("dd/mm/yyyy"),
+            cell,
+        }
+    }
+}
+
+/// XLSX planning exporter
+pub struct XlsxExporter;
+
+impl XlsxExporter {
+    /// Write the workbook and return the file content
+    pub fn export(data: &PlanningWorkbook) -> Result<Vec<u8>, XlsxError> {
+        let styles = Styles::new();
+        let mut workbook = Workbook::new();
+
+        Self::write_configuration(workbook.add_worksheet(), data, &styles)?;
+        Self::write_dashboard(workbook.add_worksheet(), data, &styles)?;
+        for month in 1..=12 {
+            Self::write_month(workbook.add_worksheet(), data, month, &styles)?;
+        }
+
+        workbook.save_to_buffer()
+    }
+
+    /// File name of the workbook of a year
+    pub fn file_name(year: i32) -> String {
+        format!("planning_{}.xlsx", year)
+    }
+
+    /// CONFIGURATION sheet
+    fn write_configuration(
+        ws: &mut Worksheet,
+        data: &PlanningWorkbook,
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        ws.set_name("CONFIGURATION")?;
+        ws.set_column_width(0, 26)?;
+        ws.set_column_width(1, 30)?;
+        ws.write_string_with_format(
+            0,
+            0,
+            format!("CONFIGURATION DU FICHIER - ANNÉE {}", data.year),
+            &styles.title,
+        )?;
+
+        ws.write_string_with_format(2, 0, "Agent", &styles.label)?;
+        ws.write_string(2, 1, &data.agent_name)?;
+        ws.write_string_with_format(3, 0, "Année", &styles.label)?;
+        ws.write_number(3, 1, data.year)?;
+
+        // Leave entitlements (rows 6-7)
+        Self::write_headers(ws, 4, &["Type", "Droit", "Report"], styles)?;
+        let mut row = 5;
+        for leave in &data.leave {
+            ws.write_string_with_format(row, 0, &leave.code, &styles.cell)?;
+            ws.write_number_with_format(row, 1, leave.entitlement, &styles.cell)?;
+            ws.write_number_with_format(row, 2, leave.carryover, &styles.cell)?;
+            row += 1;
+        }
+
+        // Periods
+        row += 1;
+        Self::write_headers(ws, row, &["Période", "Début", "Fin", "Quota"], styles)?;
+        row += 1;
+        for period in Self::periods_of_year(data) {
+            ws.write_string_with_format(row, 0, period.label(), &styles.cell)?;
+            ws.write_date_with_format(row, 1, period.start_date, &styles.date)?;
+            ws.write_date_with_format(row, 2, period.end_date, &styles.date)?;
+            ws.write_number_with_format(row, 3, period.hour_quota, &styles.cell)?;
+            row += 1;
+        }
+
+        // Shift codes
+        row += 1;
+        Self::write_headers(
+            ws,
+            row,
+            &["Code", "Description", "Heures", "H. Nuit", "Couleur"],
+            styles,
+        )?;
+        row += 1;
+        for shift_type in data.shift_types.iter().filter(|st| st.is_active) {
+            let fill = styles
+                .cell
+                .clone()
+                .set_background_color(Self::fill(shift_type));
+            ws.write_string_with_format(row, 0, &shift_type.code, &fill)?;
+            ws.write_string_with_format(
+                row,
+                1,
+                shift_type.description.as_deref().unwrap_or(""),
+                &styles.cell,
+            )?;
+            ws.write_number_with_format(row, 2, shift_type.duration_hours, &styles.cell)?;
+            ws.write_number_with_format(row, 3, shift_type.night_hours, &styles.cell)?;
+            ws.write_string_with_format(row, 4, Self::color(shift_type).hex(), &fill)?;
+            row += 1;
+        }
+
+        Ok(())
+    }
+
+    /// TABLEAU DE BORD sheet
+    fn write_dashboard(
+        ws: &mut Worksheet,
+        data: &PlanningWorkbook,
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        let dashboard = DashboardBuilder::build(
+            data.year,
+            &data.periods,
+            &data.schedules,
+            &data.shift_types,
+            &data.history,
+            &data.leave,
+        );
+
+        ws.set_name("TABLEAU DE BORD")?;
+        ws.set_column_width(0, 26)?;
+        ws.write_string_with_format(
+            0,
+            0,
+            format!("TABLEAU DE BORD - ANNÉE {}", data.year),
+            &styles.title,
+        )?;
+
+        // Code rows start at row 12
+        Self::write_headers(ws, 10, &["Code", "Jours", "H. Totales", "H. Nuit"], styles)?;
+        let mut row = 11;
+        for line in &dashboard.rows {
+            let fill = data
+                .shift_types
+                .iter()
+                .find(|st| st.code == line.code)
+                .map(|st| styles.cell.clone().set_background_color(Self::fill(st)))
+                .unwrap_or_else(|| styles.cell.clone());
+            ws.write_string_with_format(row, 0, &line.code, &fill)?;
+            ws.write_number_with_format(row, 1, line.count, &styles.cell)?;
+            ws.write_number_with_format(row, 2, line.hours, &styles.cell)?;
+            ws.write_number_with_format(row, 3, line.night_hours, &styles.cell)?;
+            row += 1;
+        }
+
+        ws.write_string_with_format(row, 0, "TOTAL", &styles.total)?;
+        ws.write_number_with_format(row, 1, dashboard.total.count, &styles.total)?;
+        ws.write_number_with_format(row, 2, dashboard.total.hours, &styles.total)?;
+        ws.write_number_with_format(row, 3, dashboard.total.night_hours, &styles.total)?;
+        row += 2;
+
+        ws.write_string_with_format(
+            row,
+            0,
+            format!("REPORT POUR {}", data.year + 1),
+            &styles.label,
+        )?;
+        row += 1;
+        Self::write_headers(
+            ws,
+            row,
+            &["Type", "Droit", "Report", "Pris", "Solde"],
+            styles,
+        )?;
+        row += 1;
+        for carry_over in &dashboard.carry_over {
+            ws.write_string_with_format(row, 0, &carry_over.code, &styles.cell)?;
+            ws.write_number_with_format(row, 1, carry_over.entitlement, &styles.cell)?;
+            ws.write_number_with_format(row, 2, carry_over.carryover, &styles.cell)?;
+            ws.write_number_with_format(row, 3, carry_over.taken, &styles.cell)?;
+            ws.write_number_with_format(row, 4, carry_over.balance, &styles.cell)?;
+            row += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Month sheet (1 = Janvier)
+    fn write_month(
+        ws: &mut Worksheet,
+        data: &PlanningWorkbook,
+        month: u32,
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        let name = MONTHS[month as usize - 1];
+        ws.set_name(name)?;
+        ws.set_column_width(0, 45)?;
+        ws.write_string_with_format(
+            0,
+            0,
+            format!("{} {}", name.to_uppercase(), data.year),
+            &styles.title,
+        )?;
+
+        let first = NaiveDate::from_ymd_opt(data.year, month, 1).unwrap();
+        let mut date = first;
+        while date.month() == month {
+            Self::write_day(ws, data, date, styles)?;
+            date += Duration::days(1);
+        }
+        let last = date - Duration::days(1);
+
+        // Monthly tables from row 16
+        let stats = MonthlyStats::aggregate(
+            first,
+            last,
+            &[data.user_id],
+            &data.schedules,
+            &data.shift_types,
+            &data.history,
+        );
+        let (worked, rest): (Vec<&ShiftType>, Vec<&ShiftType>) = data
+            .shift_types
+            .iter()
+            .filter(|st| st.is_active)
+            .partition(|st| st.is_worked());
+
+        let (row, worked_hours) =
+            Self::write_code_table(ws, 15, "Prestations", &worked, &stats[0], styles)?;
+        let (row, rest_hours) =
+            Self::write_code_table(ws, row + 1, "REPOS", &rest, &stats[0], styles)?;
+
+        let total_row = row + 1;
+        ws.write_string_with_format(total_row, 0, "Total Heures", &styles.total)?;
+        ws.write_number_with_format(total_row, 2, worked_hours + rest_hours, &styles.total)?;
+
+        Self::write_controls(ws, data, name, first, last, total_row + 4, styles)
+    }
+
+    /// Calendar cells of a day: number, weekday letter, code and period
+    fn write_day(
+        ws: &mut Worksheet,
+        data: &PlanningWorkbook,
+        date: NaiveDate,
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        let (row, col) = day_position(date.day());
+        let period = data.periods.iter().find(|p| p.contains_date(date));
+        let is_start = period.is_some_and(|p| p.start_date == date);
+        let is_end = period.is_some_and(|p| p.end_date == date);
+        let is_weekend = matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+        let is_holiday = data.holidays.iter().any(|h| h.date == date);
+
+        let mut day_format = styles.cell.clone();
+        if is_holiday {
+            day_format = day_format.set_background_color(HOLIDAY_FILL).set_bold();
+        } else if is_start || is_end {
+            day_format = day_format.set_background_color(PERIOD_BOUND_FILL);
+        } else if is_weekend {
+            day_format = day_format.set_background_color(WEEKEND_FILL);
+        }
+        ws.write_number_with_format(row, col, date.day(), &day_format)?;
+        ws.write_string_with_format(
+            row + 1,
+            col,
+            WEEKDAYS[date.weekday().num_days_from_monday() as usize],
+            &day_format,
+        )?;
+
+        // Code cell, with a thick border at period bounds
+        let mut code_format = styles.cell.clone();
+        if is_start {
+            code_format = code_format
+                .set_border_left(FormatBorder::Thick)
+                .set_border_left_color(PERIOD_BORDER);
+        } else if is_end {
+            code_format = code_format
+                .set_border_right(FormatBorder::Thick)
+                .set_border_right_color(PERIOD_BORDER);
+        }
+        let shift_type = data
+            .schedules
+            .iter()
+            .find(|s| s.user_id == data.user_id && s.date == date)
+            .and_then(|s| s.shift_type_id)
+            .and_then(|id| data.shift_types.iter().find(|st| st.id == id));
+        match shift_type {
+            Some(st) => ws.write_string_with_format(
+                row + 2,
+                col,
+                &st.code,
+                &code_format.set_background_color(Self::fill(st)),
+            )?,
+            None => ws.write_blank(row + 2, col, &code_format)?,
+        };
+
+        if let Some(period) = period {
+            let mut period_format = Format::new()
+                .set_font_name("Aptos")
+                .set_font_size(8)
+                .set_align(FormatAlign::Center);
+            if is_start || is_end {
+                period_format = period_format
+                    .set_bold()
+                    .set_font_color(PERIOD_BORDER)
+                    .set_background_color(PERIOD_BOUND_FILL);
+            }
+            ws.write_string_with_format(row + 3, col, period.label(), &period_format)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prestations or repos table with its TOTAL row
+    ///
+    /// Returns the row following the table and its total hours.
+    fn write_code_table(
+        ws: &mut Worksheet,
+        mut row: u32,
+        title: &str,
+        shift_types: &[&ShiftType],
+        stats: &AgentStats,
+        styles: &Styles,
+    ) -> Result<(u32, f64), XlsxError> {
+        Self::write_headers(
+            ws,
+            row,
+            &[title, "Jours", "H. Totales", "H. Nuit", "Couleur"],
+            styles,
+        )?;
+        row += 1;
+
+        let (mut count, mut hours, mut night_hours) = (0, 0.0, 0.0);
+        for shift_type in shift_types {
+            let code = stats.codes.iter().find(|c| c.code == shift_type.code);
+            let line = (
+                code.map(|c| c.count).unwrap_or(0),
+                code.map(|c| c.hours).unwrap_or(0.0),
+                code.map(|c| c.night_hours).unwrap_or(0.0),
+            );
+            count += line.0;
+            hours += line.1;
+            night_hours += line.2;
+
+            ws.write_string_with_format(row, 0, &shift_type.code, &styles.cell)?;
+            ws.write_number_with_format(row, 1, line.0, &styles.cell)?;
+            ws.write_number_with_format(row, 2, line.1, &styles.cell)?;
+            ws.write_number_with_format(row, 3, line.2, &styles.cell)?;
+            ws.write_blank(
+                row,
+                4,
+                &styles
+                    .cell
+                    .clone()
+                    .set_background_color(Self::fill(shift_type)),
+            )?;
+            row += 1;
+        }
+
+        ws.write_string_with_format(row, 0, "TOTAL", &styles.total)?;
+        ws.write_number_with_format(row, 1, count, &styles.total)?;
+        ws.write_number_with_format(row, 2, hours, &styles.total)?;
+        ws.write_number_with_format(row, 3, night_hours, &styles.total)?;
+        ws.write_blank(row, 4, &styles.total)?;
+
+        Ok((row + 2, hours))
+    }
+
+    /// CONTRÔLES PAR PÉRIODE block: one row per period overlapping the month
+    ///
+    /// Each period is checked over its whole range, including the days
+    /// falling in a neighbouring month.
+    fn write_controls(
+        ws: &mut Worksheet,
+        data: &PlanningWorkbook,
+        month_name: &str,
+        first: NaiveDate,
+        last: NaiveDate,
+        mut row: u32,
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        ws.write_string_with_format(
+            row,
+            0,
+            format!("CONTRÔLES PAR PÉRIODE - {}", month_name.to_uppercase()),
+            &styles.label,
+        )?;
+        row += 2;
+        Self::write_headers(
+            ws,
+            row,
+            &[
+                "Période",
+                "Heures",
+                "CH",
+                "RH",
+                "CV",
+                "RR",
+                "Férié Trav.",
+                "Statut",
+            ],
+            styles,
+        )?;
+        row += 1;
+
+        let holidays: Vec<NaiveDate> = data.holidays.iter().map(|h| h.date).collect();
+        let calculator = BalanceCalculator::new().with_history(data.history.clone());
+        let warning = styles.cell.clone().set_background_color(WARNING_FILL);
+        let alert = styles
+            .cell
+            .clone()
+            .set_bold()
+            .set_font_color(XlsxColor::White)
+            .set_background_color(ALERT_FILL);
+
+        let periods = data
+            .periods
+            .iter()
+            .filter(|p| p.start_date <= last && p.end_date >= first);
+        for period in periods {
+            let balance = calculator.calculate(
+                period,
+                data.user_id,
+                &data.schedules,
+                &data.shift_types,
+                &holidays,
+            );
+            let status = control_status(&balance, period.hour_quota);
+            let check = |ok: bool, format: &Format| {
+                if ok {
+                    styles.cell.clone()
+                } else {
+                    format.clone()
+                }
+            };
+
+            ws.write_string_with_format(row, 0, period.label(), &styles.cell)?;
+            ws.write_number_with_format(
+                row,
+                1,
+                balance.total_hours,
+                &check(balance.total_hours <= period.hour_quota as f64, &alert),
+            )?;
+            ws.write_number_with_format(
+                row,
+                2,
+                balance.ch_count,
+                &check(balance.ch_count == PeriodBalance::EXPECTED_CH, &warning),
+            )?;
+            ws.write_number_with_format(
+                row,
+                3,
+                balance.rh_count,
+                &check(balance.rh_count == PeriodBalance::EXPECTED_RH, &warning),
+            )?;
+            ws.write_number_with_format(
+                row,
+                4,
+                balance.cv_count,
+                &check(balance.cv_count == PeriodBalance::EXPECTED_CV, &warning),
+            )?;
+            ws.write_number_with_format(row, 5, balance.rr_count, &styles.cell)?;
+            ws.write_number_with_format(row, 6, balance.holidays_worked, &styles.cell)?;
+            let status_format = if status == STATUS_OK {
+                styles.cell.clone().set_background_color(OK_FILL)
+            } else {
+                alert.clone()
+            };
+            ws.write_string_with_format(row, 7, &status, &status_format)?;
+            row += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Header row starting at column A
+    fn write_headers(
+        ws: &mut Worksheet,
+        row: u32,
+        headers: &[&str],
+        styles: &Styles,
+    ) -> Result<(), XlsxError> {
+        for (col, header) in headers.iter().enumerate() {
+            ws.write_string_with_format(row, col as u16, *header, &styles.header)?;
+        }
+        Ok(())
+    }
+
+    /// Periods of the workbook year, in order
+    fn periods_of_year(data: &PlanningWorkbook) -> Vec<&Period> {
+        let mut periods: Vec<&Period> = data
+            .periods
+            .iter()
+            .filter(|p| p.year == data.year)
+            .collect();
+        periods.sort_by_key(|p| p.number);
+        periods
+    }
+
+    /// Color of a shift type (default code color when `color_hex` is invalid)
+    fn color(shift_type: &ShiftType) -> Color {
+        Color::new(shift_type.color_hex.as_str())
+            .unwrap_or_else(|_| Color::for_shift_code(&shift_type.code))
+    }
+
+    /// Cell fill of a shift type
+    fn fill(shift_type: &ShiftType) -> XlsxColor {
+        let (r, g, b) = Self::color(shift_type).rgb();
+        XlsxColor::RGB(u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b))
+    }
+}
+
+/// Zero-based (row, column) of the day number cell on a month sheet
+///
+/// Days 1-15 start at row 4 (column B onwards), days 16-31 at row 10. The
+/// weekday letter, code and period label cells are the three rows below.
+pub fn day_position(day: u32) -> (u32, u16) {
+    if day <= 15 {
+        (3, day as u16)
+    } else {
+        (9, (day - 15) as u16)
+    }
+}
+
+/// Status of a period in the control block ("Statut" column)
+///
+/// Checks run in generate.py order and the first failure is reported.
+pub fn control_status(balance: &PeriodBalance, hour_quota: i32) -> String {
+    if balance.total_hours > hour_quota as f64 {
+        format!("⚠ >{}h", hour_quota)
+    } else if balance.ch_count != PeriodBalance::EXPECTED_CH {
+        format!("⚠ CH≠{}", PeriodBalance::EXPECTED_CH)
+    } else if balance.rh_count != PeriodBalance::EXPECTED_RH {
+        format!("⚠ RH≠{}", PeriodBalance::EXPECTED_RH)
+    } else if balance.cv_count != PeriodBalance::EXPECTED_CV {
+        format!("⚠ CV≠{}", PeriodBalance::EXPECTED_CV)
+    } else if balance.holidays_worked > 0 && balance.rr_count == 0 {
+        "⚠ RR manquant".to_string()
+    } else {
+        STATUS_OK.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use chrono::Utc;
+
+    fn colored(code: &str, category: ShiftCategory, hours: f64, color: &str) -> ShiftType {
+        ShiftType {
+            color_hex: color.to_string(),
+            ..fixtures::shift_type(code, category, hours, 0.0)
+        }
+    }
+
+    fn balance() -> PeriodBalance {
+        PeriodBalance {
+            id: Uuid::new_v4(),
+            period_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            total_hours: 152.0,
+            night_hours: 0.0,
+            ch_count: 4,
+            rh_count: 4,
+            cv_count: 1,
+            rr_count: 0,
+            cn_count: 0,
+            jc_count: 0,
+            holidays_worked: 0,
+            is_valid: true,
+            validation_errors: Vec::new(),
+            calculated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_day_position() {
+        assert_eq!(day_position(1), (3, 1));
+        assert_eq!(day_position(15), (3, 15));
+        assert_eq!(day_position(16), (9, 1));
+        assert_eq!(day_position(31), (9, 16));
+    }
+
+    #[test]
+    fn test_control_status() {
+        assert_eq!(control_status(&balance(), 160), STATUS_OK);
+        assert_eq!(
+            control_status(
+                &PeriodBalance {
+                    total_hours: 168.0,
+                    ch_count: 3,
+                    ..balance()
+                },
+                160
+            ),
+            "⚠ >160h"
+        );
+        assert_eq!(
+            control_status(
+                &PeriodBalance {
+                    ch_count: 3,
+                    ..balance()
+                },
+                160
+            ),
+            "⚠ CH≠4"
+        );
+        assert_eq!(
+            control_status(
+                &PeriodBalance {
+                    holidays_worked: 1,
+                    ..balance()
+                },
+                160
+            ),
+            "⚠ RR manquant"
+        );
+    }
+
+    #[test]
+    fn test_shift_type_fill() {
+        let custom = colored("101", ShiftCategory::Standard, 8.0, "#102030");
+        let invalid = colored("CV", ShiftCategory::Rest, 8.0, "not a color");
+
+        assert_eq!(XlsxExporter::fill(&custom), XlsxColor::RGB(0x102030));
+        assert_eq!(XlsxExporter::fill(&invalid), XlsxColor::RGB(0x96D1CC));
+    }
+
+    #[test]
+    fn test_export_workbook() {
+        let types = vec![
+            colored("101", ShiftCategory::Standard, 8.0, "FFD9E6"),
+            colored("RH", ShiftCategory::Rest, 0.0, "CCCCCC"),
+        ];
+        let user_id = Uuid::new_v4();
+        let data = PlanningWorkbook {
+            year: 2026,
+            user_id,
+            agent_name: "Dupont Marie".to_string(),
+            periods: Vec::new(),
+            schedules: vec![fixtures::schedule(
+                user_id,
+                NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+                &types[0],
+            )],
+            shift_types: types,
+            history: ShiftTypeHistory::default(),
+            holidays: Vec::new(),
+            leave: Vec::new(),
+        };
+
+        let bytes = XlsxExporter::export(&data).unwrap();
+
+        // XLSX files are zip archives
+        assert_eq!(&bytes[..2], b"PK");
+        assert_eq!(XlsxExporter::file_name(2026), "planning_2026.xlsx");
+    }
+}
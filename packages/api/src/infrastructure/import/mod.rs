@@ -0,0 +1,7 @@
+//! Import Infrastructure
+//!
+//! Planning files read into the database.
+
+pub mod xlsx;
+
+pub use xlsx::XlsxImporter;
@@ -0,0 +1,194 @@
+//! XLSX Planning Import
+//!
+//! Reads workbooks shaped like `planning_{year}.xlsx` (generate.py or the
+//! native export): the code row of each month sheet calendar, the agent
+//! and the year from the CONFIGURATION sheet.
+
+use std::io::Cursor;
+
+use calamine::{open_workbook_from_rs, Data, Range, Reader, Xlsx};
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::domain::services::import_planner::ImportedDay;
+use crate::infrastructure::export::xlsx::{day_position, MONTHS};
+
+/// Content of a planning workbook
+#[derive(Debug, Clone)]
+pub struct ImportedWorkbook {
+    pub year: i32,
+    /// Agent named on the CONFIGURATION sheet, when present
+    pub agent: Option<String>,
+    /// Non-empty code cells, in date order
+    pub days: Vec<ImportedDay>,
+}
+
+/// Import error types
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Cannot read workbook: {0}")]
+    Workbook(#[from] calamine::XlsxError),
+
+    #[error("Year not found in the workbook")]
+    MissingYear,
+
+    #[error("No month sheet found")]
+    NoMonthSheets,
+}
+
+/// XLSX planning importer
+pub struct XlsxImporter;
+
+impl XlsxImporter {
+    /// Read a workbook
+    ///
+    /// `year` overrides the year written in the workbook (CONFIGURATION!B4,
+    /// or the title of the first month sheet).
+    pub fn read(bytes: &[u8], year: Option<i32>) -> Result<ImportedWorkbook, ImportError> {
+        let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))?;
+
+        let config = workbook.worksheet_range("CONFIGURATION").ok();
+        let agent = config.as_ref().and_then(|range| {
+            let label = range.get_value((2, 0)).and_then(cell_text)?;
+            (label == "Agent")
+                .then(|| range.get_value((2, 1)).and_then(cell_text))
+                .flatten()
+        });
+
+        let mut months = Vec::new();
+        for (index, name) in MONTHS.iter().enumerate() {
+            if let Ok(range) = workbook.worksheet_range(name) {
+                months.push((index as u32 + 1, *name, range));
+            }
+        }
+        if months.is_empty() {
+            return Err(ImportError::NoMonthSheets);
+        }
+
+        let year = year
+            .or_else(|| {
+                config
+                    .as_ref()
+                    .and_then(|range| range.get_value((3, 1)))
+                    .and_then(cell_text)
+                    .and_then(|text| text.parse().ok())
+            })
+            .or_else(|| {
+                // Month sheet title, e.g. "JANVIER 2026"
+                months.iter().find_map(|(_, _, range)| {
+                    range
+                        .get_value((0, 0))
+                        .and_then(cell_text)
+                        .and_then(|title| title.split_whitespace().last()?.parse().ok())
+                })
+            })
+            .ok_or(ImportError::MissingYear)?;
+
+        let mut days = Vec::new();
+        for (month, name, range) in &months {
+            days.extend(Self::read_month(year, *month, name, range));
+        }
+
+        Ok(ImportedWorkbook { year, agent, days })
+    }
+
+    /// Code cells of a month sheet
+    fn read_month(year: i32, month: u32, name: &str, range: &Range<Data>) -> Vec<ImportedDay> {
+        let mut days = Vec::new();
+        let Some(mut date) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            return days;
+        };
+
+        while date.month() == month {
+            let (row, col) = day_position(date.day());
+            let code = range.get_value((row + 2, col as u32)).and_then(cell_text);
+            if let Some(code) = code {
+                days.push(ImportedDay {
+                    date,
+                    code,
+                    cell: format!("{}!{}{}", name, column_letter(col), row + 3),
+                });
+            }
+            date += Duration::days(1);
+        }
+
+        days
+    }
+}
+
+/// Text of a cell (codes such as 101 may be stored as numbers)
+fn cell_text(data: &Data) -> Option<String> {
+    let text = match data {
+        Data::String(s) => s.trim().to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Float(f) if f.fract() == 0.0 => (*f as i64).to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Column letter of a zero-based column index (A-Z)
+fn column_letter(col: u16) -> char {
+    (b'A' + col as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_xlsxwriter::Workbook;
+
+    #[test]
+    fn test_reads_month_codes() {
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("Mars").unwrap();
+        sheet.write_string(0, 0, "MARS 2026").unwrap();
+        // 2 March: row 6, column C; 16 March: row 12, column B
+        sheet.write_number(5, 2, 101).unwrap();
+        sheet.write_string(11, 1, " RH ").unwrap();
+        let bytes = workbook.save_to_buffer().unwrap();
+
+        let imported = XlsxImporter::read(&bytes, None).unwrap();
+
+        assert_eq!(imported.year, 2026);
+        assert_eq!(imported.agent, None);
+        assert_eq!(imported.days.len(), 2);
+        assert_eq!(imported.days[0].code, "101");
+        assert_eq!(
+            imported.days[0].date,
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+        assert_eq!(imported.days[0].cell, "Mars!C6");
+        assert_eq!(imported.days[1].code, "RH");
+        assert_eq!(imported.days[1].cell, "Mars!B12");
+    }
+
+    #[test]
+    fn test_reads_configuration() {
+        let mut workbook = Workbook::new();
+        let config = workbook.add_worksheet();
+        config.set_name("CONFIGURATION").unwrap();
+        config.write_string(2, 0, "Agent").unwrap();
+        config.write_string(2, 1, "Dupont Marie").unwrap();
+        config.write_number(3, 1, 2025).unwrap();
+        workbook.add_worksheet().set_name("Janvier").unwrap();
+        let bytes = workbook.save_to_buffer().unwrap();
+
+        let imported = XlsxImporter::read(&bytes, None).unwrap();
+
+        assert_eq!(imported.year, 2025);
+        assert_eq!(imported.agent.as_deref(), Some("Dupont Marie"));
+        assert_eq!(XlsxImporter::read(&bytes, Some(2024)).unwrap().year, 2024);
+    }
+
+    #[test]
+    fn test_rejects_workbook_without_months() {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_name("Feuil1").unwrap();
+        let bytes = workbook.save_to_buffer().unwrap();
+
+        assert!(matches!(
+            XlsxImporter::read(&bytes, None),
+            Err(ImportError::NoMonthSheets)
+        ));
+    }
+}
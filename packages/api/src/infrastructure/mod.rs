@@ -4,7 +4,11 @@
 
 pub mod auth;
 pub mod config;
+pub mod events;
+pub mod export;
+pub mod import;
 pub mod persistence;
+pub mod presence;
 pub mod state;
 
 // Re-export commonly used types
@@ -0,0 +1,237 @@
+//! Period Balance Persistence
+//!
+//! Incremental recalculation of the cached `period_balances` rows.
+//! Only the (agent, period) pairs touched by a schedule write are recomputed.
+//! Like `planning_data`, this works on the pool directly and is tested
+//! against Postgres.
+
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::period::PeriodBalance;
+use crate::domain::entities::Period;
+use crate::domain::services::BalanceCalculator;
+
+use super::planning_data;
+
+/// Recalculate and persist balances affected by schedule changes
+///
+/// `changes` lists the (user_id, date) of every written schedule entry.
+pub async fn recalculate(
+    db: &PgPool,
+    org_id: Uuid,
+    changes: &[(Uuid, NaiveDate)],
+) -> Result<Vec<PeriodBalance>, sqlx::Error> {
+    let (Some(start), Some(end)) = (
+        changes.iter().map(|(_, date)| *date).min(),
+        changes.iter().map(|(_, date)| *date).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let periods = find_overlapping_periods(db, org_id, start, end).await?;
+
+    let affected: BTreeSet<(usize, Uuid)> = changes
+        .iter()
+        .filter_map(|(user_id, date)| {
+            periods
+                .iter()
+                .position(|p| p.contains_date(*date))
+                .map(|index| (index, *user_id))
+        })
+        .collect();
+
+    if affected.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let shift_types = planning_data::find_shift_types(db, org_id).await?;
+    let history = planning_data::find_shift_type_history(db, org_id).await?;
+    let calculator = BalanceCalculator::new().with_history(history);
+    let mut balances = Vec::with_capacity(affected.len());
+
+    for (index, period) in periods.iter().enumerate() {
+        let users: Vec<Uuid> = affected
+            .iter()
+            .filter(|(i, _)| *i == index)
+            .map(|(_, user_id)| *user_id)
+            .collect();
+        if users.is_empty() {
+            continue;
+        }
+
+        let holidays =
+            planning_data::find_holiday_dates(db, org_id, period.start_date, period.end_date)
+                .await?;
+
+        for user_id in users {
+            let schedules = planning_data::find_schedules(
+                db,
+                org_id,
+                period.start_date,
+                period.end_date,
+                Some(user_id),
+            )
+            .await?;
+
+            let balance =
+                calculator.calculate(period, user_id, &schedules, &shift_types, &holidays);
+            balances.push(save(db, &balance).await?);
+        }
+    }
+
+    Ok(balances)
+}
+
+/// Find periods of an organization overlapping a date range
+async fn find_overlapping_periods(
+    db: &PgPool,
+    org_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<Period>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, organization_id, year, number, start_date, end_date, hour_quota, closed_at, created_at
+        FROM periods
+        WHERE organization_id = $1 AND end_date >= $2 AND start_date <= $3
+        ORDER BY start_date
+        "#,
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(db)
+    .await
+}
+
+/// Create or update a cached balance
+pub async fn save(db: &PgPool, balance: &PeriodBalance) -> Result<PeriodBalance, sqlx::Error> {
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO period_balances (
+            period_id, user_id,
+            total_hours, night_hours,
+            ch_count, rh_count, cv_count, rr_count, cn_count, jc_count,
+            holidays_worked, is_valid, validation_errors, calculated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        ON CONFLICT (period_id, user_id) DO UPDATE
+        SET total_hours = EXCLUDED.total_hours,
+            night_hours = EXCLUDED.night_hours,
+            ch_count = EXCLUDED.ch_count,
+            rh_count = EXCLUDED.rh_count,
+            cv_count = EXCLUDED.cv_count,
+            rr_count = EXCLUDED.rr_count,
+            cn_count = EXCLUDED.cn_count,
+            jc_count = EXCLUDED.jc_count,
+            holidays_worked = EXCLUDED.holidays_worked,
+            is_valid = EXCLUDED.is_valid,
+            validation_errors = EXCLUDED.validation_errors,
+            calculated_at = EXCLUDED.calculated_at
+        RETURNING id
+        "#,
+    )
+    .bind(balance.period_id)
+    .bind(balance.user_id)
+    .bind(balance.total_hours)
+    .bind(balance.night_hours)
+    .bind(balance.ch_count)
+    .bind(balance.rh_count)
+    .bind(balance.cv_count)
+    .bind(balance.rr_count)
+    .bind(balance.cn_count)
+    .bind(balance.jc_count)
+    .bind(balance.holidays_worked)
+    .bind(balance.is_valid)
+    .bind(Json(&balance.validation_errors))
+    .bind(balance.calculated_at)
+    .fetch_one(db)
+    .await?;
+
+    Ok(PeriodBalance {
+        id,
+        ..balance.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::create_schedule::{
+        CreateScheduleCommand, CreateScheduleHandler,
+    };
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::schedule::CreateSchedule;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::ShiftType;
+    use crate::infrastructure::persistence::repositories;
+    use crate::infrastructure::persistence::testing::TestDatabase;
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn test_recalculates_the_touched_periods_on_postgres() {
+        let test_db = TestDatabase::new().await;
+        let org_id = test_db.organization().await;
+        let ports = repositories::postgres(&test_db.db);
+
+        let shift_type = ports
+            .shift_types
+            .create(&ShiftType {
+                organization_id: org_id,
+                ..fixtures::shift_type("101", ShiftCategory::Standard, 8.0, 2.0)
+            })
+            .await
+            .unwrap();
+        let agent = ports
+            .users
+            .create(&fixtures::agent(org_id, "Marie", "Dupont"))
+            .await
+            .unwrap();
+        let periods = ports.periods.generate_for_year(org_id, 2026).await.unwrap();
+        let (p1, p2) = (&periods[0], &periods[1]);
+
+        let entries = [p1.start_date, p1.end_date, p2.start_date]
+            .into_iter()
+            .map(|date| CreateSchedule {
+                user_id: agent.id,
+                shift_type_id: Some(shift_type.id),
+                date,
+                notes: None,
+            })
+            .collect();
+        CreateScheduleHandler::new(ports.clone())
+            .execute(&CreateScheduleCommand::bulk(org_id, None, entries))
+            .await
+            .unwrap();
+
+        // Only P1 was touched
+        let changes = [(agent.id, p1.start_date), (agent.id, p1.end_date)];
+        let balances = recalculate(&test_db.db, org_id, &changes).await.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].period_id, p1.id);
+        assert_eq!(balances[0].total_hours, 16.0);
+        assert_eq!(balances[0].night_hours, 4.0);
+
+        let (stored,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM period_balances WHERE user_id = $1")
+                .bind(agent.id)
+                .fetch_one(&test_db.db)
+                .await
+                .unwrap();
+        assert_eq!(stored, 1);
+
+        // Dates outside every period recalculate nothing
+        let before_p1 = p1.start_date - chrono::Duration::days(1);
+        assert!(recalculate(&test_db.db, org_id, &[(agent.id, before_p1)])
+            .await
+            .unwrap()
+            .is_empty());
+
+        test_db.drop().await;
+    }
+}
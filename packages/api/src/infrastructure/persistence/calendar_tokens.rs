@@ -0,0 +1,128 @@
+//! Calendar Token Persistence
+//!
+//! ICS feed tokens. Secrets are random and only their SHA-256 is stored,
+//! so a leaked database does not expose working feed URLs.
+
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::CalendarToken;
+
+/// Generate a new secret (64 hex characters)
+pub fn generate_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// SHA-256 of a secret, as stored in `token_hash`
+pub fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Create a token; returns it with its secret
+pub async fn create(
+    db: &PgPool,
+    org_id: Uuid,
+    user_id: Option<Uuid>,
+    label: Option<&str>,
+) -> Result<(CalendarToken, String), sqlx::Error> {
+    let secret = generate_secret();
+    let token = sqlx::query_as(
+        r#"
+        INSERT INTO calendar_tokens (organization_id, user_id, token_hash, label)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, organization_id, user_id, label, created_at, last_used_at, revoked_at
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .bind(hash_secret(&secret))
+    .bind(label)
+    .fetch_one(db)
+    .await?;
+
+    Ok((token, secret))
+}
+
+/// Find the active token of a secret and record its use
+pub async fn find_by_secret(
+    db: &PgPool,
+    secret: &str,
+) -> Result<Option<CalendarToken>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        UPDATE calendar_tokens
+        SET last_used_at = NOW()
+        WHERE token_hash = $1 AND revoked_at IS NULL
+        RETURNING id, organization_id, user_id, label, created_at, last_used_at, revoked_at
+        "#,
+    )
+    .bind(hash_secret(secret))
+    .fetch_optional(db)
+    .await
+}
+
+/// List the tokens of an organization, optionally for a single user
+pub async fn list(
+    db: &PgPool,
+    org_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<CalendarToken>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, organization_id, user_id, label, created_at, last_used_at, revoked_at
+        FROM calendar_tokens
+        WHERE organization_id = $1
+          AND ($2::UUID IS NULL OR user_id = $2)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Revoke a token, optionally only if it belongs to a user; returns false
+/// if it does not exist or was already revoked
+pub async fn revoke(
+    db: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE calendar_tokens
+        SET revoked_at = NOW()
+        WHERE id = $1 AND organization_id = $2 AND revoked_at IS NULL
+          AND ($3::UUID IS NULL OR user_id = $3)
+        "#,
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(user_id)
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_hash() {
+        let secret = generate_secret();
+
+        assert_eq!(secret.len(), 64);
+        assert_ne!(secret, generate_secret());
+        assert_eq!(hash_secret(&secret).len(), 64);
+        assert_eq!(hash_secret(&secret), hash_secret(&secret));
+        assert_eq!(
+            hash_secret("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Holiday Persistence
+//!
+//! Public holidays of an organization, and the denormalized
+//! `schedules.is_holiday` flag kept in sync with them.
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::PublicHoliday;
+use crate::domain::services::HolidayCalculator;
+
+const COLUMNS: &str = r#"
+    id, organization_id, date, name,
+    COALESCE(is_moveable, false) AS is_moveable, is_custom,
+    COALESCE(created_at, NOW()) AS created_at
+"#;
+
+/// Find the holidays of an organization, optionally limited to a year
+pub async fn find_all(
+    db: &PgPool,
+    org_id: Uuid,
+    year: Option<i32>,
+) -> Result<Vec<PublicHoliday>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        r#"
+        SELECT {}
+        FROM holidays
+        WHERE organization_id = $1 AND ($2::INT IS NULL OR EXTRACT(YEAR FROM date) = $2)
+        ORDER BY date
+        "#,
+        COLUMNS
+    ))
+    .bind(org_id)
+    .bind(year)
+    .fetch_all(db)
+    .await
+}
+
+/// Create a custom holiday
+///
+/// Returns `None` if the organization already has a holiday on that date.
+pub async fn create(
+    db: &PgPool,
+    org_id: Uuid,
+    date: NaiveDate,
+    name: &str,
+) -> Result<Option<PublicHoliday>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        r#"
+        INSERT INTO holidays (organization_id, date, name, is_moveable, is_custom)
+        VALUES ($1, $2, $3, false, true)
+        ON CONFLICT (organization_id, date) DO NOTHING
+        RETURNING {}
+        "#,
+        COLUMNS
+    ))
+    .bind(org_id)
+    .bind(date)
+    .bind(name)
+    .fetch_optional(db)
+    .await
+}
+
+/// Find a holiday of an organization
+pub async fn find(
+    db: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+) -> Result<Option<PublicHoliday>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {} FROM holidays WHERE organization_id = $1 AND id = $2",
+        COLUMNS
+    ))
+    .bind(org_id)
+    .bind(id)
+    .fetch_optional(db)
+    .await
+}
+
+/// Delete a holiday of an organization
+pub async fn delete(db: &PgPool, org_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM holidays WHERE organization_id = $1 AND id = $2")
+        .bind(org_id)
+        .bind(id)
+        .execute(db)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert the calculated holidays of the given years
+///
+/// Dates that already have a holiday (generated or custom) are kept as
+/// they are. Returns the created holidays.
+pub async fn generate(
+    db: &PgPool,
+    org_id: Uuid,
+    years: &[i32],
+) -> Result<Vec<PublicHoliday>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let mut created = Vec::new();
+
+    for year in years {
+        for holiday in HolidayCalculator::calculate_holidays(*year) {
+            let row: Option<PublicHoliday> = sqlx::query_as(&format!(
+                r#"
+                INSERT INTO holidays (organization_id, date, name, is_moveable, is_custom)
+                VALUES ($1, $2, $3, $4, false)
+                ON CONFLICT (organization_id, date) DO NOTHING
+                RETURNING {}
+                "#,
+                COLUMNS
+            ))
+            .bind(org_id)
+            .bind(holiday.date)
+            .bind(&holiday.name)
+            .bind(holiday.is_moveable)
+            .fetch_optional(&mut *tx)
+            .await?;
+            created.extend(row);
+        }
+    }
+
+    tx.commit().await?;
+    Ok(created)
+}
+
+/// Resync `schedules.is_holiday` on the given dates with the holidays table
+///
+/// Dates in closed periods are left untouched. Returns the (user_id, date)
+/// of every schedule whose flag changed.
+pub async fn refresh_schedules(
+    db: &PgPool,
+    org_id: Uuid,
+    dates: &[NaiveDate],
+) -> Result<Vec<(Uuid, NaiveDate)>, sqlx::Error> {
+    if dates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    sqlx::query_as(
+        r#"
+        UPDATE schedules s
+        SET is_holiday = NOT COALESCE(s.is_holiday, false), updated_at = NOW()
+        WHERE s.organization_id = $1
+          AND s.date = ANY($2)
+          AND COALESCE(s.is_holiday, false) <> EXISTS (
+              SELECT 1 FROM holidays h
+              WHERE h.organization_id = s.organization_id AND h.date = s.date
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM periods p
+              WHERE p.organization_id = s.organization_id
+                AND s.date BETWEEN p.start_date AND p.end_date
+                AND p.closed_at IS NOT NULL
+          )
+        RETURNING s.user_id, s.date
+        "#,
+    )
+    .bind(org_id)
+    .bind(dates)
+    .fetch_all(db)
+    .await
+}
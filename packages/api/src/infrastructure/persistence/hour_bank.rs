@@ -0,0 +1,196 @@
+//! Hour Bank Persistence
+//!
+//! Hour bank ledger queries and period closing.
+
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::domain::entities::hour_bank::{HourBankEntry, HourBankPolicy};
+use crate::domain::entities::Period;
+use crate::domain::services::HourBankCalculator;
+
+use super::balances;
+
+/// Load the hour bank policy of an organization (defaults when not configured)
+pub async fn find_policy(db: &PgPool, org_id: Uuid) -> Result<HourBankPolicy, sqlx::Error> {
+    let config: Option<(serde_json::Value,)> =
+        sqlx::query_as("SELECT config FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(db)
+            .await?;
+
+    Ok(config
+        .and_then(|(c,)| c.get("hourBank").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Current running balance of an agent (0 before the first closing)
+pub async fn find_balance(db: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<f64, sqlx::Error> {
+    find_balance_before(db, org_id, user_id, NaiveDate::MAX).await
+}
+
+/// Running balance of an agent after the last period of the organization
+/// closed before `date`
+pub async fn find_balance_before<'e, E>(
+    executor: E,
+    org_id: Uuid,
+    user_id: Uuid,
+    date: NaiveDate,
+) -> Result<f64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    let row: Option<(f64,)> = sqlx::query_as(
+        r#"
+        SELECT h.balance::FLOAT8
+        FROM hour_bank_entries h
+        JOIN periods p ON h.period_id = p.id
+        WHERE h.user_id = $1 AND p.organization_id = $2 AND p.start_date < $3
+        ORDER BY p.start_date DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(org_id)
+    .bind(date)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.map(|r| r.0).unwrap_or(0.0))
+}
+
+/// Hour bank entries recorded when a period of the organization was closed
+pub async fn find_period_entries(
+    db: &PgPool,
+    org_id: Uuid,
+    period_id: Uuid,
+) -> Result<Vec<HourBankEntry>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            id, organization_id, user_id, period_id,
+            total_hours::FLOAT8 AS total_hours, hour_quota, delta::FLOAT8 AS delta,
+            paid_out::FLOAT8 AS paid_out, forfeited::FLOAT8 AS forfeited,
+            balance::FLOAT8 AS balance, created_at
+        FROM hour_bank_entries
+        WHERE organization_id = $1 AND period_id = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(period_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Outcome of a period closing
+#[derive(Debug)]
+pub enum ClosePeriod {
+    /// Hour bank entries recorded for the period
+    Closed(Vec<HourBankEntry>),
+    AlreadyClosed,
+    /// An earlier period of the organization is still open (e.g. "P13 of 2025"):
+    /// its hour bank must be recorded first to carry the balance over
+    EarlierPeriodOpen(String),
+}
+
+/// Earliest open period of the organization starting before `period`
+async fn find_earlier_open(db: &PgPool, period: &Period) -> Result<Option<String>, sqlx::Error> {
+    let row: Option<(i32, i32)> = sqlx::query_as(
+        r#"
+        SELECT number, year
+        FROM periods
+        WHERE organization_id = $1 AND start_date < $2 AND closed_at IS NULL
+        ORDER BY start_date
+        LIMIT 1
+        "#,
+    )
+    .bind(period.organization_id)
+    .bind(period.start_date)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(row.map(|(number, year)| format!("P{} of {}", number, year)))
+}
+
+/// Close a period: recalculate balances and record the hour bank of every active agent
+///
+/// Periods are closed in order, so each entry carries over the balance of
+/// the period before it.
+pub async fn close_period(
+    db: &PgPool,
+    period: &Period,
+    closed_by: Option<Uuid>,
+) -> Result<ClosePeriod, sqlx::Error> {
+    if let Some(label) = find_earlier_open(db, period).await? {
+        return Ok(ClosePeriod::EarlierPeriodOpen(label));
+    }
+
+    let users: Vec<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM users WHERE organization_id = $1 AND is_active = true")
+            .bind(period.organization_id)
+            .fetch_all(db)
+            .await?;
+
+    let changes: Vec<_> = users.iter().map(|(id,)| (*id, period.start_date)).collect();
+    let period_balances = balances::recalculate(db, period.organization_id, &changes).await?;
+    let calculator =
+        HourBankCalculator::with_policy(find_policy(db, period.organization_id).await?);
+
+    let mut tx = db.begin().await?;
+
+    let closed = sqlx::query(
+        "UPDATE periods SET closed_at = NOW(), closed_by = $2 WHERE id = $1 AND closed_at IS NULL",
+    )
+    .bind(period.id)
+    .bind(closed_by)
+    .execute(&mut *tx)
+    .await?;
+
+    if closed.rows_affected() == 0 {
+        return Ok(ClosePeriod::AlreadyClosed);
+    }
+
+    // Carried-over balances are read in the transaction that marks the
+    // period closed, so they match the entries it records
+    let mut entries = Vec::with_capacity(period_balances.len());
+    for balance in &period_balances {
+        let previous = find_balance_before(
+            &mut *tx,
+            period.organization_id,
+            balance.user_id,
+            period.start_date,
+        )
+        .await?;
+        entries.push(calculator.close(period, balance, previous));
+    }
+
+    for entry in &entries {
+        sqlx::query(
+            r#"
+            INSERT INTO hour_bank_entries (
+                id, organization_id, user_id, period_id,
+                total_hours, hour_quota, delta, paid_out, forfeited, balance, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.organization_id)
+        .bind(entry.user_id)
+        .bind(entry.period_id)
+        .bind(entry.total_hours)
+        .bind(entry.hour_quota)
+        .bind(entry.delta)
+        .bind(entry.paid_out)
+        .bind(entry.forfeited)
+        .bind(entry.balance)
+        .bind(entry.created_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(ClosePeriod::Closed(entries))
+}
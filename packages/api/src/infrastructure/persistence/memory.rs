@@ -0,0 +1,788 @@
+//! In-Memory Repositories
+//!
+//! Adapters implementing the application repository ports without a
+//! database, for tests and demos. They enforce the same uniqueness rules
+//! as the Postgres schema; nothing is persisted.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::application::ports::{
+    AuditLogRepository, HolidayRepository, PeriodRepository, RepoResult, Repositories,
+    RepositoryError, RoleRepository, ScheduleRepository, ShiftTypeRepository, UserRepository,
+};
+use crate::domain::entities::{
+    AuditLog, Period, Role, Schedule, ShiftCodeGrammar, ShiftType, ShiftTypeVersion, User,
+};
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::domain::services::PeriodCalculator;
+
+/// In-memory adapters, kept concrete so that tests can seed them
+#[derive(Clone, Default)]
+pub struct InMemoryRepositories {
+    pub users: Arc<InMemoryUserRepository>,
+    pub roles: Arc<InMemoryRoleRepository>,
+    pub schedules: Arc<InMemoryScheduleRepository>,
+    pub shift_types: Arc<InMemoryShiftTypeRepository>,
+    pub periods: Arc<InMemoryPeriodRepository>,
+    pub holidays: Arc<InMemoryHolidayRepository>,
+    pub audit_logs: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryRepositories {
+    /// Create empty repositories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same adapters behind the repository ports
+    pub fn ports(&self) -> Repositories {
+        Repositories {
+            users: self.users.clone(),
+            roles: self.roles.clone(),
+            schedules: self.schedules.clone(),
+            shift_types: self.shift_types.clone(),
+            periods: self.periods.clone(),
+            holidays: self.holidays.clone(),
+            audit_logs: self.audit_logs.clone(),
+        }
+    }
+}
+
+/// In-memory user repository
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: RwLock<HashMap<Uuid, User>>,
+}
+
+impl InMemoryUserRepository {
+    fn check_unique(users: &HashMap<Uuid, User>, user: &User) -> RepoResult<()> {
+        let clash = users.values().any(|u| {
+            u.id != user.id
+                && u.organization_id == user.organization_id
+                && (u.email.eq_ignore_ascii_case(&user.email)
+                    || (u.matricule.is_some() && u.matricule == user.matricule))
+        });
+
+        if clash {
+            return Err(RepositoryError::Duplicate(format!("user {}", user.email)));
+        }
+        Ok(())
+    }
+
+    fn sorted(mut users: Vec<User>) -> Vec<User> {
+        users.sort_by(|a, b| (&a.last_name, &a.first_name).cmp(&(&b.last_name, &b.first_name)));
+        users
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<User>> {
+        Ok(self
+            .users
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|u| u.organization_id == org_id)
+            .cloned())
+    }
+
+    async fn find_by_email(&self, org_id: Uuid, email: &str) -> RepoResult<Option<User>> {
+        Ok(self
+            .users
+            .read()
+            .unwrap()
+            .values()
+            .find(|u| u.organization_id == org_id && u.email.eq_ignore_ascii_case(email))
+            .cloned())
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<User>> {
+        let users = self.users.read().unwrap();
+        Ok(Self::sorted(
+            users
+                .values()
+                .filter(|u| u.organization_id == org_id)
+                .cloned()
+                .collect(),
+        ))
+    }
+
+    async fn find_active(&self, org_id: Uuid) -> RepoResult<Vec<User>> {
+        let users = self.users.read().unwrap();
+        Ok(Self::sorted(
+            users
+                .values()
+                .filter(|u| u.organization_id == org_id && u.is_active)
+                .cloned()
+                .collect(),
+        ))
+    }
+
+    async fn create(&self, user: &User) -> RepoResult<User> {
+        let mut users = self.users.write().unwrap();
+        Self::check_unique(&users, user)?;
+        users.insert(user.id, user.clone());
+        Ok(user.clone())
+    }
+
+    async fn update(&self, user: &User) -> RepoResult<User> {
+        let mut users = self.users.write().unwrap();
+        if users
+            .get(&user.id)
+            .is_none_or(|u| u.organization_id != user.organization_id)
+        {
+            return Err(RepositoryError::NotFound(format!("user {}", user.id)));
+        }
+        Self::check_unique(&users, user)?;
+
+        let updated = User {
+            updated_at: Utc::now(),
+            ..user.clone()
+        };
+        users.insert(user.id, updated.clone());
+        Ok(updated)
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let mut users = self.users.write().unwrap();
+        let user = users
+            .get_mut(&id)
+            .filter(|u| u.organization_id == org_id)
+            .ok_or_else(|| RepositoryError::NotFound(format!("user {}", id)))?;
+        user.is_active = false;
+        user.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn count_by_role(&self, org_id: Uuid, role_id: Uuid) -> RepoResult<i64> {
+        let users = self.users.read().unwrap();
+        Ok(users
+            .values()
+            .filter(|u| u.organization_id == org_id && u.role_id == Some(role_id))
+            .count() as i64)
+    }
+}
+
+/// In-memory role repository
+#[derive(Default)]
+pub struct InMemoryRoleRepository {
+    roles: RwLock<HashMap<Uuid, Role>>,
+}
+
+#[async_trait]
+impl RoleRepository for InMemoryRoleRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Role>> {
+        Ok(self
+            .roles
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|r| r.organization_id == org_id)
+            .cloned())
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<Role>> {
+        let mut roles: Vec<Role> = self
+            .roles
+            .read()
+            .unwrap()
+            .values()
+            .filter(|r| r.organization_id == org_id)
+            .cloned()
+            .collect();
+        roles.sort_by(|a, b| (!a.is_system, &a.name).cmp(&(!b.is_system, &b.name)));
+        Ok(roles)
+    }
+
+    async fn create(&self, role: &Role) -> RepoResult<Role> {
+        let mut roles = self.roles.write().unwrap();
+        if roles
+            .values()
+            .any(|r| r.organization_id == role.organization_id && r.name == role.name)
+        {
+            return Err(RepositoryError::Duplicate(format!("role {}", role.name)));
+        }
+        roles.insert(role.id, role.clone());
+        Ok(role.clone())
+    }
+
+    async fn update(&self, role: &Role) -> RepoResult<Role> {
+        let mut roles = self.roles.write().unwrap();
+        let stored = roles
+            .get_mut(&role.id)
+            .filter(|r| r.organization_id == role.organization_id)
+            .ok_or_else(|| RepositoryError::NotFound(format!("role {}", role.id)))?;
+        stored.display_name = role.display_name.clone();
+        stored.permissions = role.permissions.clone();
+        Ok(stored.clone())
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let mut roles = self.roles.write().unwrap();
+        if roles.get(&id).is_none_or(|r| r.organization_id != org_id) {
+            return Err(RepositoryError::NotFound(format!("role {}", id)));
+        }
+        roles.remove(&id);
+        Ok(())
+    }
+}
+
+/// In-memory schedule repository (one entry per user per day)
+#[derive(Default)]
+pub struct InMemoryScheduleRepository {
+    schedules: RwLock<HashMap<Uuid, Schedule>>,
+}
+
+impl InMemoryScheduleRepository {
+    fn find(&self, filter: impl Fn(&Schedule) -> bool) -> Vec<Schedule> {
+        let mut schedules: Vec<_> = self
+            .schedules
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| filter(s))
+            .cloned()
+            .collect();
+        schedules.sort_by_key(|s| (s.user_id, s.date));
+        schedules
+    }
+
+    fn upsert_into(schedules: &mut HashMap<Uuid, Schedule>, schedule: &Schedule) -> Schedule {
+        let existing = schedules
+            .values()
+            .find(|s| s.user_id == schedule.user_id && s.date == schedule.date)
+            .cloned();
+
+        let written = match existing {
+            Some(existing) => Schedule {
+                id: existing.id,
+                created_by: existing.created_by,
+                created_at: existing.created_at,
+                updated_at: Utc::now(),
+                ..schedule.clone()
+            },
+            None => schedule.clone(),
+        };
+        schedules.insert(written.id, written.clone());
+        written
+    }
+}
+
+#[async_trait]
+impl ScheduleRepository for InMemoryScheduleRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Schedule>> {
+        Ok(self
+            .schedules
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|s| s.organization_id == org_id)
+            .cloned())
+    }
+
+    async fn find_by_user_date(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> RepoResult<Option<Schedule>> {
+        Ok(self
+            .find(|s| s.organization_id == org_id && s.user_id == user_id && s.date == date)
+            .into_iter()
+            .next())
+    }
+
+    async fn find_by_user_range(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Schedule>> {
+        Ok(self.find(|s| {
+            s.organization_id == org_id && s.user_id == user_id && s.date >= start && s.date <= end
+        }))
+    }
+
+    async fn find_by_org_range(
+        &self,
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Schedule>> {
+        Ok(self.find(|s| s.organization_id == org_id && s.date >= start && s.date <= end))
+    }
+
+    async fn upsert(&self, schedule: &Schedule) -> RepoResult<Schedule> {
+        let mut schedules = self.schedules.write().unwrap();
+        Ok(Self::upsert_into(&mut schedules, schedule))
+    }
+
+    async fn bulk_upsert(&self, schedules: &[Schedule]) -> RepoResult<Vec<Schedule>> {
+        let mut stored = self.schedules.write().unwrap();
+        Ok(schedules
+            .iter()
+            .map(|s| Self::upsert_into(&mut stored, s))
+            .collect())
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let mut schedules = self.schedules.write().unwrap();
+        if schedules
+            .get(&id)
+            .is_none_or(|s| s.organization_id != org_id)
+        {
+            return Err(RepositoryError::NotFound(format!("schedule {}", id)));
+        }
+        schedules.remove(&id);
+        Ok(())
+    }
+
+    async fn count_by_shift_type(&self, org_id: Uuid, shift_type_id: Uuid) -> RepoResult<i64> {
+        Ok(self
+            .find(|s| s.organization_id == org_id && s.shift_type_id == Some(shift_type_id))
+            .len() as i64)
+    }
+
+    async fn find_by_shift_type(
+        &self,
+        org_id: Uuid,
+        shift_type_id: Uuid,
+        from: Option<NaiveDate>,
+    ) -> RepoResult<Vec<Schedule>> {
+        Ok(self.find(|s| {
+            s.organization_id == org_id
+                && s.shift_type_id == Some(shift_type_id)
+                && from.is_none_or(|from| s.date >= from)
+        }))
+    }
+}
+
+/// In-memory shift type repository
+///
+/// Organizations use the default code grammar unless one is registered
+/// with `set_code_grammar`.
+#[derive(Default)]
+pub struct InMemoryShiftTypeRepository {
+    shift_types: RwLock<HashMap<Uuid, ShiftType>>,
+    versions: RwLock<Vec<ShiftTypeVersion>>,
+    grammars: RwLock<HashMap<Uuid, ShiftCodeGrammar>>,
+}
+
+impl InMemoryShiftTypeRepository {
+    /// Register the code grammar of an organization
+    pub fn set_code_grammar(&self, org_id: Uuid, grammar: ShiftCodeGrammar) {
+        self.grammars.write().unwrap().insert(org_id, grammar);
+    }
+
+    fn find(&self, filter: impl Fn(&ShiftType) -> bool) -> Vec<ShiftType> {
+        let mut shift_types: Vec<_> = self
+            .shift_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|st| filter(st))
+            .cloned()
+            .collect();
+        shift_types.sort_by(|a, b| (a.display_order, &a.code).cmp(&(b.display_order, &b.code)));
+        shift_types
+    }
+
+    fn check_unique(
+        shift_types: &HashMap<Uuid, ShiftType>,
+        shift_type: &ShiftType,
+    ) -> RepoResult<()> {
+        let clash = shift_types.values().any(|st| {
+            st.id != shift_type.id
+                && st.organization_id == shift_type.organization_id
+                && st.code == shift_type.code
+        });
+
+        if clash {
+            return Err(RepositoryError::Duplicate(format!(
+                "shift type {}",
+                shift_type.code
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShiftTypeRepository for InMemoryShiftTypeRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<ShiftType>> {
+        Ok(self
+            .shift_types
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|st| st.organization_id == org_id)
+            .cloned())
+    }
+
+    async fn find_by_code(&self, org_id: Uuid, code: &str) -> RepoResult<Option<ShiftType>> {
+        Ok(self
+            .find(|st| st.organization_id == org_id && st.code == code)
+            .into_iter()
+            .next())
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<ShiftType>> {
+        Ok(self.find(|st| st.organization_id == org_id))
+    }
+
+    async fn find_active(&self, org_id: Uuid) -> RepoResult<Vec<ShiftType>> {
+        Ok(self.find(|st| st.organization_id == org_id && st.is_active))
+    }
+
+    async fn create(&self, shift_type: &ShiftType) -> RepoResult<ShiftType> {
+        let mut shift_types = self.shift_types.write().unwrap();
+        Self::check_unique(&shift_types, shift_type)?;
+        shift_types.insert(shift_type.id, shift_type.clone());
+        Ok(shift_type.clone())
+    }
+
+    async fn update(&self, shift_type: &ShiftType) -> RepoResult<ShiftType> {
+        let mut shift_types = self.shift_types.write().unwrap();
+        if shift_types
+            .get(&shift_type.id)
+            .is_none_or(|st| st.organization_id != shift_type.organization_id)
+        {
+            return Err(RepositoryError::NotFound(format!(
+                "shift type {}",
+                shift_type.id
+            )));
+        }
+        Self::check_unique(&shift_types, shift_type)?;
+
+        let updated = ShiftType {
+            updated_at: Utc::now(),
+            ..shift_type.clone()
+        };
+        shift_types.insert(shift_type.id, updated.clone());
+        Ok(updated)
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let mut shift_types = self.shift_types.write().unwrap();
+        if shift_types
+            .get(&id)
+            .is_none_or(|st| st.organization_id != org_id)
+        {
+            return Err(RepositoryError::NotFound(format!("shift type {}", id)));
+        }
+        shift_types.remove(&id);
+        drop(shift_types);
+        self.versions
+            .write()
+            .unwrap()
+            .retain(|v| v.shift_type_id != id);
+        Ok(())
+    }
+
+    async fn find_versions(&self, org_id: Uuid) -> RepoResult<Vec<ShiftTypeVersion>> {
+        let mut versions: Vec<_> = self
+            .versions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|v| v.organization_id == org_id)
+            .cloned()
+            .collect();
+        versions.sort_by_key(|v| (v.shift_type_id, v.effective_from));
+        Ok(versions)
+    }
+
+    async fn save_version(&self, version: &ShiftTypeVersion) -> RepoResult<ShiftTypeVersion> {
+        let mut versions = self.versions.write().unwrap();
+        let written = match versions.iter_mut().find(|v| {
+            v.shift_type_id == version.shift_type_id && v.effective_from == version.effective_from
+        }) {
+            Some(existing) => {
+                *existing = ShiftTypeVersion {
+                    id: existing.id,
+                    created_at: existing.created_at,
+                    ..version.clone()
+                };
+                existing.clone()
+            }
+            None => {
+                versions.push(version.clone());
+                version.clone()
+            }
+        };
+        Ok(written)
+    }
+
+    async fn find_code_grammar(&self, org_id: Uuid) -> RepoResult<ShiftCodeGrammar> {
+        Ok(self
+            .grammars
+            .read()
+            .unwrap()
+            .get(&org_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// In-memory period repository
+///
+/// Organizations have no table here: their anchor date is registered with
+/// `set_anchor` before generating periods.
+#[derive(Default)]
+pub struct InMemoryPeriodRepository {
+    periods: RwLock<HashMap<Uuid, Period>>,
+    anchors: RwLock<HashMap<Uuid, NaiveDate>>,
+}
+
+impl InMemoryPeriodRepository {
+    /// Register the anchor date (P1 start) of an organization
+    pub fn set_anchor(&self, org_id: Uuid, anchor: NaiveDate) {
+        self.anchors.write().unwrap().insert(org_id, anchor);
+    }
+
+    /// Close a period (the Postgres adapter does it in the hour bank)
+    pub fn close(&self, id: Uuid) -> RepoResult<Period> {
+        let mut periods = self.periods.write().unwrap();
+        let period = periods
+            .get_mut(&id)
+            .ok_or_else(|| RepositoryError::NotFound(format!("period {}", id)))?;
+        period.closed_at = Some(Utc::now());
+        Ok(period.clone())
+    }
+
+    fn find(&self, filter: impl Fn(&Period) -> bool) -> Vec<Period> {
+        let mut periods: Vec<_> = self
+            .periods
+            .read()
+            .unwrap()
+            .values()
+            .filter(|p| filter(p))
+            .cloned()
+            .collect();
+        periods.sort_by_key(|p| (p.year, p.number));
+        periods
+    }
+}
+
+#[async_trait]
+impl PeriodRepository for InMemoryPeriodRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Period>> {
+        Ok(self
+            .periods
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|p| p.organization_id == org_id)
+            .cloned())
+    }
+
+    async fn find_by_date(&self, org_id: Uuid, date: NaiveDate) -> RepoResult<Option<Period>> {
+        Ok(self
+            .find(|p| p.organization_id == org_id && p.contains_date(date))
+            .into_iter()
+            .next())
+    }
+
+    async fn find_by_year(&self, org_id: Uuid, year: i32) -> RepoResult<Vec<Period>> {
+        Ok(self.find(|p| p.organization_id == org_id && p.year == year))
+    }
+
+    async fn create(&self, period: &Period) -> RepoResult<Period> {
+        let mut periods = self.periods.write().unwrap();
+        let clash = periods.values().any(|p| {
+            p.organization_id == period.organization_id
+                && p.year == period.year
+                && p.number == period.number
+        });
+        if clash {
+            return Err(RepositoryError::Duplicate(format!(
+                "period {} of {}",
+                period.label(),
+                period.year
+            )));
+        }
+
+        periods.insert(period.id, period.clone());
+        Ok(period.clone())
+    }
+
+    async fn generate_for_year(&self, org_id: Uuid, year: i32) -> RepoResult<Vec<Period>> {
+        let anchor = self
+            .anchors
+            .read()
+            .unwrap()
+            .get(&org_id)
+            .copied()
+            .ok_or_else(|| RepositoryError::NotFound(format!("organization {}", org_id)))?;
+
+        for calculated in PeriodCalculator::with_anchor(anchor).calculate_periods(year) {
+            let period = Period {
+                id: Uuid::new_v4(),
+                organization_id: org_id,
+                year,
+                number: calculated.number as i32,
+                start_date: calculated.start_date,
+                end_date: calculated.end_date,
+                hour_quota: calculated.hour_quota,
+                closed_at: None,
+                created_at: Utc::now(),
+            };
+            match self.create(&period).await {
+                Ok(_) | Err(RepositoryError::Duplicate(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.find_by_year(org_id, year).await
+    }
+}
+
+/// In-memory holiday repository
+#[derive(Default)]
+pub struct InMemoryHolidayRepository {
+    holidays: RwLock<Vec<(Uuid, Holiday)>>,
+}
+
+impl InMemoryHolidayRepository {
+    /// Add (or rename) the holiday of an organization on a date
+    pub fn insert(&self, org_id: Uuid, holiday: Holiday) {
+        let mut holidays = self.holidays.write().unwrap();
+        holidays.retain(|(org, h)| !(*org == org_id && h.date == holiday.date));
+        holidays.push((org_id, holiday));
+        holidays.sort_by_key(|(_, h)| h.date);
+    }
+}
+
+#[async_trait]
+impl HolidayRepository for InMemoryHolidayRepository {
+    async fn find_by_range(
+        &self,
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Holiday>> {
+        Ok(self
+            .holidays
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(org, h)| *org == org_id && h.date >= start && h.date <= end)
+            .map(|(_, h)| h.clone())
+            .collect())
+    }
+}
+
+/// In-memory audit log
+#[derive(Default)]
+pub struct InMemoryAuditLogRepository {
+    entries: RwLock<Vec<AuditLog>>,
+}
+
+impl InMemoryAuditLogRepository {
+    /// Recorded entries, oldest first
+    pub fn entries(&self) -> Vec<AuditLog> {
+        self.entries.read().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for InMemoryAuditLogRepository {
+    async fn record(&self, entry: &AuditLog) -> RepoResult<AuditLog> {
+        self.entries.write().unwrap().push(entry.clone());
+        Ok(entry.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::shift_type::ShiftCategory;
+
+    #[tokio::test]
+    async fn test_schedule_upsert_keeps_one_entry_per_day() {
+        let repository = InMemoryScheduleRepository::default();
+        let (user_id, date) = (Uuid::new_v4(), NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+        let (day, rest) = (
+            fixtures::shift_type("101", ShiftCategory::Standard, 8.0, 0.0),
+            fixtures::shift_type("RH", ShiftCategory::Rest, 0.0, 0.0),
+        );
+
+        let first = repository
+            .upsert(&fixtures::schedule(user_id, date, &day))
+            .await
+            .unwrap();
+        let second = repository
+            .upsert(&fixtures::schedule(user_id, date, &rest))
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(
+            repository
+                .find_by_org_range(Uuid::nil(), date, date)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shift_type_usage_stays_in_the_organization() {
+        let repository = InMemoryScheduleRepository::default();
+        let shift_type = fixtures::shift_type("101", ShiftCategory::Standard, 8.0, 0.0);
+        let (shift_type_id, date) = (shift_type.id, NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+        let used = fixtures::schedule(Uuid::new_v4(), date, &shift_type);
+        repository.upsert(&used).await.unwrap();
+
+        let other_org = Uuid::new_v4();
+        assert_eq!(
+            repository
+                .count_by_shift_type(Uuid::nil(), shift_type_id)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            repository
+                .count_by_shift_type(other_org, shift_type_id)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(repository
+            .find_by_shift_type(other_org, shift_type_id, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_period_generation_is_idempotent() {
+        let repository = InMemoryPeriodRepository::default();
+        let org_id = Uuid::new_v4();
+
+        assert!(matches!(
+            repository.generate_for_year(org_id, 2026).await,
+            Err(RepositoryError::NotFound(_))
+        ));
+
+        repository.set_anchor(org_id, NaiveDate::from_ymd_opt(2026, 1, 12).unwrap());
+        let first = repository.generate_for_year(org_id, 2026).await.unwrap();
+        let second = repository.generate_for_year(org_id, 2026).await.unwrap();
+
+        assert_eq!(first.len(), 13);
+        assert_eq!(
+            first.iter().map(|p| p.id).collect::<Vec<_>>(),
+            second.iter().map(|p| p.id).collect::<Vec<_>>()
+        );
+    }
+}
@@ -2,6 +2,18 @@
 //!
 //! Database connections and repository implementations.
 
+pub mod balances;
+pub mod calendar_tokens;
+pub mod holidays;
+pub mod hour_bank;
+pub mod memory;
+pub mod payroll;
+pub mod planning_data;
 pub mod postgres;
+pub mod refresh_tokens;
+pub mod repositories;
+
+#[cfg(test)]
+pub mod testing;
 
 pub use postgres::*;
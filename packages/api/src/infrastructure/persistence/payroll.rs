@@ -0,0 +1,155 @@
+//! Payroll Persistence
+//!
+//! Wage code mapping (organizations.config -> payroll) and export batches.
+
+use sqlx::{types::Json as SqlJson, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::payroll::{PayrollFormat, PayrollMapping};
+use crate::domain::entities::PayrollBatch;
+
+/// Load the wage code mapping of an organization (defaults when not configured)
+pub async fn find_mapping(db: &PgPool, org_id: Uuid) -> Result<PayrollMapping, sqlx::Error> {
+    let config: Option<(serde_json::Value,)> =
+        sqlx::query_as("SELECT config FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(db)
+            .await?;
+
+    Ok(config
+        .and_then(|(c,)| c.get("payroll").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Store the wage code mapping of an organization
+pub async fn save_mapping(
+    db: &PgPool,
+    org_id: Uuid,
+    mapping: &PayrollMapping,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE organizations
+        SET config = jsonb_set(COALESCE(config, '{}'::JSONB), '{payroll}', $2),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(org_id)
+    .bind(SqlJson(mapping))
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Periods among `period_ids` already sent in a batch
+pub async fn find_exported_periods(
+    db: &PgPool,
+    period_ids: &[Uuid],
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        "SELECT DISTINCT period_id FROM payroll_batch_periods WHERE period_id = ANY($1)",
+    )
+    .bind(period_ids)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+/// Record a batch with its file and periods
+pub async fn create_batch(
+    db: &PgPool,
+    org_id: Uuid,
+    format: PayrollFormat,
+    period_ids: &[Uuid],
+    line_count: i32,
+    content: &str,
+    created_by: Option<Uuid>,
+) -> Result<PayrollBatch, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO payroll_batches (organization_id, format, line_count, content, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(org_id)
+    .bind(format)
+    .bind(line_count)
+    .bind(content)
+    .bind(created_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO payroll_batch_periods (batch_id, period_id)
+        SELECT $1, UNNEST($2::UUID[])
+        "#,
+    )
+    .bind(id)
+    .bind(period_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    find_batch(db, org_id, id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+const BATCH_SELECT: &str = r#"
+    SELECT b.id, b.organization_id, b.format, b.line_count,
+           COALESCE(ARRAY_AGG(bp.period_id) FILTER (WHERE bp.period_id IS NOT NULL), '{}') AS period_ids,
+           b.created_by, b.created_at
+    FROM payroll_batches b
+    LEFT JOIN payroll_batch_periods bp ON bp.batch_id = b.id
+"#;
+
+/// List the batches of an organization, newest first
+pub async fn list_batches(db: &PgPool, org_id: Uuid) -> Result<Vec<PayrollBatch>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "{} WHERE b.organization_id = $1 GROUP BY b.id ORDER BY b.created_at DESC",
+        BATCH_SELECT
+    ))
+    .bind(org_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Find a batch of an organization
+pub async fn find_batch(
+    db: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+) -> Result<Option<PayrollBatch>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "{} WHERE b.organization_id = $1 AND b.id = $2 GROUP BY b.id",
+        BATCH_SELECT
+    ))
+    .bind(org_id)
+    .bind(id)
+    .fetch_optional(db)
+    .await
+}
+
+/// File of a batch
+pub async fn find_batch_content(
+    db: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+) -> Result<Option<(PayrollFormat, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT format, content FROM payroll_batches WHERE organization_id = $1 AND id = $2",
+    )
+    .bind(org_id)
+    .bind(id)
+    .fetch_optional(db)
+    .await
+}
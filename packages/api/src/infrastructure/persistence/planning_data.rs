@@ -0,0 +1,505 @@
+//! Planning Data Queries
+//!
+//! Shared queries for periods, shift types, schedules and holidays.
+//!
+//! Read models of the handlers, outside the repository ports: they are
+//! tested against Postgres, not with the in-memory harness.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::{types::Json as SqlJson, FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::edit_lock::EditLockPolicy;
+use crate::domain::entities::{Period, Schedule, ShiftCodeGrammar, ShiftType, ShiftTypeVersion};
+use crate::domain::services::dashboard_builder::LeaveEntitlement;
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::domain::services::ShiftTypeHistory;
+
+/// Find a period of an organization by ID
+pub async fn find_period(
+    db: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+) -> Result<Option<Period>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, organization_id, year, number, start_date, end_date, hour_quota, closed_at, created_at
+        FROM periods
+        WHERE organization_id = $1 AND id = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(id)
+    .fetch_optional(db)
+    .await
+}
+
+/// Find all shift types of an organization
+pub async fn find_shift_types(db: &PgPool, org_id: Uuid) -> Result<Vec<ShiftType>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            id, organization_id, code, description, category, color_hex, icon,
+            duration_hours::FLOAT8 AS duration_hours, night_hours::FLOAT8 AS night_hours,
+            start_time, end_time,
+            is_countable, requires_recovery, is_holiday_indicator, is_rest_day,
+            display_order, is_active, created_at, updated_at
+        FROM shift_types
+        WHERE organization_id = $1
+        ORDER BY display_order, code
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Find the shift type versions of an organization
+pub async fn find_shift_type_versions(
+    db: &PgPool,
+    org_id: Uuid,
+) -> Result<Vec<ShiftTypeVersion>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            id, shift_type_id, organization_id, effective_from, category,
+            duration_hours::FLOAT8 AS duration_hours, night_hours::FLOAT8 AS night_hours,
+            is_countable, requires_recovery, is_holiday_indicator, is_rest_day,
+            COALESCE(created_at, NOW()) AS created_at
+        FROM shift_type_versions
+        WHERE organization_id = $1
+        ORDER BY shift_type_id, effective_from
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Load the shift type history of an organization
+pub async fn find_shift_type_history(
+    db: &PgPool,
+    org_id: Uuid,
+) -> Result<ShiftTypeHistory, sqlx::Error> {
+    Ok(ShiftTypeHistory::new(
+        find_shift_type_versions(db, org_id).await?,
+    ))
+}
+
+/// Find schedules of an organization in a date range, optionally for a single user
+pub async fn find_schedules(
+    db: &PgPool,
+    org_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+    user_id: Option<Uuid>,
+) -> Result<Vec<Schedule>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            id, organization_id, user_id, shift_type_id, period_id,
+            date, is_holiday, notes,
+            created_by, updated_by, created_at, updated_at
+        FROM schedules
+        WHERE organization_id = $1
+          AND date BETWEEN $2 AND $3
+          AND ($4::UUID IS NULL OR user_id = $4)
+        ORDER BY user_id, date
+        "#,
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .bind(user_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Find holiday dates of an organization in a date range
+pub async fn find_holiday_dates(
+    db: &PgPool,
+    org_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<NaiveDate>, sqlx::Error> {
+    let rows: Vec<(NaiveDate,)> = sqlx::query_as(
+        "SELECT date FROM holidays WHERE organization_id = $1 AND date BETWEEN $2 AND $3 ORDER BY date",
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+/// Find holidays (with their names) of an organization in a date range
+pub async fn find_holidays(
+    db: &PgPool,
+    org_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<Holiday>, sqlx::Error> {
+    let rows: Vec<(NaiveDate, String, Option<bool>)> = sqlx::query_as(
+        "SELECT date, name, is_moveable FROM holidays WHERE organization_id = $1 AND date BETWEEN $2 AND $3 ORDER BY date",
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(date, name, is_moveable)| Holiday {
+            date,
+            name,
+            is_moveable: is_moveable.unwrap_or(false),
+        })
+        .collect())
+}
+
+/// Load the edit lock policy of an organization (defaults when not configured)
+pub async fn find_edit_lock_policy(
+    db: &PgPool,
+    org_id: Uuid,
+) -> Result<EditLockPolicy, sqlx::Error> {
+    let config: Option<(serde_json::Value,)> =
+        sqlx::query_as("SELECT config FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(db)
+            .await?;
+
+    Ok(config
+        .and_then(|(c,)| c.get("editLocks").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Load the shift code grammar of an organization (defaults when not configured)
+pub async fn find_shift_code_grammar(
+    db: &PgPool,
+    org_id: Uuid,
+) -> Result<ShiftCodeGrammar, sqlx::Error> {
+    let config: Option<(serde_json::Value,)> =
+        sqlx::query_as("SELECT config FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .fetch_optional(db)
+            .await?;
+
+    Ok(config
+        .and_then(|(c,)| c.get("shiftCodes").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Store the shift code grammar of an organization
+pub async fn save_shift_code_grammar(
+    db: &PgPool,
+    org_id: Uuid,
+    grammar: &ShiftCodeGrammar,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE organizations
+        SET config = jsonb_set(COALESCE(config, '{}'::JSONB), '{shiftCodes}', $2),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(org_id)
+    .bind(SqlJson(grammar))
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Find the periods of an organization for a year
+pub async fn find_periods(
+    db: &PgPool,
+    org_id: Uuid,
+    year: i32,
+) -> Result<Vec<Period>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, organization_id, year, number, start_date, end_date, hour_quota, closed_at, created_at
+        FROM periods
+        WHERE organization_id = $1 AND year = $2
+        ORDER BY number
+        "#,
+    )
+    .bind(org_id)
+    .bind(year)
+    .fetch_all(db)
+    .await
+}
+
+/// Agent identity used in planning views
+#[derive(Debug, Clone, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentSummary {
+    pub id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub matricule: Option<String>,
+}
+
+/// Find the active agents of an organization
+pub async fn find_active_agents(
+    db: &PgPool,
+    org_id: Uuid,
+) -> Result<Vec<AgentSummary>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, first_name, last_name, matricule
+        FROM users
+        WHERE organization_id = $1 AND is_active = true
+        ORDER BY last_name, first_name
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Find the agents of an organization with schedules in a date range,
+/// active or not
+pub async fn find_scheduled_agents(
+    db: &PgPool,
+    org_id: Uuid,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<AgentSummary>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT u.id, u.first_name, u.last_name, u.matricule
+        FROM users u
+        WHERE u.organization_id = $1
+          AND EXISTS (
+            SELECT 1 FROM schedules s
+            WHERE s.organization_id = $1 AND s.user_id = u.id
+              AND s.date BETWEEN $2 AND $3
+          )
+        ORDER BY u.last_name, u.first_name
+        "#,
+    )
+    .bind(org_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(db)
+    .await
+}
+
+/// Find an agent of an organization
+pub async fn find_agent(
+    db: &PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<AgentSummary>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, first_name, last_name, matricule
+        FROM users
+        WHERE organization_id = $1 AND id = $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .fetch_optional(db)
+    .await
+}
+
+/// Find an agent by matricule or name ("Last First" or "First Last", case-insensitive)
+pub async fn find_agent_by_label(
+    db: &PgPool,
+    org_id: Uuid,
+    label: &str,
+) -> Result<Option<AgentSummary>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT id, first_name, last_name, matricule
+        FROM users
+        WHERE organization_id = $1
+          AND (
+            matricule = $2
+            OR LOWER(last_name || ' ' || first_name) = LOWER($2)
+            OR LOWER(first_name || ' ' || last_name) = LOWER($2)
+          )
+        ORDER BY (matricule = $2) DESC NULLS LAST, is_active DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(org_id)
+    .bind(label.split_whitespace().collect::<Vec<_>>().join(" "))
+    .fetch_optional(db)
+    .await
+}
+
+/// CN and JC entitlements of an agent, or summed over the active team
+pub async fn find_leave_entitlements(
+    db: &PgPool,
+    org_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<LeaveEntitlement>, sqlx::Error> {
+    let (cn_entitlement, jc_entitlement, cn_carryover, jc_carryover): (i64, i64, i64, i64) =
+        sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(cn_entitlement), 0), COALESCE(SUM(jc_entitlement), 0),
+                COALESCE(SUM(cn_carryover), 0), COALESCE(SUM(jc_carryover), 0)
+            FROM users
+            WHERE organization_id = $1
+              AND ($2::UUID IS NULL OR id = $2)
+              AND ($2::UUID IS NOT NULL OR is_active = true)
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+    Ok(vec![
+        LeaveEntitlement {
+            code: "CN".to_string(),
+            entitlement: cn_entitlement as i32,
+            carryover: cn_carryover as i32,
+        },
+        LeaveEntitlement {
+            code: "JC".to_string(),
+            entitlement: jc_entitlement as i32,
+            carryover: jc_carryover as i32,
+        },
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::Repositories;
+    use crate::application::testing::fixtures;
+    use crate::domain::entities::shift_type::ShiftCategory;
+    use crate::domain::entities::User;
+    use crate::infrastructure::persistence::repositories;
+    use crate::infrastructure::persistence::testing::TestDatabase;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    /// Shift type 101 of an organization
+    async fn standard(ports: &Repositories, org_id: Uuid) -> ShiftType {
+        ports
+            .shift_types
+            .create(&ShiftType {
+                organization_id: org_id,
+                ..fixtures::shift_type("101", ShiftCategory::Standard, 8.0, 2.0)
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn plan(ports: &Repositories, agent: &User, day: u32, shift_type: &ShiftType) {
+        ports
+            .schedules
+            .upsert(&Schedule {
+                organization_id: agent.organization_id,
+                ..fixtures::schedule(agent.id, date(day), shift_type)
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn test_scheduled_agents_include_deactivated_ones() {
+        let test_db = TestDatabase::new().await;
+        let org_id = test_db.organization().await;
+        let ports = repositories::postgres(&test_db.db);
+
+        let shift_type = standard(&ports, org_id).await;
+        let departed = ports
+            .users
+            .create(&User {
+                is_active: false,
+                ..fixtures::agent(org_id, "Marie", "Dupont")
+            })
+            .await
+            .unwrap();
+        let unscheduled = ports
+            .users
+            .create(&fixtures::agent(org_id, "Jean", "Martin"))
+            .await
+            .unwrap();
+        plan(&ports, &departed, 14, &shift_type).await;
+
+        let agents = find_scheduled_agents(&test_db.db, org_id, date(14), date(14))
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = agents.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![departed.id]);
+        assert!(!ids.contains(&unscheduled.id));
+
+        assert!(
+            find_scheduled_agents(&test_db.db, org_id, date(15), date(15))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        test_db.drop().await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn test_planning_queries_stay_in_the_organization() {
+        let test_db = TestDatabase::new().await;
+        let (org_id, other_org) = (test_db.organization().await, test_db.organization().await);
+        let ports = repositories::postgres(&test_db.db);
+
+        let shift_type = standard(&ports, org_id).await;
+        let other_type = standard(&ports, other_org).await;
+        let marie = ports
+            .users
+            .create(&fixtures::agent(org_id, "Marie", "Dupont"))
+            .await
+            .unwrap();
+        let jean = ports
+            .users
+            .create(&fixtures::agent(org_id, "Jean", "Martin"))
+            .await
+            .unwrap();
+        let outsider = ports
+            .users
+            .create(&fixtures::agent(other_org, "Paul", "Durand"))
+            .await
+            .unwrap();
+        plan(&ports, &marie, 14, &shift_type).await;
+        plan(&ports, &marie, 15, &shift_type).await;
+        plan(&ports, &jean, 14, &shift_type).await;
+        plan(&ports, &outsider, 14, &other_type).await;
+
+        let shift_types = find_shift_types(&test_db.db, org_id).await.unwrap();
+        assert_eq!(shift_types.len(), 1);
+        assert_eq!(shift_types[0].id, shift_type.id);
+
+        let schedules = find_schedules(&test_db.db, org_id, date(14), date(15), None)
+            .await
+            .unwrap();
+        assert_eq!(schedules.len(), 3);
+        assert!(schedules.iter().all(|s| s.organization_id == org_id));
+
+        let own = find_schedules(&test_db.db, org_id, date(15), date(31), Some(marie.id))
+            .await
+            .unwrap();
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].date, date(15));
+
+        let agents = find_active_agents(&test_db.db, org_id).await.unwrap();
+        assert_eq!(agents.len(), 2);
+
+        test_db.drop().await;
+    }
+}
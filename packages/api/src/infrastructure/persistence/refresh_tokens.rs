@@ -0,0 +1,178 @@
+//! Refresh Token Persistence
+//!
+//! Refresh tokens are stored as their SHA-256 and rotated on every use.
+//! Tokens of a login share a family (the session); presenting a token that
+//! was already rotated means it leaked, so the whole family is revoked.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// SHA-256 of a token, as stored in `token_hash`
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Outcome of a refresh token exchange
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rotation {
+    /// Token replaced by its successor
+    Rotated { user_id: Uuid, family_id: Uuid },
+    /// Token already rotated: its family has been revoked
+    Reused { user_id: Uuid, family_id: Uuid },
+    /// Unknown, expired or revoked token
+    Invalid,
+}
+
+/// Store the first token of a session
+pub async fn create(
+    db: &PgPool,
+    user_id: Uuid,
+    family_id: Uuid,
+    token: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(user_id)
+    .bind(family_id)
+    .bind(hash_token(token))
+    .bind(expires_at)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Exchange a token for its successor, in the same family
+///
+/// The successor is only stored when the token was still valid. A token
+/// already exchanged revokes every token of its family.
+pub async fn rotate(
+    db: &PgPool,
+    token: &str,
+    successor: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<Rotation, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let current: Option<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        UPDATE refresh_tokens
+        SET rotated_at = NOW()
+        WHERE token_hash = $1
+          AND rotated_at IS NULL AND revoked_at IS NULL AND expires_at > NOW()
+        RETURNING user_id, family_id
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some((user_id, family_id)) = current {
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(family_id)
+        .bind(hash_token(successor))
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        return Ok(Rotation::Rotated { user_id, family_id });
+    }
+
+    let rotated: Option<(Uuid, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT user_id, family_id
+        FROM refresh_tokens
+        WHERE token_hash = $1 AND rotated_at IS NOT NULL
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((user_id, family_id)) = rotated else {
+        return Ok(Rotation::Invalid);
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE family_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(family_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Rotation::Reused { user_id, family_id })
+}
+
+/// Revoke the tokens of a session; returns the number revoked
+pub async fn revoke_family(
+    db: &PgPool,
+    user_id: Uuid,
+    family_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND family_id = $2 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(family_id)
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Revoke every session of a user; returns the number of sessions revoked
+pub async fn revoke_user(db: &PgPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+    let sessions: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        RETURNING family_id
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(db)
+    .await?;
+
+    let mut families: Vec<_> = sessions.into_iter().map(|(id,)| id).collect();
+    families.sort();
+    families.dedup();
+    Ok(families.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_hash() {
+        let hash = hash_token("header.payload.signature");
+
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_token("header.payload.signature"));
+        assert_ne!(hash, hash_token("header.payload.signaturf"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Audit Log Repository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::application::ports::{AuditLogRepository, RepoResult};
+use crate::domain::entities::AuditLog;
+
+/// Postgres audit log repository
+pub struct PgAuditLogRepository {
+    db: PgPool,
+}
+
+impl PgAuditLogRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PgAuditLogRepository {
+    async fn record(&self, entry: &AuditLog) -> RepoResult<AuditLog> {
+        let entry = sqlx::query_as(
+            r#"
+            INSERT INTO audit_logs (
+                id, organization_id, user_id, action, entity_type, entity_id,
+                old_value, new_value, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING
+                id, organization_id, user_id, action, entity_type, entity_id,
+                old_value, new_value, COALESCE(created_at, NOW()) AS created_at
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.organization_id)
+        .bind(entry.user_id)
+        .bind(entry.action)
+        .bind(&entry.entity_type)
+        .bind(entry.entity_id)
+        .bind(&entry.old_value)
+        .bind(&entry.new_value)
+        .bind(entry.created_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(entry)
+    }
+}
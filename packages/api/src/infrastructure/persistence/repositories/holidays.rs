@@ -0,0 +1,33 @@
+//! Holiday Repository
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::{HolidayRepository, RepoResult};
+use crate::domain::services::holiday_calculator::Holiday;
+use crate::infrastructure::persistence::planning_data;
+
+/// Postgres holiday repository
+pub struct PgHolidayRepository {
+    db: PgPool,
+}
+
+impl PgHolidayRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl HolidayRepository for PgHolidayRepository {
+    async fn find_by_range(
+        &self,
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Holiday>> {
+        Ok(planning_data::find_holidays(&self.db, org_id, start, end).await?)
+    }
+}
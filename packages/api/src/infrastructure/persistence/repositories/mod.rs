@@ -0,0 +1,52 @@
+//! Postgres Repositories
+//!
+//! Adapters implementing the application repository ports on PostgreSQL.
+
+pub mod audit_logs;
+pub mod holidays;
+pub mod periods;
+pub mod roles;
+pub mod schedules;
+pub mod shift_types;
+pub mod users;
+
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::application::ports::{Repositories, RepositoryError};
+
+pub use audit_logs::PgAuditLogRepository;
+pub use holidays::PgHolidayRepository;
+pub use periods::PgPeriodRepository;
+pub use roles::PgRoleRepository;
+pub use schedules::PgScheduleRepository;
+pub use shift_types::PgShiftTypeRepository;
+pub use users::PgUserRepository;
+
+/// Build the Postgres implementation of every repository port
+pub fn postgres(db: &PgPool) -> Repositories {
+    Repositories {
+        users: Arc::new(PgUserRepository::new(db.clone())),
+        roles: Arc::new(PgRoleRepository::new(db.clone())),
+        schedules: Arc::new(PgScheduleRepository::new(db.clone())),
+        shift_types: Arc::new(PgShiftTypeRepository::new(db.clone())),
+        periods: Arc::new(PgPeriodRepository::new(db.clone())),
+        holidays: Arc::new(PgHolidayRepository::new(db.clone())),
+        audit_logs: Arc::new(PgAuditLogRepository::new(db.clone())),
+    }
+}
+
+/// Map unique violations to `Duplicate`, foreign key violations to
+/// `Validation`, other errors to `Database`
+fn write_error(e: sqlx::Error, entity: &str) -> RepositoryError {
+    match e.as_database_error() {
+        Some(db_error) if db_error.is_unique_violation() => {
+            RepositoryError::Duplicate(entity.to_string())
+        }
+        Some(db_error) if db_error.is_foreign_key_violation() => {
+            RepositoryError::Validation(format!("{} is still referenced", entity))
+        }
+        _ => RepositoryError::Database(e),
+    }
+}
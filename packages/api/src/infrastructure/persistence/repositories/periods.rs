@@ -0,0 +1,104 @@
+//! Period Repository
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::{PeriodRepository, RepoResult, RepositoryError};
+use crate::domain::entities::Period;
+use crate::domain::services::PeriodCalculator;
+use crate::infrastructure::persistence::planning_data;
+
+use super::write_error;
+
+/// Postgres period repository
+pub struct PgPeriodRepository {
+    db: PgPool,
+}
+
+impl PgPeriodRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl PeriodRepository for PgPeriodRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Period>> {
+        Ok(planning_data::find_period(&self.db, org_id, id).await?)
+    }
+
+    async fn find_by_date(&self, org_id: Uuid, date: NaiveDate) -> RepoResult<Option<Period>> {
+        let period = sqlx::query_as(
+            r#"
+            SELECT id, organization_id, year, number, start_date, end_date, hour_quota, closed_at, created_at
+            FROM periods
+            WHERE organization_id = $1 AND $2 BETWEEN start_date AND end_date
+            "#,
+        )
+        .bind(org_id)
+        .bind(date)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(period)
+    }
+
+    async fn find_by_year(&self, org_id: Uuid, year: i32) -> RepoResult<Vec<Period>> {
+        Ok(planning_data::find_periods(&self.db, org_id, year).await?)
+    }
+
+    async fn create(&self, period: &Period) -> RepoResult<Period> {
+        sqlx::query_as(
+            r#"
+            INSERT INTO periods (id, organization_id, year, number, start_date, end_date, hour_quota)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, organization_id, year, number, start_date, end_date, hour_quota, closed_at, created_at
+            "#,
+        )
+        .bind(period.id)
+        .bind(period.organization_id)
+        .bind(period.year)
+        .bind(period.number)
+        .bind(period.start_date)
+        .bind(period.end_date)
+        .bind(period.hour_quota)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("period {} of {}", period.label(), period.year)))
+    }
+
+    /// Create the missing periods of a year from the organization's anchor date
+    async fn generate_for_year(&self, org_id: Uuid, year: i32) -> RepoResult<Vec<Period>> {
+        let anchor: Option<(NaiveDate,)> =
+            sqlx::query_as("SELECT year_start_date FROM organizations WHERE id = $1")
+                .bind(org_id)
+                .fetch_optional(&self.db)
+                .await?;
+        let (anchor,) =
+            anchor.ok_or_else(|| RepositoryError::NotFound(format!("organization {}", org_id)))?;
+
+        let mut tx = self.db.begin().await?;
+        for period in PeriodCalculator::with_anchor(anchor).calculate_periods(year) {
+            sqlx::query(
+                r#"
+                INSERT INTO periods (organization_id, year, number, start_date, end_date, hour_quota)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (organization_id, year, number) DO NOTHING
+                "#,
+            )
+            .bind(org_id)
+            .bind(year)
+            .bind(period.number as i32)
+            .bind(period.start_date)
+            .bind(period.end_date)
+            .bind(period.hour_quota)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.find_by_year(org_id, year).await
+    }
+}
@@ -0,0 +1,143 @@
+//! Role Repository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json as SqlJson, FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, RepositoryError, RoleRepository};
+use crate::domain::entities::Role;
+
+use super::write_error;
+
+const COLUMNS: &str = r#"
+    id, organization_id, name, display_name, permissions,
+    COALESCE(is_system, false) AS is_system,
+    COALESCE(created_at, NOW()) AS created_at
+"#;
+
+#[derive(FromRow)]
+struct RoleRow {
+    id: Uuid,
+    organization_id: Uuid,
+    name: String,
+    display_name: String,
+    permissions: SqlJson<Vec<String>>,
+    is_system: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<RoleRow> for Role {
+    fn from(row: RoleRow) -> Self {
+        Role {
+            id: row.id,
+            organization_id: row.organization_id,
+            name: row.name,
+            display_name: row.display_name,
+            permissions: row.permissions.0,
+            is_system: row.is_system,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Postgres role repository
+pub struct PgRoleRepository {
+    db: PgPool,
+}
+
+impl PgRoleRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for PgRoleRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Role>> {
+        let role: Option<RoleRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM roles WHERE organization_id = $1 AND id = $2",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(role.map(Role::from))
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<Role>> {
+        let roles: Vec<RoleRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM roles
+            WHERE organization_id = $1
+            ORDER BY is_system DESC, name
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(roles.into_iter().map(Role::from).collect())
+    }
+
+    async fn create(&self, role: &Role) -> RepoResult<Role> {
+        let row: RoleRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO roles (id, organization_id, name, display_name, permissions, is_system)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(role.id)
+        .bind(role.organization_id)
+        .bind(&role.name)
+        .bind(&role.display_name)
+        .bind(SqlJson(&role.permissions))
+        .bind(role.is_system)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("role {}", role.name)))?;
+
+        Ok(row.into())
+    }
+
+    async fn update(&self, role: &Role) -> RepoResult<Role> {
+        let row: Option<RoleRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE roles
+            SET display_name = $2, permissions = $3
+            WHERE id = $1 AND organization_id = $4
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(role.id)
+        .bind(&role.display_name)
+        .bind(SqlJson(&role.permissions))
+        .bind(role.organization_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        row.map(Role::from)
+            .ok_or_else(|| RepositoryError::NotFound(format!("role {}", role.id)))
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let result = sqlx::query("DELETE FROM roles WHERE organization_id = $1 AND id = $2")
+            .bind(org_id)
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound(format!("role {}", id)));
+        }
+
+        Ok(())
+    }
+}
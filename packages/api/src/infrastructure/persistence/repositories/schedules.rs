@@ -0,0 +1,216 @@
+//! Schedule Repository
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, RepositoryError, ScheduleRepository};
+use crate::domain::entities::Schedule;
+
+const COLUMNS: &str = r#"
+    id, organization_id, user_id, shift_type_id, period_id,
+    date, is_holiday, notes,
+    created_by, updated_by, created_at, updated_at
+"#;
+
+/// Postgres schedule repository
+pub struct PgScheduleRepository {
+    db: PgPool,
+}
+
+impl PgScheduleRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// Insert a schedule, or update the existing entry of the user on that day
+async fn upsert<'e, E>(executor: E, schedule: &Schedule) -> Result<Schedule, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as(&format!(
+        r#"
+        INSERT INTO schedules (
+            id, organization_id, user_id, shift_type_id, period_id,
+            date, is_holiday, notes, created_by, updated_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (user_id, date) DO UPDATE
+        SET shift_type_id = EXCLUDED.shift_type_id,
+            period_id = EXCLUDED.period_id,
+            is_holiday = EXCLUDED.is_holiday,
+            notes = EXCLUDED.notes,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = NOW()
+        RETURNING {}
+        "#,
+        COLUMNS
+    ))
+    .bind(schedule.id)
+    .bind(schedule.organization_id)
+    .bind(schedule.user_id)
+    .bind(schedule.shift_type_id)
+    .bind(schedule.period_id)
+    .bind(schedule.date)
+    .bind(schedule.is_holiday)
+    .bind(&schedule.notes)
+    .bind(schedule.created_by)
+    .bind(schedule.updated_by)
+    .fetch_one(executor)
+    .await
+}
+
+#[async_trait]
+impl ScheduleRepository for PgScheduleRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<Schedule>> {
+        let schedule = sqlx::query_as(&format!(
+            "SELECT {} FROM schedules WHERE organization_id = $1 AND id = $2",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(schedule)
+    }
+
+    async fn find_by_user_date(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> RepoResult<Option<Schedule>> {
+        let schedule = sqlx::query_as(&format!(
+            "SELECT {} FROM schedules WHERE organization_id = $1 AND user_id = $2 AND date = $3",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(user_id)
+        .bind(date)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(schedule)
+    }
+
+    async fn find_by_user_range(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Schedule>> {
+        let schedules = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM schedules
+            WHERE organization_id = $1 AND user_id = $2 AND date BETWEEN $3 AND $4
+            ORDER BY date
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(schedules)
+    }
+
+    async fn find_by_org_range(
+        &self,
+        org_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<Schedule>> {
+        let schedules = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM schedules
+            WHERE organization_id = $1 AND date BETWEEN $2 AND $3
+            ORDER BY user_id, date
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(schedules)
+    }
+
+    async fn upsert(&self, schedule: &Schedule) -> RepoResult<Schedule> {
+        Ok(upsert(&self.db, schedule).await?)
+    }
+
+    /// Upsert all schedules in one transaction
+    async fn bulk_upsert(&self, schedules: &[Schedule]) -> RepoResult<Vec<Schedule>> {
+        let mut tx = self.db.begin().await?;
+        let mut written = Vec::with_capacity(schedules.len());
+
+        for schedule in schedules {
+            written.push(upsert(&mut *tx, schedule).await?);
+        }
+
+        tx.commit().await?;
+        Ok(written)
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let result = sqlx::query("DELETE FROM schedules WHERE organization_id = $1 AND id = $2")
+            .bind(org_id)
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound(format!("schedule {}", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn count_by_shift_type(&self, org_id: Uuid, shift_type_id: Uuid) -> RepoResult<i64> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM schedules WHERE organization_id = $1 AND shift_type_id = $2",
+        )
+        .bind(org_id)
+        .bind(shift_type_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn find_by_shift_type(
+        &self,
+        org_id: Uuid,
+        shift_type_id: Uuid,
+        from: Option<NaiveDate>,
+    ) -> RepoResult<Vec<Schedule>> {
+        let schedules = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM schedules
+            WHERE organization_id = $1 AND shift_type_id = $2
+            AND ($3::DATE IS NULL OR date >= $3)
+            ORDER BY date, user_id
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(shift_type_id)
+        .bind(from)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(schedules)
+    }
+}
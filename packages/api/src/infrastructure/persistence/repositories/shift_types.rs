@@ -0,0 +1,216 @@
+//! Shift Type Repository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, RepositoryError, ShiftTypeRepository};
+use crate::domain::entities::{ShiftCodeGrammar, ShiftType, ShiftTypeVersion};
+use crate::infrastructure::persistence::planning_data;
+
+use super::write_error;
+
+const COLUMNS: &str = r#"
+    id, organization_id, code, description, category, color_hex, icon,
+    duration_hours::FLOAT8 AS duration_hours, night_hours::FLOAT8 AS night_hours,
+    start_time, end_time,
+    is_countable, requires_recovery, is_holiday_indicator, is_rest_day,
+    display_order, is_active, created_at, updated_at
+"#;
+
+/// Postgres shift type repository
+pub struct PgShiftTypeRepository {
+    db: PgPool,
+}
+
+impl PgShiftTypeRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ShiftTypeRepository for PgShiftTypeRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<ShiftType>> {
+        let shift_type = sqlx::query_as(&format!(
+            "SELECT {} FROM shift_types WHERE organization_id = $1 AND id = $2",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(shift_type)
+    }
+
+    async fn find_by_code(&self, org_id: Uuid, code: &str) -> RepoResult<Option<ShiftType>> {
+        let shift_type = sqlx::query_as(&format!(
+            "SELECT {} FROM shift_types WHERE organization_id = $1 AND code = $2",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(code)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(shift_type)
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<ShiftType>> {
+        Ok(planning_data::find_shift_types(&self.db, org_id).await?)
+    }
+
+    async fn find_active(&self, org_id: Uuid) -> RepoResult<Vec<ShiftType>> {
+        let shift_types = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM shift_types
+            WHERE organization_id = $1 AND is_active = true
+            ORDER BY display_order, code
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(shift_types)
+    }
+
+    async fn create(&self, shift_type: &ShiftType) -> RepoResult<ShiftType> {
+        sqlx::query_as(&format!(
+            r#"
+            INSERT INTO shift_types (
+                id, organization_id, code, description, category, color_hex, icon,
+                duration_hours, night_hours, start_time, end_time,
+                is_countable, requires_recovery, is_holiday_indicator, is_rest_day,
+                display_order, is_active
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(shift_type.id)
+        .bind(shift_type.organization_id)
+        .bind(&shift_type.code)
+        .bind(&shift_type.description)
+        .bind(shift_type.category)
+        .bind(&shift_type.color_hex)
+        .bind(&shift_type.icon)
+        .bind(shift_type.duration_hours)
+        .bind(shift_type.night_hours)
+        .bind(shift_type.start_time)
+        .bind(shift_type.end_time)
+        .bind(shift_type.is_countable)
+        .bind(shift_type.requires_recovery)
+        .bind(shift_type.is_holiday_indicator)
+        .bind(shift_type.is_rest_day)
+        .bind(shift_type.display_order)
+        .bind(shift_type.is_active)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("shift type {}", shift_type.code)))
+    }
+
+    async fn update(&self, shift_type: &ShiftType) -> RepoResult<ShiftType> {
+        sqlx::query_as(&format!(
+            r#"
+            UPDATE shift_types
+            SET code = $2, description = $3, category = $4, color_hex = $5, icon = $6,
+                duration_hours = $7, night_hours = $8, start_time = $9, end_time = $10,
+                is_countable = $11, requires_recovery = $12, is_holiday_indicator = $13,
+                is_rest_day = $14, display_order = $15, is_active = $16,
+                updated_at = NOW()
+            WHERE id = $1 AND organization_id = $17
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(shift_type.id)
+        .bind(&shift_type.code)
+        .bind(&shift_type.description)
+        .bind(shift_type.category)
+        .bind(&shift_type.color_hex)
+        .bind(&shift_type.icon)
+        .bind(shift_type.duration_hours)
+        .bind(shift_type.night_hours)
+        .bind(shift_type.start_time)
+        .bind(shift_type.end_time)
+        .bind(shift_type.is_countable)
+        .bind(shift_type.requires_recovery)
+        .bind(shift_type.is_holiday_indicator)
+        .bind(shift_type.is_rest_day)
+        .bind(shift_type.display_order)
+        .bind(shift_type.is_active)
+        .bind(shift_type.organization_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("shift type {}", shift_type.code)))?
+        .ok_or_else(|| RepositoryError::NotFound(format!("shift type {}", shift_type.id)))
+    }
+
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let result = sqlx::query("DELETE FROM shift_types WHERE organization_id = $1 AND id = $2")
+            .bind(org_id)
+            .bind(id)
+            .execute(&self.db)
+            .await
+            .map_err(|e| write_error(e, &format!("shift type {}", id)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound(format!("shift type {}", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn find_versions(&self, org_id: Uuid) -> RepoResult<Vec<ShiftTypeVersion>> {
+        Ok(planning_data::find_shift_type_versions(&self.db, org_id).await?)
+    }
+
+    async fn save_version(&self, version: &ShiftTypeVersion) -> RepoResult<ShiftTypeVersion> {
+        sqlx::query_as(
+            r#"
+            INSERT INTO shift_type_versions (
+                id, shift_type_id, organization_id, effective_from, category,
+                duration_hours, night_hours,
+                is_countable, requires_recovery, is_holiday_indicator, is_rest_day
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (shift_type_id, effective_from) DO UPDATE
+            SET category = EXCLUDED.category,
+                duration_hours = EXCLUDED.duration_hours,
+                night_hours = EXCLUDED.night_hours,
+                is_countable = EXCLUDED.is_countable,
+                requires_recovery = EXCLUDED.requires_recovery,
+                is_holiday_indicator = EXCLUDED.is_holiday_indicator,
+                is_rest_day = EXCLUDED.is_rest_day
+            RETURNING
+                id, shift_type_id, organization_id, effective_from, category,
+                duration_hours::FLOAT8 AS duration_hours, night_hours::FLOAT8 AS night_hours,
+                is_countable, requires_recovery, is_holiday_indicator, is_rest_day,
+                COALESCE(created_at, NOW()) AS created_at
+            "#,
+        )
+        .bind(version.id)
+        .bind(version.shift_type_id)
+        .bind(version.organization_id)
+        .bind(version.effective_from)
+        .bind(version.category)
+        .bind(version.duration_hours)
+        .bind(version.night_hours)
+        .bind(version.is_countable)
+        .bind(version.requires_recovery)
+        .bind(version.is_holiday_indicator)
+        .bind(version.is_rest_day)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("shift type version {}", version.effective_from)))
+    }
+
+    async fn find_code_grammar(&self, org_id: Uuid) -> RepoResult<ShiftCodeGrammar> {
+        Ok(planning_data::find_shift_code_grammar(&self.db, org_id).await?)
+    }
+}
@@ -0,0 +1,187 @@
+//! User Repository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::application::ports::{RepoResult, RepositoryError, UserRepository};
+use crate::domain::entities::User;
+
+use super::write_error;
+
+const COLUMNS: &str = r#"
+    id, organization_id, role_id, email, password_hash,
+    first_name, last_name, matricule, avatar_url, phone,
+    COALESCE(cn_entitlement, 20) AS cn_entitlement,
+    COALESCE(jc_entitlement, 10) AS jc_entitlement,
+    COALESCE(cn_carryover, 0) AS cn_carryover,
+    COALESCE(jc_carryover, 0) AS jc_carryover,
+    COALESCE(is_active, true) AS is_active,
+    email_verified_at, last_login_at, created_at, updated_at
+"#;
+
+/// Postgres user repository
+pub struct PgUserRepository {
+    db: PgPool,
+}
+
+impl PgUserRepository {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn find_by_id(&self, org_id: Uuid, id: Uuid) -> RepoResult<Option<User>> {
+        let user = sqlx::query_as(&format!(
+            "SELECT {} FROM users WHERE organization_id = $1 AND id = $2",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn find_by_email(&self, org_id: Uuid, email: &str) -> RepoResult<Option<User>> {
+        let user = sqlx::query_as(&format!(
+            "SELECT {} FROM users WHERE organization_id = $1 AND LOWER(email) = LOWER($2)",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(email)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn find_all(&self, org_id: Uuid) -> RepoResult<Vec<User>> {
+        let users = sqlx::query_as(&format!(
+            "SELECT {} FROM users WHERE organization_id = $1 ORDER BY last_name, first_name",
+            COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(users)
+    }
+
+    async fn find_active(&self, org_id: Uuid) -> RepoResult<Vec<User>> {
+        let users = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM users
+            WHERE organization_id = $1 AND is_active = true
+            ORDER BY last_name, first_name
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(users)
+    }
+
+    async fn create(&self, user: &User) -> RepoResult<User> {
+        sqlx::query_as(&format!(
+            r#"
+            INSERT INTO users (
+                id, organization_id, role_id, email, password_hash,
+                first_name, last_name, matricule, avatar_url, phone,
+                cn_entitlement, jc_entitlement, cn_carryover, jc_carryover, is_active
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(user.id)
+        .bind(user.organization_id)
+        .bind(user.role_id)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(&user.matricule)
+        .bind(&user.avatar_url)
+        .bind(&user.phone)
+        .bind(user.cn_entitlement)
+        .bind(user.jc_entitlement)
+        .bind(user.cn_carryover)
+        .bind(user.jc_carryover)
+        .bind(user.is_active)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("user {}", user.email)))
+    }
+
+    async fn update(&self, user: &User) -> RepoResult<User> {
+        sqlx::query_as(&format!(
+            r#"
+            UPDATE users
+            SET role_id = $2, email = $3, first_name = $4, last_name = $5,
+                matricule = $6, avatar_url = $7, phone = $8,
+                cn_entitlement = $9, jc_entitlement = $10,
+                cn_carryover = $11, jc_carryover = $12,
+                is_active = $13, updated_at = NOW()
+            WHERE id = $1 AND organization_id = $14
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(user.id)
+        .bind(user.role_id)
+        .bind(&user.email)
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(&user.matricule)
+        .bind(&user.avatar_url)
+        .bind(&user.phone)
+        .bind(user.cn_entitlement)
+        .bind(user.jc_entitlement)
+        .bind(user.cn_carryover)
+        .bind(user.jc_carryover)
+        .bind(user.is_active)
+        .bind(user.organization_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| write_error(e, &format!("user {}", user.email)))?
+        .ok_or_else(|| RepositoryError::NotFound(format!("user {}", user.id)))
+    }
+
+    /// Deactivate a user (schedules and history are kept)
+    async fn delete(&self, org_id: Uuid, id: Uuid) -> RepoResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users SET is_active = false, updated_at = NOW()
+            WHERE organization_id = $1 AND id = $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(id)
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound(format!("user {}", id)));
+        }
+
+        Ok(())
+    }
+    async fn count_by_role(&self, org_id: Uuid, role_id: Uuid) -> RepoResult<i64> {
+        let (count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM users WHERE organization_id = $1 AND role_id = $2")
+                .bind(org_id)
+                .bind(role_id)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,100 @@
+//! Postgres Test Database
+//!
+//! Tests of the Postgres adapters run in a schema of their own, migrated
+//! from packages/db, on the server of `TEST_DATABASE_URL`. They are
+//! ignored by default: `cargo test -- --ignored`.
+
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::Executor;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+const MIGRATIONS: &[&str] = &[
+    include_str!("../../../../db/src/migrations/001_initial_schema.sql"),
+    include_str!("../../../../db/src/migrations/002_rust_period_balances.sql"),
+    include_str!("../../../../db/src/migrations/003_hour_bank.sql"),
+    include_str!("../../../../db/src/migrations/004_calendar_feeds.sql"),
+    include_str!("../../../../db/src/migrations/005_payroll_exports.sql"),
+    include_str!("../../../../db/src/migrations/006_custom_holidays.sql"),
+    include_str!("../../../../db/src/migrations/007_shift_type_versions.sql"),
+    include_str!("../../../../db/src/migrations/008_role_permissions.sql"),
+    include_str!("../../../../db/src/migrations/009_refresh_token_rotation.sql"),
+];
+
+/// Extensions are shared by every schema: created once, in public
+static EXTENSIONS: OnceCell<()> = OnceCell::const_new();
+
+/// Migrated schema of a test
+pub struct TestDatabase {
+    pub db: PgPool,
+    schema: String,
+}
+
+impl TestDatabase {
+    /// Create and migrate a schema for the test
+    pub async fn new() -> Self {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let admin = PgPool::connect(&url).await.unwrap();
+
+        EXTENSIONS
+            .get_or_init(|| async {
+                sqlx::raw_sql(
+                    r#"
+                    CREATE EXTENSION IF NOT EXISTS "uuid-ossp" SCHEMA public;
+                    CREATE EXTENSION IF NOT EXISTS "pgcrypto" SCHEMA public;
+                    "#,
+                )
+                .execute(&admin)
+                .await
+                .unwrap();
+            })
+            .await;
+
+        let schema = format!("test_{}", Uuid::new_v4().simple());
+        admin
+            .execute(format!("CREATE SCHEMA {}", schema).as_str())
+            .await
+            .unwrap();
+        admin.close().await;
+
+        let search_path = format!("SET search_path TO {}, public", schema);
+        let db = PgPoolOptions::new()
+            .max_connections(4)
+            .after_connect(move |conn, _| {
+                let search_path = search_path.clone();
+                Box::pin(async move { conn.execute(search_path.as_str()).await.map(|_| ()) })
+            })
+            .connect(&url)
+            .await
+            .unwrap();
+
+        for migration in MIGRATIONS {
+            sqlx::raw_sql(migration).execute(&db).await.unwrap();
+        }
+
+        Self { db, schema }
+    }
+
+    /// Create an organization anchored on 2026-01-12
+    pub async fn organization(&self) -> Uuid {
+        let slug = format!("org-{}", Uuid::new_v4().simple());
+        let (id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO organizations (name, slug, year_start_date) VALUES ($1, $1, '2026-01-12') RETURNING id",
+        )
+        .bind(slug)
+        .fetch_one(&self.db)
+        .await
+        .unwrap();
+
+        id
+    }
+
+    /// Drop the schema of the test
+    pub async fn drop(self) {
+        self.db
+            .execute(format!("DROP SCHEMA {} CASCADE", self.schema).as_str())
+            .await
+            .unwrap();
+        self.db.close().await;
+    }
+}
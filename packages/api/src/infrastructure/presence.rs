@@ -0,0 +1,380 @@
+//! Presence Registry
+//!
+//! In-process registry of the planners editing each period and of their
+//! soft edit locks. Entries expire on their own (missed heartbeats, lock
+//! TTL); nothing is persisted.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::edit_lock::{EditLock, EditLockMode, EditLockPolicy, Presence};
+use crate::domain::entities::schedule::CreateSchedule;
+use crate::domain::entities::{Period, User};
+use crate::infrastructure::events::PlanningEvent;
+use crate::infrastructure::persistence::planning_data;
+use crate::infrastructure::AppState;
+
+/// Authenticated planner acting on the registry
+///
+/// Only built from the user row of the request's token (see the `Editor`
+/// extractor), so a request cannot act as another planner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Editor {
+    id: Uuid,
+    organization_id: Uuid,
+    name: String,
+}
+
+impl Editor {
+    /// Name shown to the other editors
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<&User> for Editor {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            organization_id: user.organization_id,
+            name: user.full_name(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Editors per (organization, period)
+    presence: HashMap<(Uuid, Uuid), Vec<Presence>>,
+    /// Locks per organization
+    locks: HashMap<Uuid, Vec<EditLock>>,
+}
+
+impl Registry {
+    fn prune(&mut self) {
+        let now = Utc::now();
+        for editors in self.presence.values_mut() {
+            editors.retain(|p| !p.is_expired(now));
+        }
+        self.presence.retain(|_, editors| !editors.is_empty());
+        for locks in self.locks.values_mut() {
+            locks.retain(|l| !l.is_expired(now));
+        }
+        self.locks.retain(|_, locks| !locks.is_empty());
+    }
+}
+
+/// Registry of editors and edit locks
+#[derive(Clone, Default)]
+pub struct PresenceRegistry {
+    inner: Arc<Mutex<Registry>>,
+}
+
+impl PresenceRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn registry(&self) -> std::sync::MutexGuard<'_, Registry> {
+        let mut registry = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        registry.prune();
+        registry
+    }
+
+    /// Record a heartbeat of an editor on a period
+    ///
+    /// Returns the editors of the period and whether the set changed
+    /// (new editor or different rows).
+    pub fn heartbeat(
+        &self,
+        period_id: Uuid,
+        editor: &Editor,
+        user_ids: Vec<Uuid>,
+    ) -> (Vec<Presence>, bool) {
+        let mut registry = self.registry();
+        let editors = registry
+            .presence
+            .entry((editor.organization_id, period_id))
+            .or_default();
+
+        let changed = match editors.iter_mut().find(|p| p.editor_id == editor.id) {
+            Some(presence) => {
+                let changed = presence.user_ids != user_ids;
+                presence.user_ids = user_ids;
+                presence.last_seen = Utc::now();
+                changed
+            }
+            None => {
+                editors.push(Presence {
+                    editor_id: editor.id,
+                    editor_name: editor.name().to_string(),
+                    period_id,
+                    user_ids,
+                    last_seen: Utc::now(),
+                });
+                true
+            }
+        };
+
+        (editors.clone(), changed)
+    }
+
+    /// Remove an editor from a period; returns false if it was not there
+    pub fn leave(&self, period_id: Uuid, editor: &Editor) -> bool {
+        let mut registry = self.registry();
+        let Some(editors) = registry
+            .presence
+            .get_mut(&(editor.organization_id, period_id))
+        else {
+            return false;
+        };
+        let before = editors.len();
+        editors.retain(|p| p.editor_id != editor.id);
+        before != editors.len()
+    }
+
+    /// Editors of a period
+    pub fn editors(&self, org_id: Uuid, period_id: Uuid) -> Vec<Presence> {
+        self.registry()
+            .presence
+            .get(&(org_id, period_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Active locks of an organization, optionally for one period
+    pub fn locks(&self, org_id: Uuid, period_id: Option<Uuid>) -> Vec<EditLock> {
+        self.registry()
+            .locks
+            .get(&org_id)
+            .map(|locks| {
+                locks
+                    .iter()
+                    .filter(|l| period_id.is_none_or(|id| l.period_id == id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Take a lock on a period or some of its agent rows
+    ///
+    /// A holder taking a lock overlapping its own replaces it. Returns the
+    /// conflicting lock when another planner holds overlapping cells.
+    pub fn acquire(
+        &self,
+        period: &Period,
+        user_ids: Vec<Uuid>,
+        holder: &Editor,
+        ttl: Duration,
+    ) -> Result<EditLock, Box<EditLock>> {
+        debug_assert_eq!(period.organization_id, holder.organization_id);
+        let holder_id = holder.id;
+        let mut registry = self.registry();
+        let locks = registry.locks.entry(period.organization_id).or_default();
+
+        if let Some(conflict) = locks
+            .iter()
+            .find(|l| l.holder_id != holder_id && l.overlaps(period.id, &user_ids))
+        {
+            return Err(Box::new(conflict.clone()));
+        }
+
+        locks.retain(|l| !(l.holder_id == holder_id && l.overlaps(period.id, &user_ids)));
+
+        let now = Utc::now();
+        let lock = EditLock {
+            id: Uuid::new_v4(),
+            organization_id: period.organization_id,
+            period_id: period.id,
+            start_date: period.start_date,
+            end_date: period.end_date,
+            user_ids,
+            holder_id,
+            holder_name: holder.name().to_string(),
+            created_at: now,
+            expires_at: now + ttl,
+        };
+        locks.push(lock.clone());
+
+        Ok(lock)
+    }
+
+    /// Release a lock of a holder; returns the released lock
+    pub fn release(&self, lock_id: Uuid, holder: &Editor) -> Option<EditLock> {
+        let mut registry = self.registry();
+        let locks = registry.locks.get_mut(&holder.organization_id)?;
+        let index = locks
+            .iter()
+            .position(|l| l.id == lock_id && l.holder_id == holder.id)?;
+        Some(locks.remove(index))
+    }
+
+    /// Locks of other planners covering any of the cells
+    pub fn conflicts(&self, cells: &[(Uuid, NaiveDate)], editor: &Editor) -> Vec<EditLock> {
+        self.locks(editor.organization_id, None)
+            .into_iter()
+            .filter(|l| l.holder_id != editor.id)
+            .filter(|l| {
+                cells
+                    .iter()
+                    .any(|(user_id, date)| l.covers(*user_id, *date))
+            })
+            .collect()
+    }
+}
+
+/// Publish the editors and locks of a period
+pub fn publish(state: &AppState, period: &Period) {
+    state.events.publish(PlanningEvent::PresenceChanged {
+        organization_id: period.organization_id,
+        period_id: period.id,
+        start_date: period.start_date,
+        end_date: period.end_date,
+        editors: state.presence.editors(period.organization_id, period.id),
+        locks: state
+            .presence
+            .locks(period.organization_id, Some(period.id)),
+    });
+}
+
+/// Check a schedule write of an editor against the locks of other planners
+///
+/// Returns the locks to warn about, or the blocking locks as an error when
+/// the organization blocks locked writes.
+pub async fn check_write(
+    state: &AppState,
+    editor: &Editor,
+    entries: &[CreateSchedule],
+) -> Result<Vec<EditLock>, Vec<EditLock>> {
+    let cells: Vec<_> = entries.iter().map(|e| (e.user_id, e.date)).collect();
+    let conflicts = state.presence.conflicts(&cells, editor);
+    if conflicts.is_empty() {
+        return Ok(conflicts);
+    }
+
+    let policy = planning_data::find_edit_lock_policy(&state.db, editor.organization_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to load edit lock policy: {:?}", e);
+            EditLockPolicy::default()
+        });
+
+    match policy.mode {
+        EditLockMode::Warn => Ok(conflicts),
+        EditLockMode::Block => Err(conflicts),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period() -> Period {
+        Period {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            year: 2026,
+            number: 5,
+            start_date: NaiveDate::from_ymd_opt(2026, 5, 4).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 5, 31).unwrap(),
+            hour_quota: 160,
+            closed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn editor(period: &Period, name: &str) -> Editor {
+        Editor {
+            id: Uuid::new_v4(),
+            organization_id: period.organization_id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_heartbeat_and_leave() {
+        let registry = PresenceRegistry::new();
+        let period = period();
+        let alice = editor(&period, "Alice");
+
+        let (editors, changed) = registry.heartbeat(period.id, &alice, Vec::new());
+        assert_eq!(editors.len(), 1);
+        assert_eq!(editors[0].editor_name, "Alice");
+        assert!(changed);
+
+        let (_, changed) = registry.heartbeat(period.id, &alice, Vec::new());
+        assert!(!changed);
+
+        // Another planner cannot make Alice leave
+        assert!(!registry.leave(period.id, &editor(&period, "Bob")));
+        assert!(registry.leave(period.id, &alice));
+        assert!(registry
+            .editors(period.organization_id, period.id)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_locks_conflict_between_planners() {
+        let registry = PresenceRegistry::new();
+        let period = period();
+        let (alice, bob) = (editor(&period, "Alice"), editor(&period, "Bob"));
+        let (agent, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let ttl = Duration::minutes(15);
+
+        let lock = registry.acquire(&period, vec![agent], &alice, ttl).unwrap();
+
+        // Bob cannot lock the same row, but can lock another one
+        assert_eq!(
+            registry
+                .acquire(&period, Vec::new(), &bob, ttl)
+                .unwrap_err()
+                .id,
+            lock.id
+        );
+        assert!(registry.acquire(&period, vec![other], &bob, ttl).is_ok());
+
+        // Alice re-locking her row replaces her lock
+        registry.acquire(&period, vec![agent], &alice, ttl).unwrap();
+        assert_eq!(registry.locks(period.organization_id, None).len(), 2);
+
+        let cell = [(agent, period.start_date)];
+        assert_eq!(registry.conflicts(&cell, &bob).len(), 1);
+        assert!(registry.conflicts(&cell, &alice).is_empty());
+    }
+
+    #[test]
+    fn test_release_by_holder_only() {
+        let registry = PresenceRegistry::new();
+        let period = period();
+        let alice = editor(&period, "Alice");
+        let lock = registry
+            .acquire(&period, Vec::new(), &alice, Duration::minutes(5))
+            .unwrap();
+
+        assert!(registry.release(lock.id, &editor(&period, "Bob")).is_none());
+        assert!(registry.release(lock.id, &alice).is_some());
+        assert!(registry.locks(period.organization_id, None).is_empty());
+    }
+
+    #[test]
+    fn test_expired_locks_are_dropped() {
+        let registry = PresenceRegistry::new();
+        let period = period();
+
+        registry
+            .acquire(
+                &period,
+                Vec::new(),
+                &editor(&period, "Alice"),
+                Duration::zero(),
+            )
+            .unwrap();
+
+        assert!(registry.locks(period.organization_id, None).is_empty());
+    }
+}
@@ -4,20 +4,36 @@
 
 use std::sync::Arc;
 
+use crate::application::ports::Repositories;
 use crate::infrastructure::config::Settings;
+use crate::infrastructure::events::EventHub;
+use crate::infrastructure::persistence::repositories;
+use crate::infrastructure::presence::PresenceRegistry;
 
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
     /// Database connection pool
     pub db: sqlx::PgPool,
+    /// Repository adapters used by the application services
+    pub repositories: Repositories,
     /// Application settings
     pub settings: Arc<Settings>,
+    /// Live planning events
+    pub events: EventHub,
+    /// Planner presence and edit locks
+    pub presence: PresenceRegistry,
 }
 
 impl AppState {
     /// Create a new AppState
     pub fn new(db: sqlx::PgPool, settings: Arc<Settings>) -> Self {
-        Self { db, settings }
+        Self {
+            repositories: repositories::postgres(&db),
+            db,
+            settings,
+            events: EventHub::new(),
+            presence: PresenceRegistry::new(),
+        }
     }
 }
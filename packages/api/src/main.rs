@@ -82,7 +82,7 @@ fn build_router(state: AppState) -> Router {
         // Health check
         .route("/health", axum::routing::get(api::handlers::health::health_check))
         // API v1 routes
-        .nest("/api/v1", api::routes::api_routes())
+        .nest("/api/v1", api::routes::api_routes(state.clone()))
         // Middleware
         .layer(TraceLayer::new_for_http())
         .layer(cors)
@@ -0,0 +1,15 @@
+-- PlanningOS Database Schema
+-- Version: 1.1.0
+-- Description: Period balances are now computed by the API (BalanceCalculator)
+
+-- ============================================
+-- FUNCTIONS
+-- ============================================
+
+-- Balance aggregation and quota validation moved to Rust so that the rules
+-- live in a single place. Rows in period_balances are written by the API
+-- after every schedule change.
+DROP FUNCTION IF EXISTS recalculate_period_balance(UUID, UUID);
+DROP FUNCTION IF EXISTS validate_period_quotas(UUID, UUID);
+
+COMMENT ON TABLE period_balances IS 'Cached period statistics for quota validation (computed by the API)';
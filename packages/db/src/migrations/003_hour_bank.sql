@@ -0,0 +1,45 @@
+-- PlanningOS Database Schema
+-- Version: 1.2.0
+-- Description: Hour bank ledger and period closing
+
+-- ============================================
+-- TABLE: periods (closing)
+-- ============================================
+
+ALTER TABLE periods ADD COLUMN IF NOT EXISTS closed_at TIMESTAMPTZ;
+ALTER TABLE periods ADD COLUMN IF NOT EXISTS closed_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+COMMENT ON COLUMN periods.closed_at IS 'Set when the period is closed (hour bank recorded, schedules frozen)';
+
+-- ============================================
+-- TABLE: hour_bank_entries
+-- Ledger of hours above/below quota, one row per agent and closed period
+-- Policy: organizations.config -> hourBank { maxBalance, minBalance, excess }
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS hour_bank_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    period_id UUID NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+
+    -- Period figures at closing
+    total_hours DECIMAL(6,2) NOT NULL,
+    hour_quota INTEGER NOT NULL,
+    delta DECIMAL(6,2) NOT NULL,         -- total_hours - hour_quota
+
+    -- Policy outcome
+    paid_out DECIMAL(6,2) NOT NULL DEFAULT 0,
+    forfeited DECIMAL(6,2) NOT NULL DEFAULT 0,
+    balance DECIMAL(7,2) NOT NULL,       -- Running balance carried over
+
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+
+    UNIQUE(user_id, period_id)
+);
+
+COMMENT ON TABLE hour_bank_entries IS 'Hour bank ledger (overtime carry-over across periods)';
+COMMENT ON COLUMN hour_bank_entries.forfeited IS 'Hours dropped by the caps (positive credit or negative debt)';
+
+CREATE INDEX IF NOT EXISTS idx_hour_bank_entries_user ON hour_bank_entries(user_id);
+CREATE INDEX IF NOT EXISTS idx_hour_bank_entries_period ON hour_bank_entries(period_id);
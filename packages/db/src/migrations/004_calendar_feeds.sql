@@ -0,0 +1,38 @@
+-- PlanningOS Database Schema
+-- Version: 1.3.0
+-- Description: Shift times and iCalendar subscription tokens
+
+-- ============================================
+-- TABLE: shift_types (times)
+-- ============================================
+
+-- Local start/end times used by calendar feeds. Codes without times
+-- (rest days, leave) are rendered as all-day events.
+ALTER TABLE shift_types ADD COLUMN IF NOT EXISTS start_time TIME;
+ALTER TABLE shift_types ADD COLUMN IF NOT EXISTS end_time TIME;
+
+COMMENT ON COLUMN shift_types.end_time IS 'Earlier than start_time for shifts ending the next day (nights)';
+
+-- ============================================
+-- TABLE: calendar_tokens
+-- Secret URL tokens for ICS subscriptions (no JWT in calendar apps)
+-- Only the SHA-256 of the token is stored.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS calendar_tokens (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Agent of the feed, NULL for the team feed
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    label VARCHAR(100),
+
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+COMMENT ON TABLE calendar_tokens IS 'Revocable ICS feed subscription tokens';
+
+CREATE INDEX IF NOT EXISTS idx_calendar_tokens_user ON calendar_tokens(user_id);
@@ -0,0 +1,40 @@
+-- PlanningOS Database Schema
+-- Version: 1.4.0
+-- Description: Payroll export batches
+
+-- ============================================
+-- TABLE: payroll_batches
+-- One row per payroll export, with the generated file
+-- Wage code mapping: organizations.config -> payroll
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS payroll_batches (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    format VARCHAR(10) NOT NULL CHECK (format IN ('csv', 'fixed')),
+    line_count INTEGER NOT NULL,
+    content TEXT NOT NULL,
+
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+COMMENT ON TABLE payroll_batches IS 'Payroll exports of closed periods';
+COMMENT ON COLUMN payroll_batches.content IS 'File sent to payroll, kept for re-download';
+
+CREATE INDEX IF NOT EXISTS idx_payroll_batches_org ON payroll_batches(organization_id);
+
+-- ============================================
+-- TABLE: payroll_batch_periods
+-- Periods sent in each batch
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS payroll_batch_periods (
+    batch_id UUID NOT NULL REFERENCES payroll_batches(id) ON DELETE CASCADE,
+    period_id UUID NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (batch_id, period_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payroll_batch_periods_period ON payroll_batch_periods(period_id);
@@ -0,0 +1,14 @@
+-- PlanningOS Database Schema
+-- Version: 1.5.0
+-- Description: Custom holidays
+
+-- ============================================
+-- TABLE: holidays (custom)
+-- ============================================
+
+-- Holidays generated from the calendar (fixed dates and Easter-derived)
+-- are regenerated freely; custom ones are entered by hand (local feast
+-- days, bridge days) and never touched by generation.
+ALTER TABLE holidays ADD COLUMN IF NOT EXISTS is_custom BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN holidays.is_custom IS 'True for holidays entered by hand rather than generated';
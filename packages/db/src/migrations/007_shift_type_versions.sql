@@ -0,0 +1,35 @@
+-- PlanningOS Database Schema
+-- Version: 1.6.0
+-- Description: Effective-dated shift type definitions
+
+-- ============================================
+-- TABLE: shift_type_versions
+-- Hour attributes of a shift type, each in force from a date
+-- The shift_types row mirrors the latest version
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS shift_type_versions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    shift_type_id UUID NOT NULL REFERENCES shift_types(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+
+    effective_from DATE NOT NULL,
+
+    -- Versioned attributes (same meaning as in shift_types)
+    category shift_category NOT NULL,
+    duration_hours DECIMAL(4,2) NOT NULL,
+    night_hours DECIMAL(4,2) NOT NULL,
+    is_countable BOOLEAN NOT NULL,
+    requires_recovery BOOLEAN NOT NULL,
+    is_holiday_indicator BOOLEAN NOT NULL,
+    is_rest_day BOOLEAN NOT NULL,
+
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+
+    UNIQUE (shift_type_id, effective_from)
+);
+
+COMMENT ON TABLE shift_type_versions IS 'Shift type definitions over time, used for past balances';
+COMMENT ON COLUMN shift_type_versions.effective_from IS 'First schedule date using this definition';
+
+CREATE INDEX IF NOT EXISTS idx_shift_type_versions_org ON shift_type_versions(organization_id);
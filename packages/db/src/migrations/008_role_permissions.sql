@@ -0,0 +1,108 @@
+-- PlanningOS Database Schema
+-- Version: 1.7.0
+-- Description: Permission catalogue of the system and custom roles
+
+-- ============================================
+-- SYSTEM ROLES
+-- Permissions are now enforced by the API; rewrite the system roles
+-- with the catalogue names (resource:action, ":own" for the holder's
+-- own rows). Custom roles are mapped below.
+-- ============================================
+
+UPDATE roles
+SET permissions = '[
+    "users:read", "users:manage",
+    "roles:read", "roles:manage",
+    "schedules:read", "schedules:write",
+    "shift_types:read", "shift_types:manage",
+    "periods:read", "periods:write", "periods:lock",
+    "balances:read",
+    "holidays:read", "holidays:manage",
+    "statistics:read",
+    "calendar:manage",
+    "payroll:manage"
+]'::JSONB
+WHERE is_system AND name = 'admin';
+
+UPDATE roles
+SET permissions = '[
+    "users:read",
+    "roles:read",
+    "schedules:read", "schedules:write",
+    "shift_types:read",
+    "periods:read", "periods:lock",
+    "balances:read",
+    "holidays:read",
+    "statistics:read",
+    "calendar:manage"
+]'::JSONB
+WHERE is_system AND name = 'planner';
+
+UPDATE roles
+SET permissions = '[
+    "schedules:read:own",
+    "shift_types:read",
+    "periods:read",
+    "balances:read:own",
+    "holidays:read",
+    "statistics:read:own",
+    "calendar:manage:own"
+]'::JSONB
+WHERE is_system AND name = 'agent';
+
+-- ============================================
+-- CUSTOM ROLES
+-- Map the names of earlier releases to the catalogue: write and delete
+-- grants of managed resources become "manage", schedule deletion is part
+-- of "schedules:write". Leave requests, audit and settings have no
+-- permission any more and are dropped.
+-- ============================================
+
+UPDATE roles r
+SET permissions = (
+    SELECT COALESCE(jsonb_agg(DISTINCT mapped), '[]'::JSONB)
+    FROM (
+        SELECT CASE grant_name
+            WHEN 'users:write' THEN 'users:manage'
+            WHEN 'users:delete' THEN 'users:manage'
+            WHEN 'roles:write' THEN 'roles:manage'
+            WHEN 'roles:delete' THEN 'roles:manage'
+            WHEN 'schedules:delete' THEN 'schedules:write'
+            WHEN 'shift_types:write' THEN 'shift_types:manage'
+            WHEN 'shift_types:delete' THEN 'shift_types:manage'
+            WHEN 'holidays:write' THEN 'holidays:manage'
+            WHEN 'holidays:delete' THEN 'holidays:manage'
+            ELSE grant_name
+        END AS mapped
+        FROM jsonb_array_elements_text(r.permissions) AS grant_name
+        WHERE split_part(grant_name, ':', 1) NOT IN ('leave_requests', 'audit', 'settings')
+    ) grants
+)
+WHERE NOT r.is_system;
+
+-- Any other name would be ignored by the API and silently revoke access:
+-- stop the migration so that the role is fixed by hand
+DO $$
+DECLARE
+    v_unknown TEXT;
+BEGIN
+    SELECT string_agg(DISTINCT r.name || ' (' || grant_name || ')', ', ')
+    INTO v_unknown
+    FROM roles r, jsonb_array_elements_text(r.permissions) AS grant_name
+    WHERE grant_name NOT IN (
+        'users:read', 'users:manage',
+        'roles:read', 'roles:manage',
+        'schedules:read', 'schedules:read:own', 'schedules:write',
+        'shift_types:read', 'shift_types:manage',
+        'periods:read', 'periods:write', 'periods:lock',
+        'balances:read', 'balances:read:own',
+        'holidays:read', 'holidays:manage',
+        'statistics:read', 'statistics:read:own',
+        'calendar:manage', 'calendar:manage:own',
+        'payroll:manage'
+    );
+
+    IF v_unknown IS NOT NULL THEN
+        RAISE EXCEPTION 'Roles hold permissions outside the catalogue: %', v_unknown;
+    END IF;
+END $$;
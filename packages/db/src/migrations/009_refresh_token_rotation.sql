@@ -0,0 +1,22 @@
+-- PlanningOS Database Schema
+-- Version: 1.8.0
+-- Description: Refresh token rotation and session families
+
+-- ============================================
+-- TABLE: refresh_tokens
+-- Each login starts a family (session); every refresh rotates the
+-- token within it. A rotated token presented again revokes the family.
+-- ============================================
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN IF NOT EXISTS family_id UUID,
+    -- Set when the token was exchanged for its successor
+    ADD COLUMN IF NOT EXISTS rotated_at TIMESTAMPTZ;
+
+UPDATE refresh_tokens SET family_id = id WHERE family_id IS NULL;
+
+ALTER TABLE refresh_tokens ALTER COLUMN family_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens(family_id);
+
+COMMENT ON COLUMN refresh_tokens.family_id IS 'Session of the token (shared by its rotations)';
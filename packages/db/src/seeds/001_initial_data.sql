@@ -34,16 +34,16 @@ VALUES
         'admin',
         'Administrateur',
         '[
-            "users:read", "users:write", "users:delete",
-            "roles:read", "roles:write",
-            "schedules:read", "schedules:write", "schedules:delete",
-            "shift_types:read", "shift_types:write", "shift_types:delete",
-            "periods:read", "periods:write",
-            "holidays:read", "holidays:write", "holidays:delete",
-            "leave_requests:read", "leave_requests:approve",
+            "users:read", "users:manage",
+            "roles:read", "roles:manage",
+            "schedules:read", "schedules:write",
+            "shift_types:read", "shift_types:manage",
+            "periods:read", "periods:write", "periods:lock",
+            "balances:read",
+            "holidays:read", "holidays:manage",
             "statistics:read",
-            "audit:read",
-            "settings:read", "settings:write"
+            "calendar:manage",
+            "payroll:manage"
         ]'::JSONB,
         true
     ),
@@ -55,12 +55,14 @@ VALUES
         'Planificateur',
         '[
             "users:read",
+            "roles:read",
             "schedules:read", "schedules:write",
             "shift_types:read",
-            "periods:read",
+            "periods:read", "periods:lock",
+            "balances:read",
             "holidays:read",
-            "leave_requests:read", "leave_requests:approve",
-            "statistics:read"
+            "statistics:read",
+            "calendar:manage"
         ]'::JSONB,
         true
     ),
@@ -74,9 +76,10 @@ VALUES
             "schedules:read:own",
             "shift_types:read",
             "periods:read",
+            "balances:read:own",
             "holidays:read",
-            "leave_requests:read:own", "leave_requests:write:own",
-            "statistics:read:own"
+            "statistics:read:own",
+            "calendar:manage:own"
         ]'::JSONB,
         true
     )